@@ -0,0 +1,96 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use lib_genetic_algorithm::{
+	Chromosome, CrossoverMethod, CrossoverVariant, GaussianMutation, GeneticAlgorithm, Individual,
+	MutationMethod, MutationVariant, RouletteWheelSelection, UniformCrossover,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// Roughly the chromosome length of the simulation's animals
+const CHROMOSOME_LEN: usize = 220;
+
+struct BenchIndividual {
+	chromosome: Chromosome,
+}
+
+impl Individual for BenchIndividual {
+	fn create(chromosome: Chromosome) -> Self {
+		Self { chromosome }
+	}
+
+	fn fitness(&self) -> f32 {
+		self.chromosome.iter().map(|gene| gene.abs()).sum()
+	}
+
+	fn chromosome(&self) -> &Chromosome {
+		&self.chromosome
+	}
+}
+
+fn evolve(c: &mut Criterion) {
+	let mut group = c.benchmark_group("evolve");
+
+	for population_size in [10, 100, 1000] {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+		let population: Vec<_> = (0..population_size)
+			.map(|_| {
+				let genes = (0..CHROMOSOME_LEN).map(|_| rng.gen_range(-1.0..=1.0)).collect();
+				BenchIndividual::create(genes)
+			})
+			.collect();
+
+		let mut ga = GeneticAlgorithm::new(
+			RouletteWheelSelection,
+			UniformCrossover,
+			GaussianMutation::new(0.01, 0.3),
+		);
+
+		group.bench_with_input(
+			BenchmarkId::from_parameter(population_size),
+			&population,
+			|b, population| b.iter(|| ga.evolve(&mut rng, black_box(population))),
+		);
+	}
+
+	group.finish();
+}
+
+// Breeds a single child, which is where the crossover and mutation methods
+// get called; boxed trait objects versus the enums `GeneticAlgorithm` uses
+fn dispatch(c: &mut Criterion) {
+	let mut group = c.benchmark_group("dispatch");
+	let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+	let parent_a: Chromosome = (0..CHROMOSOME_LEN).map(|_| rng.gen_range(-1.0..=1.0)).collect();
+	let parent_b: Chromosome = (0..CHROMOSOME_LEN).map(|_| rng.gen_range(-1.0..=1.0)).collect();
+
+	let boxed: (Box<dyn CrossoverMethod>, Box<dyn MutationMethod>) =
+		(Box::new(UniformCrossover), Box::new(GaussianMutation::new(0.01, 0.3)));
+
+	let variants = (
+		CrossoverVariant::from(UniformCrossover),
+		MutationVariant::from(GaussianMutation::new(0.01, 0.3)),
+	);
+
+	group.bench_function("boxed", |b| {
+		b.iter(|| {
+			let mut child = boxed.0.crossover(&mut rng, black_box(&parent_a), black_box(&parent_b));
+			boxed.1.mutate(&mut rng, &mut child);
+			child
+		})
+	});
+
+	group.bench_function("enum", |b| {
+		b.iter(|| {
+			let mut child = variants.0.crossover(&mut rng, black_box(&parent_a), black_box(&parent_b));
+			variants.1.mutate(&mut rng, &mut child);
+			child
+		})
+	});
+
+	group.finish();
+}
+
+criterion_group!(benches, evolve, dispatch);
+criterion_main!(benches);
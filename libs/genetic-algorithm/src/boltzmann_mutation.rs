@@ -0,0 +1,134 @@
+use crate::*;
+
+/// Mutation methods whose strength depends on a temperature that can be
+/// lowered as evolution goes on (i.e. simulated annealing).
+pub trait TemperatureMutationMethod: MutationMethod {
+	fn mutate_at_temperature(&self, rng: &mut dyn RngCore, child: &mut Chromosome, temperature: f32);
+
+	fn temperature(&self) -> f32;
+
+	/// Multiplies the temperature by `1 - cooling_rate`.
+	fn cool(&mut self, cooling_rate: f32);
+}
+
+/// `GaussianMutation` whose coefficient shrinks as it gets cooled down.
+#[derive(Clone, Debug)]
+pub struct BoltzmannMutation {
+	// The probability of a gene being mutated, 0 <= chance <= 1
+	chance: f32,
+	// Magnitude of the mutation at temperature 1.0
+	initial_coeff: f32,
+	// Starts at 1.0
+	temperature: f32,
+}
+
+impl BoltzmannMutation {
+	pub fn new(chance: f32, initial_coeff: f32) -> Self {
+		assert!((0.0..=1.0).contains(&chance));
+		assert!((0.0..=3.0).contains(&initial_coeff));
+
+		Self {
+			chance,
+			initial_coeff,
+			temperature: 1.0,
+		}
+	}
+
+	/// Magnitude of the mutation at the current temperature.
+	pub fn coeff(&self) -> f32 {
+		self.initial_coeff * self.temperature
+	}
+
+	pub fn cool(&mut self, cooling_rate: f32) {
+		assert!((0.0..=1.0).contains(&cooling_rate));
+
+		self.temperature *= 1.0 - cooling_rate;
+	}
+}
+
+impl TemperatureMutationMethod for BoltzmannMutation {
+	fn mutate_at_temperature(&self, rng: &mut dyn RngCore, child: &mut Chromosome, temperature: f32) {
+		let coeff = self.initial_coeff * temperature;
+
+		for gene in child.iter_mut() {
+			let sign = if rng.gen_bool(0.5) {-1.0} else {1.0};
+
+			if rng.gen_bool(self.chance as f64) {
+				*gene += sign * coeff * rng.gen::<f32>();
+			}
+		}
+	}
+
+	fn temperature(&self) -> f32 {
+		self.temperature
+	}
+
+	fn cool(&mut self, cooling_rate: f32) {
+		BoltzmannMutation::cool(self, cooling_rate);
+	}
+}
+
+impl MutationMethod for BoltzmannMutation {
+	fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+		self.mutate_at_temperature(rng, child, self.temperature);
+	}
+
+	fn as_temperature_mut(&mut self) -> Option<&mut dyn TemperatureMutationMethod> {
+		Some(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	#[test]
+	fn cool() {
+		let mut mutation = BoltzmannMutation::new(0.5, 2.0);
+
+		for _ in 0..100 {
+			mutation.cool(0.01);
+		}
+
+		let expected = 2.0 * 0.99f32.powi(100);
+		assert!((mutation.coeff() - expected).abs() <= expected * 0.01);
+	}
+
+	#[test]
+	fn mutation_shrinks_with_temperature() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mutation = BoltzmannMutation::new(1.0, 1.0);
+		let original: Chromosome = vec![0.0; 1000].into_iter().collect();
+
+		let mut distance = |temperature| {
+			let mut child = original.clone();
+			mutation.mutate_at_temperature(&mut rng, &mut child, temperature);
+			Chromosome::mutation_distance(&original, &child)
+		};
+
+		let hot = distance(1.0);
+		let cold = distance(0.1);
+
+		assert!(cold < hot * 0.2);
+		assert_eq!(distance(0.0), 0.0);
+	}
+
+	#[test]
+	fn genetic_algorithm_exposes_temperature() {
+		let mut ga = GeneticAlgorithm::new(
+			RouletteWheelSelection,
+			UniformCrossover,
+			BoltzmannMutation::new(0.5, 1.0),
+		);
+
+		ga.mutation_method_mut()
+			.as_temperature_mut()
+			.unwrap()
+			.cool(0.5);
+
+		let mutation = ga.mutation_method_mut().as_temperature_mut().unwrap();
+		assert_eq!(mutation.temperature(), 0.5);
+	}
+}
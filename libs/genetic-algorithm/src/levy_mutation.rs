@@ -0,0 +1,98 @@
+use crate::*;
+use rand_distr::{Distribution, Normal, StandardNormal};
+
+// Stability index of the Lévy distribution
+const BETA: f32 = 1.5;
+
+// Mantegna's sigma_u for BETA = 1.5:
+// (gamma(1 + b) * sin(pi * b / 2) / (gamma((1 + b) / 2) * b * 2^((b - 1) / 2)))^(1 / b)
+const SIGMA_U: f32 = 0.696_574_5;
+
+/// Adds Lévy-distributed steps to genes; most steps are small, but the
+/// occasional very large one helps escape local optima.
+#[derive(Clone, Debug)]
+pub struct LevyMutation {
+	// The probability of a gene being mutated, 0 <= chance <= 1
+	chance: f32,
+	// Scale of the mutation, alpha >= 0
+	alpha: f32,
+}
+
+impl LevyMutation {
+	pub fn new(chance: f32, alpha: f32) -> Self {
+		assert!((0.0..=1.0).contains(&chance));
+		assert!(alpha >= 0.0);
+		Self { chance, alpha }
+	}
+
+	// Mantegna's algorithm
+	fn sample(rng: &mut dyn RngCore) -> f32 {
+		let u: f32 = Normal::new(0.0, SIGMA_U).unwrap().sample(rng);
+		let v: f32 = StandardNormal.sample(rng);
+
+		u / v.abs().powf(1.0 / BETA)
+	}
+}
+
+impl MutationMethod for LevyMutation {
+	fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+		for gene in child.iter_mut() {
+			let sign = if rng.gen_bool(0.5) {-1.0} else {1.0};
+
+			if rng.gen_bool(self.chance as f64) {
+				*gene += sign * Self::sample(rng) * self.alpha;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn percentile(mut samples: Vec<f32>, percentile: f32) -> f32 {
+		samples.sort_by(|a, b| a.total_cmp(b));
+		samples[((samples.len() - 1) as f32 * percentile) as usize]
+	}
+
+	#[test]
+	fn has_heavier_tails_than_gaussian() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+		let levy: Vec<f32> = (0..100_000)
+			.map(|_| LevyMutation::sample(&mut rng).abs())
+			.collect();
+
+		let gaussian: Vec<f32> = (0..100_000)
+			.map(|_| StandardNormal.sample(&mut rng))
+			.map(|sample: f32| sample.abs())
+			.collect();
+
+		let levy = percentile(levy, 0.999);
+		let gaussian = percentile(gaussian, 0.999);
+
+		assert!(levy > 5.0 * gaussian, "levy = {}, gaussian = {}", levy, gaussian);
+	}
+
+	#[test]
+	fn given_zero_chance_does_not_change_the_original_chromosome() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut child: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+
+		LevyMutation::new(0.0, 1.0).mutate(&mut rng, &mut child);
+
+		assert_eq!(child.iter().copied().collect::<Vec<_>>(), vec![1.0, 2.0, 3.0]);
+	}
+
+	#[test]
+	fn given_max_chance_changes_every_gene() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut child: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+
+		LevyMutation::new(1.0, 1.0).mutate(&mut rng, &mut child);
+
+		assert!(child.iter().zip([1.0, 2.0, 3.0]).all(|(a, b)| *a != b));
+	}
+}
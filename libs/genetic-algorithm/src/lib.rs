@@ -1,35 +1,105 @@
+mod boltzmann_mutation;
+mod levy_mutation;
+mod multi_start;
+#[cfg(feature = "neural-network")]
+mod network;
+mod niching;
+mod normalized_selection;
+mod statistics;
+mod steady_state;
+#[cfg(test)]
+mod test_individual;
+mod variant;
+
+pub use self::{
+	boltzmann_mutation::*, levy_mutation::*, multi_start::*, niching::*, normalized_selection::*,
+	statistics::*, steady_state::*, variant::*,
+};
+#[cfg(feature = "neural-network")]
+pub use self::network::*;
+#[cfg(test)]
+pub(crate) use self::test_individual::*;
+use rand::distributions::WeightedError;
 use rand::seq::SliceRandom;
 use rand::{RngCore, Rng};
 use std::ops::Index;
 
 pub struct GeneticAlgorithm<S> {
 	selection_method: S,
-	crossover_method: Box<dyn CrossoverMethod>,
-	mutation_method: Box<dyn MutationMethod>,
+	crossover_method: CrossoverVariant,
+	mutation_method: MutationVariant,
 	generation: usize,
+	// Number of the fittest individuals copied over unchanged
+	elite_count: usize,
 }
 
 impl<S> GeneticAlgorithm<S>
 where 
 	S: SelectionMethod,
 {
+	/// Takes any built-in crossover and mutation method; custom ones go in
+	/// through `CrossoverVariant::custom()` and `MutationVariant::custom()`.
 	pub fn new(selection_method: S,
-		crossover_method: impl CrossoverMethod + 'static,
-		mutation_method: impl MutationMethod + 'static,
+		crossover_method: impl Into<CrossoverVariant>,
+		mutation_method: impl Into<MutationVariant>,
 	) -> Self {
 		Self { 
 			selection_method,
-			crossover_method: Box::new(crossover_method),
-			mutation_method: Box::new(mutation_method),
+			crossover_method: crossover_method.into(),
+			mutation_method: mutation_method.into(),
 			generation: 1,
+			elite_count: 0,
 		}
 	}
+
+	pub fn with_elitism(mut self, elite_count: usize) -> Self {
+		self.elite_count = elite_count;
+		self
+	}
+
+	pub fn mutation_method_mut(&mut self) -> &mut dyn MutationMethod {
+		&mut self.mutation_method
+	}
+
+	pub fn set_mutation_method(&mut self, mutation_method: impl Into<MutationVariant>) {
+		self.mutation_method = mutation_method.into();
+	}
+
+	pub fn set_crossover_method(&mut self, crossover_method: impl Into<CrossoverVariant>) {
+		self.crossover_method = crossover_method.into();
+	}
+
+	/// Starts counting generations from `generation`, e.g. when resuming an
+	/// earlier run.
+	pub fn with_generation(mut self, generation: usize) -> Self {
+		self.generation = generation;
+		self
+	}
+
 	pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I>
 	where I: Individual
 	{
-		assert!(!population.is_empty());
 		self.generation += 1;
-		(0..population.len())
+		self.breed(rng, population)
+	}
+
+	/// Same as `evolve()`, but doesn't count the generation.
+	pub fn breed<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I>
+	where
+		I: Individual,
+	{
+		assert!(!population.is_empty());
+
+		let elite_count = self.elite_count.min(population.len());
+		let mut ranked: Vec<&I> = population.iter().collect();
+		ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+		let elites = ranked
+			.into_iter()
+			.take(elite_count)
+			.map(|individual| I::create(individual.chromosome().clone()));
+
+		let children = (elite_count..population.len())
 			.map(|_| {
 				let parent_a = self.selection_method.select(rng, population).chromosome();
 				let parent_b = self.selection_method.select(rng, population).chromosome();
@@ -37,8 +107,9 @@ where
 				self.mutation_method.mutate(rng, &mut child);
 
 				I::create(child)
-			})
-			.collect()
+			});
+
+		elites.chain(children).collect()
 	}
 
 	pub fn generation(&self) -> usize {
@@ -65,9 +136,14 @@ impl SelectionMethod for RouletteWheelSelection {
 	where 
 		I: Individual
 	{
-		population
-			.choose_weighted(rng, |individual| individual.fitness())
-			.expect("get an empty population")
+		match population.choose_weighted(rng, |individual| individual.fitness()) {
+			Ok(individual) => individual,
+			// Nobody is fitter than anyone else, so everyone gets the same
+			// chance
+			Err(WeightedError::AllWeightsZero) => population.choose(rng).unwrap(),
+			Err(WeightedError::NoItem) => panic!("get an empty population"),
+			Err(err) => panic!("got an invalid fitness: {}", err),
+		}
 	}
 }
 
@@ -109,6 +185,11 @@ impl CrossoverMethod for UniformCrossover {
 
 pub trait MutationMethod {
 	fn mutate(&self, rng: &mut dyn RngCore, chromosome: &mut Chromosome);
+
+	/// Gives access to the method's temperature, if it has one.
+	fn as_temperature_mut(&mut self) -> Option<&mut dyn TemperatureMutationMethod> {
+		None
+	}
 }
 
 #[derive(Clone, Debug)]
@@ -121,10 +202,24 @@ pub struct GaussianMutation {
 
 impl GaussianMutation { 
 	pub fn new(chance: f32, coeff: f32) -> Self {
-		assert!(0.0 <= chance && chance <= 1.0);
-		assert!(0.0 <= coeff && coeff <= 3.0);
+		assert!((0.0..=1.0).contains(&chance));
+		assert!((0.0..=3.0).contains(&coeff));
 		Self { chance, coeff }
 	}
+
+	/// Mutates a copy of `chromosome`, returning it along with the size of
+	/// the mutation step.
+	pub fn mutate_with_distance(
+		&self,
+		rng: &mut dyn RngCore,
+		chromosome: &Chromosome,
+	) -> (Chromosome, f32) {
+		let mut mutated = chromosome.clone();
+		self.mutate(rng, &mut mutated);
+
+		let distance = Chromosome::mutation_distance(chromosome, &mutated);
+		(mutated, distance)
+	}
 }
 
 impl MutationMethod for GaussianMutation {
@@ -140,6 +235,33 @@ impl MutationMethod for GaussianMutation {
 	}
 }
 
+#[derive(Clone, Debug)]
+pub struct UniformMutation {
+	// The probability of a gene being replaced, 0 <= chance <= 1
+	chance: f32,
+	// Bounds of the replacement value, min < max
+	min: f32,
+	max: f32,
+}
+
+impl UniformMutation {
+	pub fn new(chance: f32, min: f32, max: f32) -> Self {
+		assert!((0.0..=1.0).contains(&chance));
+		assert!(min < max);
+		Self { chance, min, max }
+	}
+}
+
+impl MutationMethod for UniformMutation {
+	fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+		for gene in child.iter_mut() {
+			if rng.gen_bool(self.chance as f64) {
+				*gene = rng.gen_range(self.min..self.max);
+			}
+		}
+	}
+}
+
 #[derive(Clone, Debug)]
 pub struct Chromosome {
 	genes: Vec<f32>,
@@ -154,6 +276,37 @@ impl Chromosome {
 		self.genes.len()
 	}
 
+	pub fn is_empty(&self) -> bool {
+		self.genes.is_empty()
+	}
+
+	/// L2 norm of the gene-wise difference between both chromosomes.
+	pub fn mutation_distance(before: &Chromosome, after: &Chromosome) -> f32 {
+		Self::euclidean_distance(before, after)
+	}
+
+	pub fn euclidean_distance(a: &Chromosome, b: &Chromosome) -> f32 {
+		Self::squared_distance(a, b).sqrt()
+	}
+
+	/// Similarity of both chromosomes, from 1.0 when they're identical down
+	/// to 0.0 when they're much further apart than `sigma`.
+	pub fn gaussian_distance(a: &Chromosome, b: &Chromosome, sigma: f32) -> f32 {
+		assert!(sigma > 0.0);
+
+		(-Self::squared_distance(a, b) / (2.0 * sigma * sigma)).exp()
+	}
+
+	fn squared_distance(a: &Chromosome, b: &Chromosome) -> f32 {
+		assert_eq!(a.len(), b.len());
+
+		a.iter().zip(b.iter()).map(|(a, b)| (a - b).powi(2)).sum()
+	}
+
+	pub fn as_slice(&self) -> &[f32] {
+		&self.genes
+	}
+
 	pub fn iter(&self) -> impl Iterator<Item = &f32> {
 		self.genes.iter()
 	}
@@ -208,7 +361,7 @@ mod tests {
 			TestIndividual::new(4.0),
 		];
 		let actual = RouletteWheelSelection.select(&mut rng, &population);
-		assert!(actual.fitness() == (&population[1]).fitness());
+		assert!(actual.fitness() == population[1].fitness());
 
 		let mut action_histogram = BTreeMap::new();
 
@@ -226,6 +379,23 @@ mod tests {
 		assert_eq!(action_histogram, expected_histogram);
 	}
 
+	#[test]
+	fn roulette_wheel_selection_without_fitness() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let population: Vec<_> = (0..4).map(|_| TestIndividual::new(0.0)).collect();
+
+		let mut picks = [0; 4];
+
+		for _ in 0..1000 {
+			let selected = RouletteWheelSelection.select(&mut rng, &population);
+			let index = population.iter().position(|individual| std::ptr::eq(individual, selected)).unwrap();
+
+			picks[index] += 1;
+		}
+
+		assert!(picks.iter().all(|&count| count > 200), "{:?}", picks);
+	}
+
 	#[test]
 	fn uniform_crossover() {
 		let mut rng = ChaCha8Rng::from_seed(Default::default());
@@ -240,40 +410,79 @@ mod tests {
 		assert_eq!(diff_b, 51);
 	}
 
-	#[derive(Clone, Debug, PartialEq)]
-	enum TestIndividual {
-		WithChromosome { chromosome: Chromosome },
-		WithFitness { fitness: f32 },
-	}
+	mod uniform_mutation {
+		use super::*;
 
-	impl TestIndividual {
-		fn new(fitness: f32) -> Self {
-			Self::WithFitness { fitness }
+		fn actual(chance: f32, genes: usize) -> Vec<f32> {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut child = (0..genes).map(|n| n as f32 + 10.0).collect();
+			UniformMutation::new(chance, -1.0, 1.0).mutate(&mut rng, &mut child);
+
+			child.iter().copied().collect()
 		}
-	}
 
-	impl Individual for TestIndividual {
-		fn create(chromosome: Chromosome) -> Self {
-			Self::WithChromosome { chromosome }
+		#[test]
+		fn given_zero_chance_does_not_change_the_original_chromosome() {
+			let expected: Vec<f32> = (0..100).map(|n| n as f32 + 10.0).collect();
+
+			assert_eq!(actual(0.0, 100), expected);
 		}
 
-		fn chromosome(&self) -> &Chromosome {
-			match self {
-				Self::WithChromosome { chromosome } => chromosome,
-				Self::WithFitness { .. } => {
-					panic!("No chromosome")
-				}
-			}
+		#[test]
+		fn given_max_chance_replaces_every_gene_within_bounds() {
+			assert!(actual(1.0, 100).iter().all(|gene| (-1.0..1.0).contains(gene)));
 		}
 
-		fn fitness(&self) -> f32 {
-			match self {
-				Self::WithChromosome { .. } => {
-					self.chromosome().iter().sum::<f32>() / self.chromosome().len() as f32
-				}
-				Self::WithFitness { fitness } => *fitness,
+		#[test]
+		fn replacement_is_uniform() {
+			let mut histogram = [0; 10];
+
+			for gene in actual(1.0, 10_000) {
+				histogram[((gene + 1.0) * 5.0) as usize] += 1;
+			}
+
+			for count in histogram {
+				assert!((900..=1100).contains(&count), "{:?}", histogram);
 			}
 		}
+
+		#[test]
+		#[should_panic]
+		fn rejects_empty_bounds() {
+			UniformMutation::new(0.5, 1.0, 1.0);
+		}
+	}
+
+	mod mutation_distance {
+		use super::*;
+		use approx::assert_relative_eq;
+
+		#[test]
+		fn identical_chromosomes() {
+			let chromosome: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+
+			assert_eq!(Chromosome::mutation_distance(&chromosome, &chromosome), 0.0);
+		}
+
+		#[test]
+		fn different_chromosomes() {
+			let before: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+			let after: Chromosome = vec![4.0, 2.0, 7.0].into_iter().collect();
+
+			assert_relative_eq!(Chromosome::mutation_distance(&before, &after), 5.0);
+		}
+
+		#[test]
+		fn mutate_with_distance() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let chromosome: Chromosome = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+			let (mutated, distance) = GaussianMutation::new(1.0, 1.0)
+				.mutate_with_distance(&mut rng, &chromosome);
+
+			assert!(distance > 0.0);
+			assert_relative_eq!(distance, Chromosome::mutation_distance(&chromosome, &mutated));
+		}
 	}
 
 	impl PartialEq for Chromosome {
@@ -282,6 +491,48 @@ mod tests {
 		}
 	}
 
+	mod distances {
+		use super::*;
+		use approx::assert_relative_eq;
+
+		fn chromosome() -> Chromosome {
+			vec![1.0, 2.0, 3.0].into_iter().collect()
+		}
+
+		#[test]
+		fn identical_chromosomes() {
+			assert_eq!(Chromosome::euclidean_distance(&chromosome(), &chromosome()), 0.0);
+			assert_eq!(Chromosome::gaussian_distance(&chromosome(), &chromosome(), 0.5), 1.0);
+		}
+
+		#[test]
+		fn close_chromosomes() {
+			let other: Chromosome = vec![1.0, 2.0, 4.0].into_iter().collect();
+
+			assert_relative_eq!(Chromosome::euclidean_distance(&chromosome(), &other), 1.0);
+			assert_relative_eq!(
+				Chromosome::gaussian_distance(&chromosome(), &other, 1.0),
+				(-0.5f32).exp()
+			);
+		}
+
+		#[test]
+		fn distant_chromosomes() {
+			let other: Chromosome = vec![10.0, -20.0, 30.0].into_iter().collect();
+			let similarity = Chromosome::gaussian_distance(&chromosome(), &other, 1.0);
+
+			assert!((0.0..1e-6).contains(&similarity));
+		}
+
+		#[test]
+		#[should_panic]
+		fn different_lengths() {
+			let other: Chromosome = vec![1.0, 2.0].into_iter().collect();
+
+			Chromosome::euclidean_distance(&chromosome(), &other);
+		}
+	}
+
 	mod gausssian_mutation {
 		use super::*;
 
@@ -404,4 +655,236 @@ mod tests {
 		assert_eq!(population, expect_population);
 
 	}
-}
\ No newline at end of file
+
+	mod hot_swapping {
+		use super::*;
+
+		// Equally fit no matter the genes, so that selection never fails
+		struct Flat;
+
+		impl Landscape for Flat {
+			fn fitness(_: &Chromosome) -> f32 {
+				1.0
+			}
+		}
+
+		type FlatIndividual = TestIndividual<Flat>;
+
+		struct ZeroCrossover;
+
+		impl CrossoverMethod for ZeroCrossover {
+			fn crossover(
+				&self,
+				_: &mut dyn RngCore,
+				parent_a: &Chromosome,
+				_: &Chromosome,
+			) -> Chromosome {
+				parent_a.iter().map(|_| 0.0).collect()
+			}
+		}
+
+		fn population() -> Vec<FlatIndividual> {
+			(0..10)
+				.map(|_| FlatIndividual::create(vec![1.0; 20].into_iter().collect()))
+				.collect()
+		}
+
+		fn total_change(population: &[FlatIndividual]) -> f32 {
+			population
+				.iter()
+				.flat_map(|individual| individual.chromosome().iter())
+				.map(|gene| (gene - 1.0).abs())
+				.sum()
+		}
+
+		#[test]
+		fn set_mutation_method() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut ga = GeneticAlgorithm::new(
+				RouletteWheelSelection,
+				UniformCrossover,
+				GaussianMutation::new(1.0, 0.0),
+			);
+			let mut population = population();
+
+			for _ in 0..5 {
+				population = ga.evolve(&mut rng, &population);
+			}
+			assert_eq!(total_change(&population), 0.0);
+
+			ga.set_mutation_method(GaussianMutation::new(1.0, 3.0));
+
+			for _ in 0..5 {
+				population = ga.evolve(&mut rng, &population);
+			}
+			assert!(total_change(&population) > 200.0);
+		}
+
+		#[test]
+		fn set_crossover_method() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut ga = GeneticAlgorithm::new(
+				RouletteWheelSelection,
+				UniformCrossover,
+				GaussianMutation::new(0.0, 0.0),
+			);
+
+			ga.set_crossover_method(CrossoverVariant::custom(ZeroCrossover));
+			let population = ga.evolve(&mut rng, &population());
+
+			assert!(population
+				.iter()
+				.all(|individual| individual.chromosome().iter().all(|gene| *gene == 0.0)));
+		}
+	}
+
+	mod elitism {
+		use super::*;
+
+		// Fitness grows as genes drift away from zero, except for the
+		// all-zero chromosome which is the isolated global optimum
+		struct Deceptive;
+
+		impl Landscape for Deceptive {
+			fn fitness(chromosome: &Chromosome) -> f32 {
+				if chromosome.iter().all(|gene| *gene == 0.0) {
+					100.0
+				} else {
+					chromosome.iter().map(|gene| gene.abs().min(1.0)).sum()
+				}
+			}
+		}
+
+		type DeceptiveIndividual = TestIndividual<Deceptive>;
+
+		fn population() -> Vec<DeceptiveIndividual> {
+			[[0.5, 0.5, 0.5], [0.0, 0.0, 0.0], [0.1, 0.2, 0.3], [0.9, 0.1, 0.4]]
+				.iter()
+				.map(|genes| DeceptiveIndividual::create(genes.iter().copied().collect()))
+				.collect()
+		}
+
+		#[test]
+		fn best_individual_always_survives() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut ga = GeneticAlgorithm::new(
+				RouletteWheelSelection,
+				UniformCrossover,
+				GaussianMutation::new(0.5, 0.5),
+			)
+			.with_elitism(1);
+
+			let mut population = population();
+
+			for _ in 0..100 {
+				population = ga.evolve(&mut rng, &population);
+
+				assert_eq!(population.len(), 4);
+				assert_eq!(population[0].fitness(), 100.0);
+				assert!(population[0].chromosome().iter().all(|gene| *gene == 0.0));
+			}
+		}
+
+		#[test]
+		fn elite_count_is_capped_by_population_size() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut ga = GeneticAlgorithm::new(
+				RouletteWheelSelection,
+				UniformCrossover,
+				GaussianMutation::new(1.0, 1.0),
+			)
+			.with_elitism(10);
+
+			let population = population();
+			let evolved = ga.evolve(&mut rng, &population);
+
+			let mut expected: Vec<f32> = population.iter().map(|i| i.fitness()).collect();
+			expected.sort_by(|a, b| b.total_cmp(a));
+			let actual: Vec<f32> = evolved.iter().map(|i| i.fitness()).collect();
+
+			assert_eq!(actual, expected);
+		}
+	}
+
+	mod properties {
+		use super::*;
+		use proptest::prelude::*;
+
+		prop_compose! {
+			// Non-empty population of equally long chromosomes with
+			// non-negative genes (and thus non-negative fitness)
+			fn population()(chromosome_len in 1..20usize, size in 1..30usize)
+				(genes in prop::collection::vec(
+					prop::collection::vec(0.0f32..10.0, chromosome_len),
+					size,
+				)) -> Vec<TestIndividual> {
+				genes
+					.into_iter()
+					.map(|genes| TestIndividual::create(genes.into_iter().collect()))
+					.collect()
+			}
+		}
+
+		prop_compose! {
+			// Fitnesses where some individuals may be unfit, but not all of them
+			fn fitnesses()(fitnesses in prop::collection::vec(
+				prop_oneof![Just(0.0f32), 0.1f32..10.0],
+				1..30,
+			), fittest in 0.1f32..10.0) -> Vec<f32> {
+				let mut fitnesses = fitnesses;
+				fitnesses.push(fittest);
+				fitnesses
+			}
+		}
+
+		fn evolve(seed: u64, population: &[TestIndividual], mutation_chance: f32) -> Vec<TestIndividual> {
+			let mut rng = ChaCha8Rng::seed_from_u64(seed);
+			let mut ga = GeneticAlgorithm::new(
+				RouletteWheelSelection,
+				UniformCrossover,
+				GaussianMutation::new(mutation_chance, 0.5),
+			);
+
+			ga.evolve(&mut rng, population)
+		}
+
+		proptest! {
+			#[test]
+			fn evolve_preserves_population_size(seed: u64, population in population()) {
+				prop_assert_eq!(evolve(seed, &population, 0.5).len(), population.len());
+			}
+
+			#[test]
+			fn evolve_preserves_chromosome_length(seed: u64, population in population()) {
+				let len = population[0].chromosome().len();
+
+				for child in evolve(seed, &population, 0.5) {
+					prop_assert_eq!(child.chromosome().len(), len);
+				}
+			}
+
+			#[test]
+			fn evolve_without_mutation_keeps_fitness_non_negative(
+				seed: u64,
+				population in population(),
+			) {
+				for child in evolve(seed, &population, 0.0) {
+					prop_assert!(child.fitness() >= 0.0);
+				}
+			}
+
+			#[test]
+			fn roulette_wheel_never_selects_unfit_individuals(
+				seed: u64,
+				fitnesses in fitnesses(),
+			) {
+				let mut rng = ChaCha8Rng::seed_from_u64(seed);
+				let population: Vec<_> = fitnesses.into_iter().map(TestIndividual::new).collect();
+
+				for _ in 0..10 {
+					prop_assert!(RouletteWheelSelection.select(&mut rng, &population).fitness() > 0.0);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,108 @@
+use crate::*;
+
+/// Evolves `n_runs` independent populations (each built by
+/// `population_factory`) for `n_generations` generations and returns the
+/// one whose fittest individual is the fittest overall.
+///
+/// Since each run starts from scratch, this makes it less likely for the
+/// result to be stuck in a local optimum.
+pub fn multi_start<I, S>(
+	ga: &GeneticAlgorithm<S>,
+	rng: &mut dyn RngCore,
+	population_factory: impl Fn(&mut dyn RngCore) -> Vec<I>,
+	n_runs: usize,
+	n_generations: usize,
+) -> Vec<I>
+where
+	I: Individual,
+	S: SelectionMethod,
+{
+	assert!(n_runs > 0);
+
+	(0..n_runs)
+		.map(|_| {
+			let mut population = population_factory(rng);
+
+			for _ in 0..n_generations {
+				population = ga.breed(rng, &population);
+			}
+
+			population
+		})
+		.max_by(|a, b| max_fitness(a).total_cmp(&max_fitness(b)))
+		.unwrap()
+}
+
+fn max_fitness<I: Individual>(population: &[I]) -> f32 {
+	PopulationStats::new(population).max_fitness()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	// Several local optima
+	struct Bumpy;
+
+	impl Landscape for Bumpy {
+		fn fitness(chromosome: &Chromosome) -> f32 {
+			chromosome
+				.iter()
+				.map(|gene| 1.0 + (gene * 3.0).sin() * (1.0 - gene.abs() / 10.0))
+				.sum::<f32>()
+				.max(0.0)
+		}
+	}
+
+	fn population(rng: &mut dyn RngCore) -> Vec<TestIndividual<Bumpy>> {
+		(0..10)
+			.map(|_| TestIndividual::create((0..3).map(|_| rng.gen_range(-5.0..5.0)).collect()))
+			.collect()
+	}
+
+	fn ga() -> GeneticAlgorithm<RouletteWheelSelection> {
+		GeneticAlgorithm::new(
+			RouletteWheelSelection,
+			UniformCrossover,
+			GaussianMutation::new(0.3, 0.3),
+		)
+	}
+
+	#[test]
+	fn returns_the_best_run() {
+		let ga = ga();
+		let best = multi_start(&ga, &mut ChaCha8Rng::seed_from_u64(42), population, 6, 5);
+
+		// Same runs again, one by one
+		let mut rng = ChaCha8Rng::seed_from_u64(42);
+		let runs: Vec<f32> = (0..6)
+			.map(|_| {
+				let mut population = population(&mut rng);
+
+				for _ in 0..5 {
+					population = ga.breed(&mut rng, &population);
+				}
+
+				max_fitness(&population)
+			})
+			.collect();
+
+		let best = max_fitness(&best);
+		let beaten = runs.iter().filter(|run| best > **run).count();
+
+		assert!(runs.iter().all(|run| best >= *run));
+		assert!(beaten >= 3, "{} beats only {} of {:?}", best, beaten, runs);
+	}
+
+	#[test]
+	fn breed_does_not_count_generations() {
+		let ga = ga();
+		let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+		multi_start(&ga, &mut rng, population, 2, 3);
+
+		assert_eq!(ga.generation(), 1);
+	}
+}
@@ -0,0 +1,156 @@
+use crate::*;
+use lib_neural_network::{Layer, LayerTopology, Network};
+
+impl Chromosome {
+	/// Encodes the network's biases and weights as genes.
+	pub fn from_network(network: &Network) -> Self {
+		Self::new(network.weights())
+	}
+
+	/// Decodes a network of given topology out of the genes, i.e. the
+	/// inverse of `from_network()`.
+	pub fn into_network(self, topology: &[LayerTopology]) -> Network {
+		Network::from_weight_slice(topology, self.as_slice()).unwrap_or_else(|err| panic!("{}", err))
+	}
+}
+
+/// Mates two networks that may differ in their hidden layers: layers of the
+/// same shape and position get their parameters picked uniformly from
+/// either parent, while the rest are copied from the parent with more
+/// layers, which also decides the child's topology.
+///
+/// Returns `None` if the networks differ in their input or output sizes.
+pub fn crossover_networks_compatible(
+	rng: &mut dyn RngCore,
+	a: &Network,
+	b: &Network,
+) -> Option<Network> {
+	let input_size = |network: &Network| network.layers()[0].input_size();
+	let output_size = |network: &Network| network.layers().last().unwrap().neurons().len();
+
+	if input_size(a) != input_size(b) || output_size(a) != output_size(b) {
+		return None;
+	}
+
+	let (larger, smaller) = if b.layers().len() > a.layers().len() {
+		(b, a)
+	} else {
+		(a, b)
+	};
+
+	let smaller_params = smaller.weights_by_layer();
+	let mut params = Vec::with_capacity(larger.param_count());
+
+	for (l, larger_params) in larger.weights_by_layer().into_iter().enumerate() {
+		match smaller.layers().get(l) {
+			Some(layer) if layers_match(layer, &larger.layers()[l]) => {
+				params.extend(
+					larger_params
+						.into_iter()
+						.zip(&smaller_params[l])
+						.map(|(a, &b)| if rng.gen_bool(0.5) { a } else { b }),
+				);
+			}
+			_ => params.extend(larger_params),
+		}
+	}
+
+	let mut params = params.into_iter();
+
+	Some(larger.map(|_| params.next().unwrap()))
+}
+
+// Whether both layers have parameters that correspond one to one
+fn layers_match(a: &Layer, b: &Layer) -> bool {
+	let (first_a, first_b) = (&a.neurons()[0], &b.neurons()[0]);
+
+	a.neurons().len() == b.neurons().len()
+		&& first_a.weights().len() == first_b.weights().len()
+		&& first_a.has_bias() == first_b.has_bias()
+		&& a.input_size() == b.input_size()
+		&& a.kind() == b.kind()
+		&& a.skip_from() == b.skip_from()
+		&& a.is_mirrored() == b.is_mirrored()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use approx::assert_relative_eq;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn network(rng: &mut dyn RngCore, sizes: &[usize]) -> Network {
+		let topology: Vec<_> = sizes.iter().map(|&size| LayerTopology::new(size)).collect();
+
+		Network::random(rng, &topology)
+	}
+
+	fn shape(network: &Network) -> Vec<usize> {
+		let mut shape = vec![network.layers()[0].input_size()];
+		shape.extend(network.layers().iter().map(|layer| layer.neurons().len()));
+		shape
+	}
+
+	#[test]
+	fn crossover_networks_compatible() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let a = network(&mut rng, &[3, 5, 2]);
+		let b = network(&mut rng, &[3, 5, 4, 2]);
+
+		let mut child = super::crossover_networks_compatible(&mut rng, &a, &b).unwrap();
+		assert_eq!(shape(&child), vec![3, 5, 4, 2]);
+
+		// The first layers match, so they get mixed; the rest comes from `b`
+		let (a, b, actual) = (a.weights_by_layer(), b.weights_by_layer(), child.weights_by_layer());
+
+		assert!(actual[0].iter().zip(&a[0]).any(|(actual, a)| actual == a));
+		assert!(actual[0].iter().zip(&b[0]).any(|(actual, b)| actual == b));
+		for ((actual, a), b) in actual[0].iter().zip(&a[0]).zip(&b[0]) {
+			assert!(actual == a || actual == b);
+		}
+		assert_eq!(actual[1..], b[1..]);
+
+		let outputs = child.propagate(vec![0.5, -0.25, 1.0]);
+		assert_eq!(outputs.len(), 2);
+	}
+
+	#[test]
+	fn crossover_networks_compatible_with_no_matching_layers() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let a = network(&mut rng, &[3, 8, 6, 2]);
+		let b = network(&mut rng, &[3, 5, 2]);
+
+		let mut child = super::crossover_networks_compatible(&mut rng, &a, &b).unwrap();
+
+		assert_eq!(shape(&child), vec![3, 8, 6, 2]);
+		assert_eq!(child.weights(), a.weights());
+		assert_eq!(child.propagate(vec![0.5, -0.25, 1.0]).len(), 2);
+	}
+
+	#[test]
+	fn crossover_networks_compatible_rejects_different_ends() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let a = network(&mut rng, &[3, 5, 2]);
+
+		for sizes in [[4, 5, 2], [3, 5, 1]] {
+			let b = network(&mut rng, &sizes);
+
+			assert!(super::crossover_networks_compatible(&mut rng, &a, &b).is_none());
+		}
+	}
+
+	#[test]
+	fn round_trip() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let topology = [LayerTopology::new(3), LayerTopology::new(5), LayerTopology::new(2)];
+		let network = Network::random(&mut rng, &topology);
+
+		let chromosome = Chromosome::from_network(&network);
+		assert_eq!(chromosome.len(), LayerTopology::param_count(&topology));
+
+		let restored = chromosome.into_network(&topology);
+		let (expected, actual) = (network.weights(), restored.weights());
+		assert_relative_eq!(actual.as_slice(), expected.as_slice());
+	}
+}
@@ -0,0 +1,170 @@
+use crate::*;
+
+/// Picks which individual a child should compete with: the least fit among
+/// the `window_size` individuals closest to it.
+///
+/// Since children only ever replace individuals similar to themselves,
+/// separate niches (e.g. different peaks of the fitness landscape) don't
+/// crowd each other out.
+#[derive(Clone, Debug)]
+pub struct RestrictedTournamentSelection {
+	window_size: usize,
+}
+
+impl RestrictedTournamentSelection {
+	pub fn new(window_size: usize) -> Self {
+		assert!(window_size > 0);
+
+		Self { window_size }
+	}
+
+	pub fn window_size(&self) -> usize {
+		self.window_size
+	}
+
+	/// Returns the index of the individual `child` should compete with.
+	pub fn select_for_replacement<I>(&self, population: &[I], child: &I) -> usize
+	where
+		I: Individual,
+	{
+		assert!(!population.is_empty());
+
+		let mut by_distance: Vec<(usize, f32)> = population
+			.iter()
+			.map(|individual| Chromosome::mutation_distance(individual.chromosome(), child.chromosome()))
+			.enumerate()
+			.collect();
+
+		by_distance.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+		by_distance
+			.into_iter()
+			.take(self.window_size)
+			.map(|(i, _)| i)
+			.min_by(|&a, &b| population[a].fitness().total_cmp(&population[b].fitness()))
+			.unwrap()
+	}
+}
+
+/// Same as `SteadyStateGA`, but children replace a similar individual
+/// (picked by `RestrictedTournamentSelection`) instead of the worst one,
+/// which lets the population settle on several optima at once.
+pub struct NichingGA<S> {
+	selection_method: S,
+	replacement: RestrictedTournamentSelection,
+	crossover_method: CrossoverVariant,
+	mutation_method: MutationVariant,
+}
+
+impl<S> NichingGA<S>
+where
+	S: SelectionMethod,
+{
+	pub fn new(
+		selection_method: S,
+		replacement: RestrictedTournamentSelection,
+		crossover_method: impl Into<CrossoverVariant>,
+		mutation_method: impl Into<MutationVariant>,
+	) -> Self {
+		Self {
+			selection_method,
+			replacement,
+			crossover_method: crossover_method.into(),
+			mutation_method: mutation_method.into(),
+		}
+	}
+
+	/// Breeds `population.len()` children, one at a time, each replacing its
+	/// competitor if it's fitter.
+	///
+	/// Returns how many children made it into the population.
+	pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &mut [I]) -> usize
+	where
+		I: Individual,
+	{
+		assert!(!population.is_empty());
+
+		let mut replaced = 0;
+
+		for _ in 0..population.len() {
+			let parent_a = self.selection_method.select(rng, population).chromosome();
+			let parent_b = self.selection_method.select(rng, population).chromosome();
+			let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+			self.mutation_method.mutate(rng, &mut child);
+			let child = I::create(child);
+
+			let competitor = self.replacement.select_for_replacement(population, &child);
+
+			if child.fitness() > population[competitor].fitness() {
+				population[competitor] = child;
+				replaced += 1;
+			}
+		}
+
+		replaced
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	// Single gene, with a peak at -1.0 and a slightly lower one at 1.0
+	struct TwoPeaks;
+
+	impl Landscape for TwoPeaks {
+		fn fitness(chromosome: &Chromosome) -> f32 {
+			let x = chromosome[0];
+
+			(-((x + 1.0) / 0.3).powi(2)).exp() + 0.9 * (-((x - 1.0) / 0.3).powi(2)).exp()
+		}
+	}
+
+	fn individual(gene: f32) -> TestIndividual<TwoPeaks> {
+		TestIndividual::create(Chromosome::new(vec![gene]))
+	}
+
+	#[test]
+	fn select_for_replacement() {
+		let population: Vec<_> = [-2.0, -1.1, -0.8, 1.0, 1.5].into_iter().map(individual).collect();
+		let child = individual(-1.0);
+
+		// Closest is -1.1
+		assert_eq!(RestrictedTournamentSelection::new(1).select_for_replacement(&population, &child), 1);
+
+		// -0.8 is within the window and less fit
+		assert_eq!(RestrictedTournamentSelection::new(2).select_for_replacement(&population, &child), 2);
+
+		// -2.0 gets into the window
+		assert_eq!(RestrictedTournamentSelection::new(4).select_for_replacement(&population, &child), 0);
+	}
+
+	#[test]
+	fn maintains_both_peaks() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let ga = NichingGA::new(
+			RouletteWheelSelection,
+			RestrictedTournamentSelection::new(3),
+			UniformCrossover,
+			GaussianMutation::new(0.5, 0.1),
+		);
+
+		let mut population: Vec<_> = (0..40).map(|_| individual(rng.gen_range(-2.0..2.0))).collect();
+
+		for _ in 0..100 {
+			ga.evolve(&mut rng, &mut population);
+		}
+
+		let near = |peak: f32| {
+			population
+				.iter()
+				.filter(|individual| (individual.chromosome()[0] - peak).abs() < 0.2)
+				.count()
+		};
+
+		assert!(near(-1.0) >= 5, "{} individuals near -1.0", near(-1.0));
+		assert!(near(1.0) >= 5, "{} individuals near 1.0", near(1.0));
+	}
+}
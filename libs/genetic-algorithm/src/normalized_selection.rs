@@ -0,0 +1,170 @@
+use crate::*;
+
+/// How `NormalizedSelection` rescales fitnesses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NormalizationMode {
+	/// Maps fitnesses linearly onto `[min_pressure, 1]`, so that the worst
+	/// individual keeps a `min_pressure` chance relative to the best one.
+	Linear { min_pressure: f32 },
+	/// Replaces fitnesses with ranks, from 1 for the worst individual up to
+	/// the population's size for the best one.
+	Rank,
+	/// Sigma scaling: `1 + (fitness - mean) / (2 * std)`, floored at
+	/// `SIGMA_FLOOR`, so that outliers only get a bounded advantage.
+	Sigma,
+}
+
+// Sigma scaling can go negative for individuals far below the mean, which
+// still deserve a small chance
+const SIGMA_FLOOR: f32 = 0.1;
+
+impl NormalizationMode {
+	fn normalize(&self, fitnesses: &[f32]) -> Vec<f32> {
+		let len = fitnesses.len() as f32;
+
+		match *self {
+			Self::Linear { min_pressure } => {
+				let min = fitnesses.iter().copied().fold(f32::INFINITY, f32::min);
+				let max = fitnesses.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+				fitnesses
+					.iter()
+					.map(|fitness| {
+						if max > min {
+							min_pressure + (1.0 - min_pressure) * (fitness - min) / (max - min)
+						} else {
+							1.0
+						}
+					})
+					.collect()
+			}
+
+			Self::Rank => {
+				let mut order: Vec<usize> = (0..fitnesses.len()).collect();
+				order.sort_by(|&a, &b| fitnesses[a].total_cmp(&fitnesses[b]));
+
+				let mut ranks = vec![0.0; fitnesses.len()];
+				for (rank, i) in order.into_iter().enumerate() {
+					ranks[i] = (rank + 1) as f32;
+				}
+
+				ranks
+			}
+
+			Self::Sigma => {
+				let mean = fitnesses.iter().sum::<f32>() / len;
+				let std = (fitnesses.iter().map(|fitness| (fitness - mean).powi(2)).sum::<f32>() / len).sqrt();
+
+				fitnesses
+					.iter()
+					.map(|fitness| {
+						if std > 0.0 {
+							(1.0 + (fitness - mean) / (2.0 * std)).max(SIGMA_FLOOR)
+						} else {
+							1.0
+						}
+					})
+					.collect()
+			}
+		}
+	}
+}
+
+/// Rescales fitnesses before handing the population over to `inner`, e.g.
+/// to keep roulette wheel selection working when raw fitnesses are badly
+/// scaled.
+#[derive(Clone, Debug)]
+pub struct NormalizedSelection<S> {
+	inner: S,
+	mode: NormalizationMode,
+}
+
+impl<S: SelectionMethod> NormalizedSelection<S> {
+	pub fn new(inner: S, mode: NormalizationMode) -> Self {
+		if let NormalizationMode::Linear { min_pressure } = mode {
+			assert!((0.0..=1.0).contains(&min_pressure));
+		}
+
+		Self { inner, mode }
+	}
+}
+
+impl<S: SelectionMethod> SelectionMethod for NormalizedSelection<S> {
+	fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+	where
+		I: Individual,
+	{
+		let fitnesses: Vec<f32> = population.iter().map(Individual::fitness).collect();
+
+		let normalized: Vec<Normalized<I>> = population
+			.iter()
+			.zip(self.mode.normalize(&fitnesses))
+			.map(|(individual, fitness)| Normalized { individual, fitness })
+			.collect();
+
+		self.inner.select(rng, &normalized).individual
+	}
+}
+
+// An individual as seen by the inner selection method
+struct Normalized<'a, I> {
+	individual: &'a I,
+	fitness: f32,
+}
+
+impl<I: Individual> Individual for Normalized<'_, I> {
+	fn create(_: Chromosome) -> Self {
+		unreachable!("selection methods don't create individuals")
+	}
+
+	fn fitness(&self) -> f32 {
+		self.fitness
+	}
+
+	fn chromosome(&self) -> &Chromosome {
+		self.individual.chromosome()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	#[test]
+	fn linear() {
+		let mode = NormalizationMode::Linear { min_pressure: 0.2 };
+
+		assert_eq!(mode.normalize(&[10.0, 30.0, 20.0]), vec![0.2, 1.0, 0.6]);
+		assert_eq!(mode.normalize(&[5.0, 5.0]), vec![1.0, 1.0]);
+	}
+
+	#[test]
+	fn rank() {
+		assert_eq!(NormalizationMode::Rank.normalize(&[0.5, 9000.0, -1.0]), vec![2.0, 3.0, 1.0]);
+	}
+
+	#[test]
+	fn sigma_keeps_outliers_from_dominating() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+		let mut population: Vec<TestIndividual> = (0..10).map(|_| TestIndividual::new(1.0)).collect();
+		population[0] = TestIndividual::new(10_000.0);
+
+		let outlier = &population[0];
+		let normalized = NormalizedSelection::new(RouletteWheelSelection, NormalizationMode::Sigma);
+
+		let raw = (0..1000)
+			.filter(|_| std::ptr::eq(RouletteWheelSelection.select(&mut rng, &population), outlier))
+			.count();
+
+		let sigma = (0..1000)
+			.filter(|_| std::ptr::eq(normalized.select(&mut rng, &population), outlier))
+			.count();
+
+		assert!(raw > 990, "raw = {}", raw);
+		assert!(sigma < 500, "sigma = {}", sigma);
+		assert!(sigma > 100, "sigma = {}", sigma);
+	}
+}
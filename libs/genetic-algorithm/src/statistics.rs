@@ -0,0 +1,246 @@
+use crate::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PopulationStats {
+	min_fitness: f32,
+	max_fitness: f32,
+	mean_fitness: f32,
+	median_fitness: f32,
+}
+
+impl PopulationStats {
+	pub fn new<I>(population: &[I]) -> Self
+	where
+		I: Individual,
+	{
+		assert!(!population.is_empty());
+
+		let mut min_fitness = f32::MAX;
+		let mut max_fitness = f32::MIN;
+		let mut sum_fitness = 0.0;
+		let mut fitnesses = Vec::with_capacity(population.len());
+
+		for individual in population {
+			let fitness = individual.fitness();
+
+			min_fitness = min_fitness.min(fitness);
+			max_fitness = max_fitness.max(fitness);
+			sum_fitness += fitness;
+			fitnesses.push(fitness);
+		}
+
+		fitnesses.sort_by(f32::total_cmp);
+
+		let middle = fitnesses.len() / 2;
+		let median_fitness = if fitnesses.len() % 2 == 0 {
+			(fitnesses[middle - 1] + fitnesses[middle]) / 2.0
+		} else {
+			fitnesses[middle]
+		};
+
+		Self {
+			min_fitness,
+			max_fitness,
+			mean_fitness: sum_fitness / population.len() as f32,
+			median_fitness,
+		}
+	}
+
+	pub fn min_fitness(&self) -> f32 {
+		self.min_fitness
+	}
+
+	pub fn max_fitness(&self) -> f32 {
+		self.max_fitness
+	}
+
+	pub fn mean_fitness(&self) -> f32 {
+		self.mean_fitness
+	}
+
+	/// For an even population, the mean of the two middle fitnesses.
+	pub fn median_fitness(&self) -> f32 {
+		self.median_fitness
+	}
+}
+
+/// Statistics of each gene across a population, see
+/// `population_gene_stats()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneStats {
+	pub per_gene_mean: Vec<f32>,
+	pub per_gene_variance: Vec<f32>,
+	/// Mean of `per_gene_variance`
+	pub overall_diversity: f32,
+}
+
+/// Computes the mean and variance of every gene across the chromosomes.
+///
+/// Panics if there are none or if they differ in length.
+pub fn population_gene_stats(population: &[Chromosome]) -> GeneStats {
+	assert!(!population.is_empty());
+
+	let len = population[0].len();
+	let count = population.len() as f32;
+
+	assert!(
+		population.iter().all(|chromosome| chromosome.len() == len),
+		"got chromosomes of different lengths",
+	);
+
+	let mut per_gene_mean = vec![0.0; len];
+
+	for chromosome in population {
+		for (mean, gene) in per_gene_mean.iter_mut().zip(chromosome.iter()) {
+			*mean += gene / count;
+		}
+	}
+
+	let mut per_gene_variance = vec![0.0; len];
+
+	for chromosome in population {
+		for ((variance, mean), gene) in per_gene_variance.iter_mut().zip(&per_gene_mean).zip(chromosome.iter()) {
+			*variance += (gene - mean).powi(2) / count;
+		}
+	}
+
+	let overall_diversity = if len == 0 {
+		0.0
+	} else {
+		per_gene_variance.iter().sum::<f32>() / len as f32
+	};
+
+	GeneStats {
+		per_gene_mean,
+		per_gene_variance,
+		overall_diversity,
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct ConvergenceDetector {
+	// Number of most recent generations taken into account
+	window: usize,
+	// Largest standard deviation of the mean fitness still deemed converged
+	threshold: f32,
+}
+
+impl ConvergenceDetector {
+	pub fn new(window: usize, threshold: f32) -> Self {
+		assert!(window > 0);
+		assert!(threshold >= 0.0);
+		Self { window, threshold }
+	}
+
+	pub fn has_converged(&self, stats_history: &[PopulationStats]) -> bool {
+		let mean_fitnesses: Vec<_> = stats_history.iter().map(|stats| stats.mean_fitness).collect();
+
+		self.has_converged_by_means(&mean_fitnesses)
+	}
+
+	/// Same as `has_converged()`, given just each generation's mean fitness,
+	/// oldest first.
+	pub fn has_converged_by_means(&self, mean_fitnesses: &[f32]) -> bool {
+		if mean_fitnesses.len() < self.window {
+			return false;
+		}
+
+		let window = &mean_fitnesses[mean_fitnesses.len() - self.window..];
+		let mean = window.iter().sum::<f32>() / self.window as f32;
+		let variance = window.iter().map(|fitness| (fitness - mean).powi(2)).sum::<f32>() / self.window as f32;
+
+		variance.sqrt() <= self.threshold
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn stats(fitnesses: &[f32]) -> PopulationStats {
+		let population: Vec<_> = fitnesses
+			.iter()
+			.map(|&fitness| TestIndividual::new(fitness))
+			.collect();
+
+		PopulationStats::new(&population)
+	}
+
+	#[test]
+	fn population_stats() {
+		let stats = stats(&[1.0, 2.0, 6.0]);
+
+		assert_eq!(stats.min_fitness(), 1.0);
+		assert_eq!(stats.max_fitness(), 6.0);
+		assert_eq!(stats.mean_fitness(), 3.0);
+		assert_eq!(stats.median_fitness(), 2.0);
+	}
+
+	#[test]
+	fn median_of_even_population() {
+		assert_eq!(stats(&[8.0, 1.0, 2.0, 6.0]).median_fitness(), 4.0);
+	}
+
+	#[test]
+	fn gene_stats_of_identical_population() {
+		let population = vec![Chromosome::new(vec![0.5, -1.0, 2.0]); 4];
+		let stats = population_gene_stats(&population);
+
+		assert_eq!(stats.per_gene_mean, vec![0.5, -1.0, 2.0]);
+		assert_eq!(stats.per_gene_variance, vec![0.0; 3]);
+		assert_eq!(stats.overall_diversity, 0.0);
+	}
+
+	#[test]
+	fn gene_stats_of_random_population() {
+		use rand::{Rng, SeedableRng};
+		use rand_chacha::ChaCha8Rng;
+
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let population: Vec<_> = (0..200)
+			.map(|_| Chromosome::new((0..5).map(|_| rng.gen_range(-1.0..=1.0)).collect()))
+			.collect();
+
+		let stats = population_gene_stats(&population);
+
+		// U(-1, 1) has a mean of 0 and a variance of 1/3
+		for (mean, variance) in stats.per_gene_mean.iter().zip(&stats.per_gene_variance) {
+			assert!(mean.abs() < 0.15, "mean = {}", mean);
+			assert!((variance - 1.0 / 3.0).abs() < 0.1, "variance = {}", variance);
+		}
+
+		let mean_variance = stats.per_gene_variance.iter().sum::<f32>() / 5.0;
+		assert_eq!(stats.overall_diversity, mean_variance);
+	}
+
+	#[test]
+	#[should_panic(expected = "got chromosomes of different lengths")]
+	fn gene_stats_need_same_lengths() {
+		population_gene_stats(&[Chromosome::new(vec![0.0; 2]), Chromosome::new(vec![0.0; 3])]);
+	}
+
+	#[test]
+	fn converges_immediately_for_same_fitness_population() {
+		let detector = ConvergenceDetector::new(3, 0.0);
+		let history = vec![stats(&[2.0, 2.0, 2.0]); 3];
+
+		assert!(detector.has_converged(&history));
+	}
+
+	#[test]
+	fn needs_a_full_window() {
+		let detector = ConvergenceDetector::new(3, 0.0);
+		let history = vec![stats(&[2.0, 2.0, 2.0]); 2];
+
+		assert!(!detector.has_converged(&history));
+	}
+
+	#[test]
+	fn only_looks_at_the_last_window() {
+		let detector = ConvergenceDetector::new(2, 0.1);
+		let history = vec![stats(&[1.0]), stats(&[5.0]), stats(&[5.0])];
+
+		assert!(detector.has_converged(&history));
+		assert!(!detector.has_converged(&history[..2]));
+	}
+}
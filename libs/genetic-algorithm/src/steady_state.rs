@@ -0,0 +1,150 @@
+use crate::*;
+
+/// Instead of replacing the whole population at once, breeds a single child
+/// at a time and swaps it in for the worst individual if it's fitter.
+///
+/// Calling `evolve_one` `population.len()` times roughly corresponds to one
+/// generation of `GeneticAlgorithm`.
+pub struct SteadyStateGA<S> {
+	selection_method: S,
+	crossover_method: CrossoverVariant,
+	mutation_method: MutationVariant,
+}
+
+impl<S> SteadyStateGA<S>
+where
+	S: SelectionMethod,
+{
+	pub fn new(
+		selection_method: S,
+		crossover_method: impl Into<CrossoverVariant>,
+		mutation_method: impl Into<MutationVariant>,
+	) -> Self {
+		Self {
+			selection_method,
+			crossover_method: crossover_method.into(),
+			mutation_method: mutation_method.into(),
+		}
+	}
+
+	/// Returns whether the child made it into the population.
+	pub fn evolve_one<I>(&self, rng: &mut dyn RngCore, population: &mut [I]) -> bool
+	where
+		I: Individual,
+	{
+		assert!(!population.is_empty());
+
+		let parent_a = self.selection_method.select(rng, population).chromosome();
+		let parent_b = self.selection_method.select(rng, population).chromosome();
+		let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+		self.mutation_method.mutate(rng, &mut child);
+		let child = I::create(child);
+
+		let (worst, worst_fitness) = population
+			.iter()
+			.map(|individual| individual.fitness())
+			.enumerate()
+			.min_by(|(_, a), (_, b)| a.total_cmp(b))
+			.unwrap();
+
+		if child.fitness() > worst_fitness {
+			population[worst] = child;
+			true
+		} else {
+			false
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	struct GeneSum;
+
+	impl Landscape for GeneSum {
+		fn fitness(chromosome: &Chromosome) -> f32 {
+			chromosome.iter().sum::<f32>().max(0.0)
+		}
+	}
+
+	type SumIndividual = TestIndividual<GeneSum>;
+
+	fn population(rng: &mut dyn RngCore) -> Vec<SumIndividual> {
+		(0..20)
+			.map(|_| SumIndividual::create((0..5).map(|_| rng.gen_range(0.0..1.0)).collect()))
+			.collect()
+	}
+
+	fn mean_fitness(population: &[SumIndividual]) -> f32 {
+		PopulationStats::new(population).mean_fitness()
+	}
+
+	#[test]
+	fn replaces_the_worst_individual_only_with_a_fitter_child() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let ga = SteadyStateGA::new(
+			RouletteWheelSelection,
+			UniformCrossover,
+			GaussianMutation::new(0.5, 0.5),
+		);
+
+		let mut population = population(&mut rng);
+
+		for _ in 0..100 {
+			let before: Vec<f32> = population.iter().map(|i| i.fitness()).collect();
+			let replaced = ga.evolve_one(&mut rng, &mut population);
+			let after: Vec<f32> = population.iter().map(|i| i.fitness()).collect();
+
+			let changed: Vec<usize> = (0..before.len()).filter(|&i| before[i] != after[i]).collect();
+
+			if replaced {
+				assert_eq!(changed.len(), 1);
+				let worst = before.iter().copied().fold(f32::MAX, f32::min);
+				assert_eq!(before[changed[0]], worst);
+				assert!(after[changed[0]] > worst);
+			} else {
+				assert!(changed.is_empty());
+			}
+		}
+	}
+
+	#[test]
+	fn keeps_up_with_the_generational_ga() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let initial = population(&mut rng);
+
+		let mut generational = GeneticAlgorithm::new(
+			RouletteWheelSelection,
+			UniformCrossover,
+			GaussianMutation::new(0.5, 0.5),
+		);
+		let mut population: Vec<SumIndividual> = initial
+			.iter()
+			.map(|i| SumIndividual::create(i.chromosome().clone()))
+			.collect();
+		for _ in 0..100 {
+			population = generational.evolve(&mut rng, &population);
+		}
+		let threshold = mean_fitness(&population);
+
+		let steady_state = SteadyStateGA::new(
+			RouletteWheelSelection,
+			UniformCrossover,
+			GaussianMutation::new(0.5, 0.5),
+		);
+		let mut population = initial;
+		for _ in 0..100 * population.len() {
+			steady_state.evolve_one(&mut rng, &mut population);
+		}
+
+		assert!(
+			mean_fitness(&population) > threshold,
+			"{} <= {}",
+			mean_fitness(&population),
+			threshold,
+		);
+	}
+}
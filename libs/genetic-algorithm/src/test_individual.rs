@@ -0,0 +1,68 @@
+use crate::*;
+use std::marker::PhantomData;
+
+/// Scores the chromosomes `TestIndividual`s get created from.
+pub(crate) trait Landscape {
+	fn fitness(chromosome: &Chromosome) -> f32;
+}
+
+/// Mean of the genes.
+#[derive(Clone, Debug)]
+pub(crate) struct MeanGene;
+
+impl Landscape for MeanGene {
+	fn fitness(chromosome: &Chromosome) -> f32 {
+		chromosome.iter().sum::<f32>() / chromosome.len() as f32
+	}
+}
+
+/// Individual shared by the tests; either created from a chromosome, which
+/// `L` scores, or given just a fitness through `new()`.
+#[derive(Clone, Debug)]
+pub(crate) struct TestIndividual<L = MeanGene> {
+	chromosome: Option<Chromosome>,
+	fitness: f32,
+	landscape: PhantomData<L>,
+}
+
+impl TestIndividual {
+	pub(crate) fn new(fitness: f32) -> Self {
+		Self {
+			chromosome: None,
+			fitness,
+			landscape: PhantomData,
+		}
+	}
+}
+
+impl<L: Landscape> Individual for TestIndividual<L> {
+	fn create(chromosome: Chromosome) -> Self {
+		Self {
+			fitness: L::fitness(&chromosome),
+			chromosome: Some(chromosome),
+			landscape: PhantomData,
+		}
+	}
+
+	fn chromosome(&self) -> &Chromosome {
+		match &self.chromosome {
+			Some(chromosome) => chromosome,
+			None => panic!("No chromosome"),
+		}
+	}
+
+	fn fitness(&self) -> f32 {
+		self.fitness
+	}
+}
+
+// Fitnesses of created individuals follow from their chromosomes
+impl<L> PartialEq for TestIndividual<L> {
+	fn eq(&self, other: &Self) -> bool {
+		match (&self.chromosome, &other.chromosome) {
+			(Some(a), Some(b)) => a == b,
+			(None, None) => self.fitness == other.fitness,
+			_ => false,
+		}
+	}
+}
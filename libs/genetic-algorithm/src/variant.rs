@@ -0,0 +1,160 @@
+use crate::*;
+
+/// Every built-in crossover method, dispatched with a `match` instead of
+/// through a vtable; anything else goes into `Custom`.
+pub enum CrossoverVariant {
+	Uniform(UniformCrossover),
+	Custom(Box<dyn CrossoverMethod>),
+}
+
+impl CrossoverVariant {
+	pub fn custom(method: impl CrossoverMethod + 'static) -> Self {
+		Self::Custom(Box::new(method))
+	}
+}
+
+impl CrossoverMethod for CrossoverVariant {
+	fn crossover(
+		&self,
+		rng: &mut dyn RngCore,
+		parent_a: &Chromosome,
+		parent_b: &Chromosome,
+	) -> Chromosome {
+		match self {
+			Self::Uniform(method) => method.crossover(rng, parent_a, parent_b),
+			Self::Custom(method) => method.crossover(rng, parent_a, parent_b),
+		}
+	}
+}
+
+impl From<UniformCrossover> for CrossoverVariant {
+	fn from(method: UniformCrossover) -> Self {
+		Self::Uniform(method)
+	}
+}
+
+/// Every built-in mutation method, dispatched with a `match` instead of
+/// through a vtable; anything else goes into `Custom`.
+pub enum MutationVariant {
+	Gaussian(GaussianMutation),
+	Uniform(UniformMutation),
+	Levy(LevyMutation),
+	Boltzmann(BoltzmannMutation),
+	Custom(Box<dyn MutationMethod>),
+}
+
+impl MutationVariant {
+	pub fn custom(method: impl MutationMethod + 'static) -> Self {
+		Self::Custom(Box::new(method))
+	}
+}
+
+impl MutationMethod for MutationVariant {
+	fn mutate(&self, rng: &mut dyn RngCore, chromosome: &mut Chromosome) {
+		match self {
+			Self::Gaussian(method) => method.mutate(rng, chromosome),
+			Self::Uniform(method) => method.mutate(rng, chromosome),
+			Self::Levy(method) => method.mutate(rng, chromosome),
+			Self::Boltzmann(method) => method.mutate(rng, chromosome),
+			Self::Custom(method) => method.mutate(rng, chromosome),
+		}
+	}
+
+	fn as_temperature_mut(&mut self) -> Option<&mut dyn TemperatureMutationMethod> {
+		match self {
+			Self::Gaussian(method) => method.as_temperature_mut(),
+			Self::Uniform(method) => method.as_temperature_mut(),
+			Self::Levy(method) => method.as_temperature_mut(),
+			Self::Boltzmann(method) => method.as_temperature_mut(),
+			Self::Custom(method) => method.as_temperature_mut(),
+		}
+	}
+}
+
+impl From<GaussianMutation> for MutationVariant {
+	fn from(method: GaussianMutation) -> Self {
+		Self::Gaussian(method)
+	}
+}
+
+impl From<UniformMutation> for MutationVariant {
+	fn from(method: UniformMutation) -> Self {
+		Self::Uniform(method)
+	}
+}
+
+impl From<LevyMutation> for MutationVariant {
+	fn from(method: LevyMutation) -> Self {
+		Self::Levy(method)
+	}
+}
+
+impl From<BoltzmannMutation> for MutationVariant {
+	fn from(method: BoltzmannMutation) -> Self {
+		Self::Boltzmann(method)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn chromosome() -> Chromosome {
+		(0..50).map(|gene| gene as f32 / 10.0).collect()
+	}
+
+	#[test]
+	fn variants_match_their_methods() {
+		let mutation = GaussianMutation::new(0.5, 0.5);
+		let (parent_a, parent_b) = (chromosome(), chromosome().iter().map(|gene| -gene).collect());
+
+		let direct = {
+			let mut rng = ChaCha8Rng::seed_from_u64(0);
+			let mut child = UniformCrossover.crossover(&mut rng, &parent_a, &parent_b);
+			mutation.mutate(&mut rng, &mut child);
+			child
+		};
+
+		let variants = {
+			let mut rng = ChaCha8Rng::seed_from_u64(0);
+			let crossover = CrossoverVariant::from(UniformCrossover);
+			let mut child = crossover.crossover(&mut rng, &parent_a, &parent_b);
+			MutationVariant::from(mutation).mutate(&mut rng, &mut child);
+			child
+		};
+
+		assert_eq!(direct, variants);
+	}
+
+	#[test]
+	fn custom() {
+		struct Negate;
+
+		impl MutationMethod for Negate {
+			fn mutate(&self, _: &mut dyn RngCore, chromosome: &mut Chromosome) {
+				for gene in chromosome.iter_mut() {
+					*gene = -*gene;
+				}
+			}
+		}
+
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut negated = chromosome();
+		MutationVariant::custom(Negate).mutate(&mut rng, &mut negated);
+
+		assert!(negated.iter().zip(chromosome().iter()).all(|(a, b)| *a == -b));
+	}
+
+	#[test]
+	fn as_temperature_mut() {
+		let mut boltzmann = MutationVariant::from(BoltzmannMutation::new(0.5, 1.0));
+		let mut gaussian = MutationVariant::from(GaussianMutation::new(0.5, 1.0));
+
+		boltzmann.as_temperature_mut().unwrap().cool(0.5);
+
+		assert_eq!(boltzmann.as_temperature_mut().unwrap().temperature(), 0.5);
+		assert!(gaussian.as_temperature_mut().is_none());
+	}
+}
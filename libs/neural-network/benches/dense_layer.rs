@@ -0,0 +1,36 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use lib_neural_network::{LayerTopology, Network};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const INPUT_SIZE: usize = 32;
+
+fn layer(c: &mut Criterion) {
+	let mut group = c.benchmark_group("layer");
+
+	for output_size in [8, 16, 32, 64] {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let topology = [LayerTopology::new(INPUT_SIZE), LayerTopology::new(output_size)];
+		let mut network: Network = Network::random(&mut rng, &topology);
+		let dense = Network::from_weights(&topology, network.weights())
+			.into_layers()
+			.remove(0)
+			.into_dense();
+
+		let inputs = vec![0.5; INPUT_SIZE];
+		let mut outputs = vec![0.0; output_size];
+
+		group.bench_with_input(BenchmarkId::new("neurons", output_size), &inputs, |b, inputs| {
+			b.iter(|| network.propagate(black_box(inputs.clone())))
+		});
+
+		group.bench_with_input(BenchmarkId::new("dense", output_size), &inputs, |b, inputs| {
+			b.iter(|| dense.propagate_into(black_box(inputs), &mut outputs))
+		});
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, layer);
+criterion_main!(benches);
@@ -0,0 +1,143 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use lib_neural_network::{LayerTopology, Network};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+fn neuron(c: &mut Criterion) {
+	let mut group = c.benchmark_group("neuron");
+
+	for input_size in [9, 512] {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network: Network = Network::random(
+			&mut rng,
+			&[LayerTopology::new(input_size), LayerTopology::new(1)],
+		);
+		let inputs = vec![0.5; input_size];
+
+		group.bench_with_input(BenchmarkId::from_parameter(input_size), &inputs, |b, inputs| {
+			b.iter(|| network.propagate(black_box(inputs.clone())))
+		});
+	}
+
+	group.finish();
+}
+
+fn network(c: &mut Criterion) {
+	let mut group = c.benchmark_group("network");
+
+	for hidden_size in [10, 18, 64] {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network: Network = Network::random(
+			&mut rng,
+			&[
+				LayerTopology::new(5),
+				LayerTopology::new(hidden_size),
+				LayerTopology::new(2),
+			],
+		);
+		let inputs = vec![0.5; 5];
+
+		group.bench_with_input(BenchmarkId::from_parameter(hidden_size), &inputs, |b, inputs| {
+			b.iter(|| network.propagate(black_box(inputs.clone())))
+		});
+	}
+
+	group.finish();
+}
+
+fn buffer(c: &mut Criterion) {
+	let mut group = c.benchmark_group("buffer");
+	let mut rng = ChaCha8Rng::from_seed(Default::default());
+	let mut network: Network = Network::random(
+		&mut rng,
+		&[
+			LayerTopology::new(9),
+			LayerTopology::new(18),
+			LayerTopology::new(2),
+		],
+	);
+	let inputs = vec![0.5; 9];
+	let mut buffer = Vec::new();
+
+	group.bench_function("propagate", |b| {
+		b.iter(|| network.propagate(black_box(inputs.clone())))
+	});
+
+	group.bench_function("propagate_into", |b| {
+		b.iter(|| network.propagate_into(black_box(&inputs), &mut buffer))
+	});
+
+	group.finish();
+}
+
+// The simulation's brains, followed by a couple of larger networks
+const SHAPES: [&[usize]; 3] = [&[9, 18, 2], &[64, 128, 16], &[256, 256, 256, 10]];
+
+// One propagation per animal, as in a single simulation step
+const BATCH_SIZE: usize = 40;
+
+// Label of a shape, e.g. "9-18-2", along with its topology
+fn shape_topology(shape: &[usize]) -> (String, Vec<LayerTopology>) {
+	let label = shape.iter().map(|size| size.to_string()).collect::<Vec<_>>().join("-");
+	let topology = shape.iter().map(|&size| LayerTopology::new(size)).collect();
+
+	(label, topology)
+}
+
+fn from_weights(c: &mut Criterion) {
+	let mut group = c.benchmark_group("from_weights");
+
+	for shape in SHAPES {
+		let (label, topology) = shape_topology(shape);
+		let weights = vec![0.5; LayerTopology::param_count(&topology)];
+
+		group.bench_with_input(BenchmarkId::new("iterator", &label), &weights, |b, weights| {
+			b.iter(|| Network::<f32>::from_weights(&topology, black_box(weights.iter().copied())))
+		});
+
+		group.bench_with_input(BenchmarkId::new("slice", &label), &weights, |b, weights| {
+			b.iter(|| Network::<f32>::from_weight_slice(&topology, black_box(weights)))
+		});
+	}
+
+	group.finish();
+}
+
+fn shapes(c: &mut Criterion) {
+	let mut group = c.benchmark_group("shapes");
+
+	for shape in SHAPES {
+		let (label, topology) = shape_topology(shape);
+
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network: Network = Network::random(&mut rng, &topology);
+		let quantized = network.quantize();
+
+		let batch: Vec<Vec<f32>> = (0..BATCH_SIZE)
+			.map(|_| (0..shape[0]).map(|_| rng.gen_range(-1.0..=1.0)).collect())
+			.collect();
+
+		group.bench_with_input(BenchmarkId::new("propagate", &label), &batch[0], |b, inputs| {
+			b.iter(|| network.propagate(black_box(inputs.clone())))
+		});
+
+		group.bench_with_input(BenchmarkId::new("batch", &label), &batch, |b, batch| {
+			let mut buffer = Vec::new();
+
+			b.iter(|| {
+				for inputs in black_box(batch) {
+					network.propagate_into(inputs, &mut buffer);
+				}
+			})
+		});
+
+		group.bench_with_input(BenchmarkId::new("quantized", &label), &batch[0], |b, inputs| {
+			b.iter(|| quantized.propagate(black_box(inputs)))
+		});
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, neuron, network, buffer, from_weights, shapes);
+criterion_main!(benches);
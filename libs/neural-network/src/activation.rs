@@ -0,0 +1,67 @@
+use crate::*;
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Activation {
+	#[default]
+	Relu,
+	Tanh,
+}
+
+impl Activation {
+	pub fn apply<T: Float>(self, x: T) -> T {
+		match self {
+			Self::Relu => x.max(T::zero()),
+			Self::Tanh => x.tanh(),
+		}
+	}
+
+	/// Derivative of the activation at pre-activation `x`.
+	pub fn derivative<T: Float>(self, x: T) -> T {
+		match self {
+			Self::Relu => {
+				if x > T::zero() {
+					T::one()
+				} else {
+					T::zero()
+				}
+			}
+			Self::Tanh => T::one() - x.tanh().powi(2),
+		}
+	}
+}
+
+impl fmt::Display for Activation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Relu => write!(f, "ReLU"),
+			Self::Tanh => write!(f, "tanh"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use approx::assert_relative_eq;
+
+	#[test]
+	fn relu() {
+		assert_eq!(Activation::Relu.apply(-2.0), 0.0);
+		assert_eq!(Activation::Relu.apply(2.0), 2.0);
+		assert_eq!(Activation::Relu.derivative(-2.0), 0.0);
+		assert_eq!(Activation::Relu.derivative(2.0), 1.0);
+	}
+
+	#[test]
+	fn tanh() {
+		assert_relative_eq!(Activation::Tanh.apply(0.5), 0.5f32.tanh());
+		assert_relative_eq!(Activation::Tanh.derivative(0.0), 1.0);
+
+		// Matches a central difference
+		let (x, h) = (0.3f64, 1e-6);
+		let expected = (Activation::Tanh.apply(x + h) - Activation::Tanh.apply(x - h)) / (2.0 * h);
+		assert_relative_eq!(Activation::Tanh.derivative(x), expected, epsilon = 1e-8);
+	}
+}
@@ -0,0 +1,136 @@
+use crate::*;
+
+/// Dense layer with all of its parameters stored in two flat vectors, which
+/// is friendlier to the cache than `Layer`'s vector of neurons.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseLayer<T = f32> {
+	biases: Vec<T>,
+	// Row-major, i.e. `input_size` consecutive weights per output
+	weights: Vec<T>,
+	input_size: usize,
+	output_size: usize,
+	activation: Activation,
+}
+
+impl<T: Float> DenseLayer<T> {
+	pub fn biases(&self) -> &[T] {
+		&self.biases
+	}
+
+	pub fn weights(&self) -> &[T] {
+		&self.weights
+	}
+
+	pub fn input_size(&self) -> usize {
+		self.input_size
+	}
+
+	pub fn output_size(&self) -> usize {
+		self.output_size
+	}
+
+	pub fn activation(&self) -> Activation {
+		self.activation
+	}
+
+	pub fn propagate(&self, inputs: &[T]) -> Vec<T> {
+		let mut outputs = vec![T::zero(); self.output_size];
+		self.propagate_into(inputs, &mut outputs);
+		outputs
+	}
+
+	pub fn propagate_into(&self, inputs: &[T], outputs: &mut [T]) {
+		assert_eq!(inputs.len(), self.input_size);
+		assert_eq!(outputs.len(), self.output_size);
+
+		let rows = self.weights.chunks_exact(self.input_size);
+
+		for ((output, bias), weights) in outputs.iter_mut().zip(&self.biases).zip(rows) {
+			*output = self.activation.apply(*bias + dot(inputs, weights));
+		}
+	}
+}
+
+impl<T: Float> Layer<T> {
+	/// Converts the layer into the flat layout; neurons without bias get a
+	/// zero one.
+	///
+	/// Panics for recurrent and normalize layers, which `DenseLayer` cannot
+	/// represent.
+	pub fn into_dense(self) -> DenseLayer<T> {
+		assert_eq!(self.kind, LayerKind::Dense, "got a {} layer", self.kind);
+
+		let input_size = self.input_size() + self.skip.map_or(0, |skip| skip.size);
+		let output_size = self.neurons.len();
+
+		let mut biases = Vec::with_capacity(output_size);
+		let mut weights = Vec::with_capacity(input_size * output_size);
+
+		for neuron in self.neurons {
+			biases.push(neuron.bias());
+			weights.extend(neuron.weights);
+		}
+
+		DenseLayer {
+			biases,
+			weights,
+			input_size,
+			output_size,
+			activation: self.activation,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	#[test]
+	fn into_dense() {
+		let network: Network = Network::from_weights(
+			&[LayerTopology::new(2), LayerTopology::new(2)],
+			vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6],
+		);
+		let layer = network.into_layers().remove(0).into_dense();
+
+		assert_eq!(layer.biases(), &[0.1, 0.4]);
+		assert_eq!(layer.weights(), &[0.2, 0.3, 0.5, 0.6]);
+		assert_eq!((layer.input_size(), layer.output_size()), (2, 2));
+	}
+
+	#[test]
+	fn propagate_matches_layer() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let topology = [
+			LayerTopology::new(7),
+			LayerTopology::new(20)
+				.without_bias()
+				.with_activation(Activation::Tanh),
+		];
+		let mut network: Network = Network::random(&mut rng, &topology);
+		let dense = Network::from_weights(&topology, network.weights())
+			.into_layers()
+			.remove(0)
+			.into_dense();
+
+		let inputs: Vec<f32> = (0..7).map(|n| n as f32 / 7.0 - 0.5).collect();
+
+		assert_eq!(dense.propagate(&inputs), network.propagate(inputs));
+	}
+
+	#[test]
+	#[should_panic(expected = "got a recurrent layer")]
+	fn rejects_recurrent_layers() {
+		let network: Network = Network::from_weights(
+			&[
+				LayerTopology::new(1),
+				LayerTopology::new(1).with_kind(LayerKind::Recurrent),
+			],
+			vec![0.0, 1.0, 1.0],
+		);
+
+		network.into_layers().remove(0).into_dense();
+	}
+}
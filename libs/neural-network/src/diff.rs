@@ -0,0 +1,166 @@
+use crate::*;
+
+/// Summary of how two networks of the same shape differ, see
+/// `Network::diff()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkDiff<T = f32> {
+	/// L2 distance between the parameters of each pair of layers
+	pub layer_distances: Vec<T>,
+	/// Largest absolute difference between two corresponding parameters
+	pub max_delta: T,
+	/// Where `max_delta` was found; `None` if the networks have no
+	/// parameters at all
+	pub max_delta_at: Option<ParamLocation>,
+	/// How many parameters differ by more than the epsilon
+	pub changed: usize,
+}
+
+/// Coordinates of a single parameter within a network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParamLocation {
+	pub layer: usize,
+	pub neuron: usize,
+	/// Index into the neuron's weights, or `None` for its bias
+	pub weight: Option<usize>,
+}
+
+impl<T: Float> NetworkDiff<T> {
+	/// Returns the index of the layer that moved the most.
+	pub fn most_changed_layer(&self) -> Option<usize> {
+		self.layer_distances
+			.iter()
+			.enumerate()
+			.max_by(|(_, a), (_, b)| a.total_cmp(b))
+			.map(|(i, _)| i)
+	}
+}
+
+impl<T: Float> Network<T> {
+	/// Compares the parameters of `self` and `other`, e.g. a parent and its
+	/// mutated child, counting as changed whatever moved by more than
+	/// `epsilon`.
+	pub fn diff(&self, other: &Self, epsilon: T) -> Result<NetworkDiff<T>, TopologyMismatch> {
+		if !self.params_match(other, |_, _| true) {
+			return Err(TopologyMismatch);
+		}
+
+		let mut diff = NetworkDiff {
+			layer_distances: Vec::with_capacity(self.layers.len()),
+			max_delta: T::zero(),
+			max_delta_at: None,
+			changed: 0,
+		};
+
+		for (l, (a, b)) in self.layers.iter().zip(&other.layers).enumerate() {
+			let mut squared_distance = T::zero();
+
+			for (n, (a_neuron, b_neuron)) in a.neurons.iter().zip(&b.neurons).enumerate() {
+				// Mirrored params are copies of other neurons' and so
+				// don't count twice
+				let (own_bias, own_weights) = a.own_params(n);
+
+				let biases = a_neuron
+					.bias
+					.zip(b_neuron.bias)
+					.filter(|_| own_bias)
+					.map(|pair| (None, pair));
+
+				let weights = a_neuron.weights[..own_weights]
+					.iter()
+					.zip(&b_neuron.weights)
+					.enumerate()
+					.map(|(w, (a, b))| (Some(w), (*a, *b)));
+
+				for (weight, (a, b)) in biases.into_iter().chain(weights) {
+					let delta = (a - b).abs();
+
+					squared_distance += delta * delta;
+
+					if delta > epsilon {
+						diff.changed += 1;
+					}
+
+					if diff.max_delta_at.is_none() || delta > diff.max_delta {
+						diff.max_delta = delta;
+						diff.max_delta_at = Some(ParamLocation { layer: l, neuron: n, weight });
+					}
+				}
+			}
+
+			diff.layer_distances.push(squared_distance.sqrt());
+		}
+
+		Ok(diff)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn layers() -> [LayerTopology; 3] {
+		[LayerTopology::new(2), LayerTopology::new(3), LayerTopology::new(2)]
+	}
+
+	#[test]
+	fn identical() {
+		let network = Network::<f32>::constant(&layers(), 0.5);
+		let diff = network.diff(&network, 0.0).unwrap();
+
+		assert_eq!(diff.layer_distances, vec![0.0, 0.0]);
+		assert_eq!(diff.max_delta, 0.0);
+		assert_eq!(diff.changed, 0);
+	}
+
+	#[test]
+	fn single_changed_weight() {
+		let parent = Network::<f32>::constant(&layers(), 0.5);
+
+		// Layer 0 has 3 * (1 + 2) params, then each neuron of layer 1 has 1 + 3
+		let mut weights = parent.weights();
+		weights[9 + 4 + 2] = -0.25;
+		let child = Network::from_weights(&layers(), weights);
+
+		let diff = parent.diff(&child, 0.01).unwrap();
+
+		assert_eq!(diff.layer_distances, vec![0.0, 0.75]);
+		assert_eq!(diff.most_changed_layer(), Some(1));
+		assert_eq!(diff.max_delta, 0.75);
+		assert_eq!(
+			diff.max_delta_at,
+			Some(ParamLocation { layer: 1, neuron: 1, weight: Some(1) }),
+		);
+		assert_eq!(diff.changed, 1);
+	}
+
+	#[test]
+	fn changes_below_epsilon_are_not_counted() {
+		let parent = Network::<f32>::constant(&layers(), 0.5);
+		let child = Network::<f32>::constant(&layers(), 0.505);
+
+		assert_eq!(parent.diff(&child, 0.01).unwrap().changed, 0);
+		assert_eq!(parent.diff(&child, 0.001).unwrap().changed, 17);
+	}
+
+	#[test]
+	fn mirrored_params_are_counted_once() {
+		let layers = [
+			LayerTopology::new(4),
+			LayerTopology::new(2).with_mirrored_weights(),
+			LayerTopology::new(1),
+		];
+
+		let parent = Network::<f32>::constant(&layers, 0.5);
+		let child = Network::<f32>::constant(&layers, 0.6);
+
+		assert_eq!(parent.diff(&child, 0.01).unwrap().changed, parent.param_count());
+	}
+
+	#[test]
+	fn topology_mismatch() {
+		let a = Network::<f32>::zeros(&layers());
+		let b = Network::<f32>::zeros(&[LayerTopology::new(2), LayerTopology::new(2)]);
+
+		assert_eq!(a.diff(&b, 0.0), Err(TopologyMismatch));
+	}
+}
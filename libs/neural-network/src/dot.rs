@@ -0,0 +1,210 @@
+use crate::*;
+use alloc::{format, string::String};
+use core::fmt::Write;
+
+#[derive(Clone, Debug)]
+pub struct DotOptions {
+	// Edges whose absolute weight is below this value are left out
+	pub threshold: f32,
+	// Whether to scale edge thickness by |weight| and color them by sign
+	pub scale_edges: bool,
+}
+
+impl Default for DotOptions {
+	fn default() -> Self {
+		Self {
+			threshold: 0.0,
+			scale_edges: false,
+		}
+	}
+}
+
+impl<T: Float> Network<T> {
+	/// Renders the network as a Graphviz digraph.
+	pub fn to_dot(&self) -> String {
+		self.to_dot_with(&DotOptions::default())
+	}
+
+	pub fn to_dot_with(&self, options: &DotOptions) -> String {
+		let to_f64 = |value: T| value.to_f64().unwrap_or(f64::NAN);
+
+		let max_weight = self
+			.layers
+			.iter()
+			.flat_map(|layer| &layer.neurons)
+			.flat_map(|neuron| &neuron.weights)
+			.map(|weight| to_f64(*weight).abs())
+			.fold(0.0, f64::max);
+
+		let mut dot = String::new();
+		writeln!(dot, "digraph network {{").unwrap();
+		writeln!(dot, "\trankdir=LR;").unwrap();
+
+		let input_size = self.layers.first().map_or(0, |layer| layer.input_size());
+		for input in 0..input_size {
+			writeln!(dot, "\tn0_{} [label=\"input {}\"];", input, input).unwrap();
+		}
+
+		for (l, layer) in self.layers.iter().enumerate() {
+			let (from, to) = (l, l + 1);
+			let input_size = layer.input_size();
+			let skip = layer.skip.map_or((0, 0), |skip| (skip.from, skip.size));
+
+			for n in 0..layer.output_size() {
+				if layer.kind.has_params() {
+					let bias = to_f64(layer.neurons[n].bias());
+					writeln!(dot, "\tn{}_{} [label=\"{:.3}\"];", to, n, bias).unwrap();
+				} else {
+					writeln!(dot, "\tn{}_{} [label=\"norm\"];", to, n).unwrap();
+				}
+
+				for (i, weight) in layer.connections(n) {
+					// Inputs past the layer's own come from the skipped
+					// layer, and then from its own previous outputs
+					let source = if i < input_size {
+						format!("n{}_{}", from, i)
+					} else if i < input_size + skip.1 {
+						format!("n{}_{}", skip.0, i - input_size)
+					} else {
+						format!("n{}_{}", to, i - input_size - skip.1)
+					};
+
+					let weight = match weight {
+						Some(weight) => to_f64(weight),
+						None => {
+							writeln!(dot, "\t{} -> n{}_{};", source, to, n).unwrap();
+							continue;
+						}
+					};
+
+					if weight.abs() < options.threshold as f64 {
+						continue;
+					}
+
+					write!(dot, "\t{} -> n{}_{} [label=\"{:.3}\"", source, to, n, weight).unwrap();
+
+					if i >= input_size + skip.1 {
+						write!(dot, ", style=dashed").unwrap();
+					}
+
+					if options.scale_edges && max_weight > 0.0 {
+						let color = if weight < 0.0 { "red" } else { "blue" };
+						let width = 0.5 + 4.5 * weight.abs() / max_weight;
+						write!(dot, ", color={}, penwidth={:.2}", color, width).unwrap();
+					}
+
+					writeln!(dot, "];").unwrap();
+				}
+			}
+		}
+
+		dot.push_str("}\n");
+		dot
+	}
+}
+
+impl<T: Float> Layer<T> {
+	// Inputs of the `n`-th neuron, as indices into the layer's inputs
+	// followed by its state, along with their weights; normalize layers have
+	// none and pass their `n`-th input on as is
+	fn connections(&self, n: usize) -> Vec<(usize, Option<T>)> {
+		match self.kind {
+			LayerKind::Dense | LayerKind::Recurrent => self.neurons[n]
+				.weights
+				.iter()
+				.map(|weight| Some(*weight))
+				.enumerate()
+				.collect(),
+			LayerKind::Normalize => vec![(n, None)],
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn network() -> Network {
+		Network::from_weights(
+			&[
+				LayerTopology::new(2),
+				LayerTopology::new(3),
+				LayerTopology::new(1),
+			],
+			vec![
+				0.1, 0.5, -0.5, //
+				0.2, 0.01, 0.3, //
+				0.3, -0.7, 0.0, //
+				0.4, 1.0, -1.0, 0.02,
+			],
+		)
+	}
+
+	fn count(dot: &str, pattern: &str) -> usize {
+		dot.lines().filter(|line| line.contains(pattern)).count()
+	}
+
+	#[test]
+	fn nodes_and_edges() {
+		let dot = network().to_dot();
+
+		assert!(dot.starts_with("digraph network {"));
+		assert_eq!(count(&dot, "[label=") - count(&dot, "->"), 2 + 3 + 1);
+		assert_eq!(count(&dot, "->"), 9);
+		assert!(dot.contains("\tn1_1 -> n2_0 [label=\"-1.000\"];"));
+	}
+
+	#[test]
+	fn threshold_omits_small_weights() {
+		let options = DotOptions {
+			threshold: 0.05,
+			..Default::default()
+		};
+		let dot = network().to_dot_with(&options);
+
+		assert_eq!(count(&dot, "->"), 9 - 3);
+	}
+
+	#[test]
+	fn scale_edges() {
+		let options = DotOptions {
+			scale_edges: true,
+			..Default::default()
+		};
+		let dot = network().to_dot_with(&options);
+
+		assert!(dot.contains("\tn1_1 -> n2_0 [label=\"-1.000\", color=red, penwidth=5.00];"));
+		assert!(dot.contains("\tn0_0 -> n1_0 [label=\"0.500\", color=blue, penwidth=2.75];"));
+	}
+
+	#[test]
+	fn skip_edges() {
+		let network: Network = Network::from_weights(
+			&[
+				LayerTopology::new(1),
+				LayerTopology::new(1),
+				LayerTopology::new(1).with_skip_from(0),
+			],
+			vec![0.0, 1.0, 0.0, 0.5, 0.25],
+		);
+		let dot = network.to_dot();
+
+		assert!(dot.contains("\tn1_0 -> n2_0 [label=\"0.500\"];"));
+		assert!(dot.contains("\tn0_0 -> n2_0 [label=\"0.250\"];"));
+	}
+
+	#[test]
+	fn recurrent_edges() {
+		let network: Network = Network::from_weights(
+			&[
+				LayerTopology::new(1),
+				LayerTopology::new(2).with_kind(LayerKind::Recurrent),
+			],
+			vec![0.0, 1.0, 0.5, 0.25, 0.0, 1.0, 0.5, 0.25],
+		);
+		let dot = network.to_dot();
+
+		assert_eq!(count(&dot, "style=dashed"), 4);
+		assert!(dot.contains("\tn1_1 -> n1_0 [label=\"0.250\", style=dashed];"));
+	}
+}
@@ -0,0 +1,101 @@
+use crate::*;
+
+impl<T: Float> Network<T> {
+	/// Returns a copy with `sigma * epsilon` added to its parameters, along
+	/// with `epsilon` itself - one standard gaussian sample per parameter,
+	/// in the order of `weights()`.
+	///
+	/// For antithetic sampling, the mirrored network can be built with
+	/// `apply_update()` and the negated epsilon.
+	pub fn perturb(&self, rng: &mut dyn RngCore, sigma: T) -> (Self, Vec<T>) {
+		let mut epsilon = Vec::with_capacity(self.param_count());
+
+		let network = self.map(|weight| {
+			let sample = noise::gaussian(rng, T::one());
+			epsilon.push(sample);
+			weight + sigma * sample
+		});
+
+		(network, epsilon)
+	}
+
+	/// Adds `step * direction` to the parameters, with `direction` ordered
+	/// the same way as `weights()`.
+	pub fn apply_update(&mut self, direction: &[T], step: T) -> Result<(), WeightsError> {
+		if direction.len() != self.param_count() {
+			return Err(WeightsError::CountMismatch {
+				expected: self.param_count(),
+				actual: direction.len(),
+			});
+		}
+
+		let mut direction = direction.iter();
+
+		for layer in &mut self.layers {
+			for param in layer.params_mut() {
+				*param += step * *direction.next().unwrap();
+			}
+
+			layer.sync_mirrored();
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use approx::assert_relative_eq;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn network(rng: &mut dyn RngCore) -> Network {
+		Network::random(
+			rng,
+			&[
+				LayerTopology::new(3),
+				LayerTopology::new(4).without_bias(),
+				LayerTopology::new(2),
+			],
+		)
+	}
+
+	#[test]
+	fn perturb() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let base = network(&mut rng);
+		let (perturbed, epsilon) = base.perturb(&mut rng, 0.1);
+
+		assert_eq!(epsilon.len(), base.param_count());
+		assert!(!perturbed.approx_eq(&base, 0.0));
+
+		for ((a, b), epsilon) in perturbed.weights().iter().zip(base.weights()).zip(&epsilon) {
+			assert_relative_eq!(*a, b + 0.1 * epsilon, epsilon = 1e-6);
+		}
+	}
+
+	#[test]
+	fn apply_update_undoes_perturbation() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let base = network(&mut rng);
+		let (mut perturbed, epsilon) = base.perturb(&mut rng, 0.1);
+
+		let negated: Vec<f32> = epsilon.iter().map(|epsilon| -epsilon).collect();
+		perturbed.apply_update(&negated, 0.1).unwrap();
+
+		assert!(perturbed.approx_eq(&base, 1e-6));
+	}
+
+	#[test]
+	fn apply_update_rejects_wrong_length() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network = network(&mut rng);
+		let expected = network.param_count();
+
+		assert_eq!(
+			network.apply_update(&[1.0; 3], 0.1),
+			Err(WeightsError::CountMismatch { expected, actual: 3 })
+		);
+	}
+}
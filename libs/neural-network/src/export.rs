@@ -0,0 +1,197 @@
+use crate::*;
+use std::io::{self, Write};
+
+impl<T: Float> Network<T> {
+	/// Writes one CSV row per neuron: its layer, its index within the layer,
+	/// its bias (empty if it has none) and then its weights.
+	///
+	/// Like `weights()`, neurons of mirrored layers only get the parameters
+	/// they own, and those owning none are left out.
+	///
+	/// There's no header, since rows of different layers can have different
+	/// numbers of weights.
+	pub fn export_csv(&self, mut writer: impl Write) -> io::Result<()> {
+		for (l, layer) in self.layers.iter().enumerate() {
+			for (n, neuron) in layer.neurons.iter().enumerate() {
+				let (own_bias, own_weights) = layer.own_params(n);
+
+				if !own_bias && own_weights == 0 {
+					continue;
+				}
+
+				write!(writer, "{},{},", l, n)?;
+
+				if let (true, Some(bias)) = (own_bias, neuron.bias) {
+					write!(writer, "{:?}", bias)?;
+				}
+
+				for weight in &neuron.weights[..own_weights] {
+					write!(writer, ",{:?}", weight)?;
+				}
+
+				writeln!(writer)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Writes `weights()` as a one-dimensional NumPy array, in the `.npy`
+	/// format (version 1.0), so that `numpy.load()` can read it.
+	pub fn export_npy(&self, mut writer: impl Write) -> io::Result<()> {
+		let weights = self.weights();
+		let f32_sized = core::mem::size_of::<T>() == 4;
+
+		let mut header = format!(
+			"{{'descr': '<f{}', 'fortran_order': False, 'shape': ({},), }}",
+			if f32_sized { 4 } else { 8 },
+			weights.len(),
+		);
+
+		// The magic string, version and header length take 10 bytes; the
+		// header is padded so that the data starts at a multiple of 64
+		let len = 10 + header.len() + 1;
+		header.extend(core::iter::repeat(' ').take((64 - len % 64) % 64));
+		header.push('\n');
+
+		writer.write_all(b"\x93NUMPY\x01\x00")?;
+		writer.write_all(&(header.len() as u16).to_le_bytes())?;
+		writer.write_all(header.as_bytes())?;
+
+		for weight in weights {
+			if f32_sized {
+				writer.write_all(&weight.to_f32().unwrap().to_le_bytes())?;
+			} else {
+				writer.write_all(&weight.to_f64().unwrap().to_le_bytes())?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn network() -> Network {
+		Network::from_weights(
+			&[
+				LayerTopology::new(2),
+				LayerTopology::new(2).without_bias(),
+				LayerTopology::new(1),
+			],
+			vec![0.5, -1.0, 0.25, 2.0, 0.1, -0.75, 3.0],
+		)
+	}
+
+	#[test]
+	fn export_csv() {
+		let mut csv = Vec::new();
+		network().export_csv(&mut csv).unwrap();
+
+		let rows: Vec<Vec<&str>> = std::str::from_utf8(&csv)
+			.unwrap()
+			.lines()
+			.map(|line| line.split(',').collect())
+			.collect();
+
+		assert_eq!(
+			rows,
+			vec![
+				vec!["0", "0", "", "0.5", "-1.0"],
+				vec!["0", "1", "", "0.25", "2.0"],
+				vec!["1", "0", "0.1", "-0.75", "3.0"],
+			],
+		);
+
+		let weights: Vec<f32> = rows
+			.iter()
+			.flat_map(|row| &row[2..])
+			.filter(|value| !value.is_empty())
+			.map(|value| value.parse().unwrap())
+			.collect();
+
+		assert_eq!(weights, network().weights());
+	}
+
+	#[test]
+	fn export_npy() {
+		let mut npy = Vec::new();
+		network().export_npy(&mut npy).unwrap();
+
+		assert_eq!(&npy[..8], b"\x93NUMPY\x01\x00");
+
+		let header_len = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+		let header = std::str::from_utf8(&npy[10..10 + header_len]).unwrap();
+
+		assert_eq!((10 + header_len) % 64, 0);
+		assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (7,), }"));
+		assert!(header.ends_with('\n'));
+
+		let payload = &npy[10 + header_len..];
+		assert_eq!(payload.len(), 7 * 4);
+
+		let weights: Vec<f32> = payload
+			.chunks_exact(4)
+			.map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+			.collect();
+
+		assert_eq!(weights, network().weights());
+	}
+
+	#[test]
+	fn export_mirrored() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let network: Network = Network::random(
+			&mut rng,
+			&[
+				LayerTopology::new(5),
+				LayerTopology::new(3).with_mirrored_weights(),
+				LayerTopology::new(2),
+			],
+		);
+
+		let mut csv = Vec::new();
+		network.export_csv(&mut csv).unwrap();
+
+		let rows: Vec<Vec<&str>> = std::str::from_utf8(&csv)
+			.unwrap()
+			.lines()
+			.map(|line| line.split(',').collect())
+			.collect();
+
+		// The third hidden neuron mirrors the first one
+		assert_eq!(rows.len(), 4);
+
+		let weights: Vec<f32> = rows
+			.iter()
+			.flat_map(|row| &row[2..])
+			.filter(|value| !value.is_empty())
+			.map(|value| value.parse().unwrap())
+			.collect();
+
+		assert_eq!(weights, network.weights());
+
+		let mut npy = Vec::new();
+		network.export_npy(&mut npy).unwrap();
+
+		let header_len = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+		assert_eq!(npy.len() - 10 - header_len, weights.len() * 4);
+	}
+
+	#[test]
+	fn export_npy_f64() {
+		let network: Network<f64> = Network::zeros(&[LayerTopology::new(3), LayerTopology::new(2)]);
+		let mut npy = Vec::new();
+		network.export_npy(&mut npy).unwrap();
+
+		let header_len = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+		let header = std::str::from_utf8(&npy[10..10 + header_len]).unwrap();
+
+		assert!(header.contains("'descr': '<f8'"));
+		assert_eq!(npy.len() - 10 - header_len, 8 * 8);
+	}
+}
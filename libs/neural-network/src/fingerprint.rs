@@ -0,0 +1,103 @@
+use crate::*;
+
+impl<T: Float> Network<T> {
+	/// Returns a hash of the network's topology and weights.
+	///
+	/// Weights are first rounded to multiples of `step`, so networks that
+	/// differ only by float noise smaller than that usually get the same
+	/// fingerprint (unless the noise carries a weight across a rounding
+	/// boundary).
+	/// The hash doesn't depend on the platform or on the compiler version,
+	/// so fingerprints can be stored and compared between runs.
+	pub fn fingerprint(&self, step: T) -> u64 {
+		assert!(step > T::zero());
+
+		let mut hasher = Fnv1a::default();
+
+		hasher.write(self.layers.len() as u64);
+
+		for layer in &self.layers {
+			hasher.write(layer.neurons.len() as u64);
+			hasher.write(layer.kind as u64);
+			hasher.write(layer.activation as u64);
+			hasher.write(layer.skip_from().map_or(0, |from| from as u64 + 1));
+
+			for neuron in &layer.neurons {
+				hasher.write(neuron.bias.is_some() as u64);
+				hasher.write(neuron.weights.len() as u64);
+
+				for weight in neuron.bias.iter().chain(&neuron.weights) {
+					let quantized = (*weight / step).round().to_i64().unwrap_or(i64::MAX);
+					hasher.write(quantized as u64);
+				}
+			}
+		}
+
+		hasher.0
+	}
+}
+
+// std's `DefaultHasher` is not guaranteed to be stable across releases
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+	fn default() -> Self {
+		Self(0xcbf29ce484222325)
+	}
+}
+
+impl Fnv1a {
+	fn write(&mut self, value: u64) {
+		for byte in value.to_le_bytes() {
+			self.0 ^= byte as u64;
+			self.0 = self.0.wrapping_mul(0x100000001b3);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn layers() -> [LayerTopology; 3] {
+		[LayerTopology::new(2), LayerTopology::new(2), LayerTopology::new(1)]
+	}
+
+	fn network(offset: f32) -> Network {
+		let weights = (0..LayerTopology::param_count(&layers())).map(|i| i as f32 * 0.5 + offset);
+
+		Network::from_weights(&layers(), weights)
+	}
+
+	#[test]
+	fn identical_networks_hash_equal() {
+		assert_eq!(network(0.0).fingerprint(0.01), network(0.0).fingerprint(0.01));
+	}
+
+	#[test]
+	fn noise_below_step_hashes_equal() {
+		assert_eq!(network(0.0).fingerprint(0.01), network(0.001).fingerprint(0.01));
+	}
+
+	#[test]
+	fn changes_above_step_hash_differently() {
+		assert_ne!(network(0.0).fingerprint(0.01), network(0.05).fingerprint(0.01));
+	}
+
+	#[test]
+	fn different_topologies_hash_differently() {
+		let other = Network::<f32>::zeros(&[LayerTopology::new(2), LayerTopology::new(3), LayerTopology::new(1)]);
+		let zeros = Network::<f32>::zeros(&layers());
+
+		assert_ne!(zeros.fingerprint(0.01), other.fingerprint(0.01));
+		assert_ne!(
+			zeros.fingerprint(0.01),
+			Network::<f32>::zeros(&[
+				LayerTopology::new(2),
+				LayerTopology::new(2).with_activation(Activation::Tanh),
+				LayerTopology::new(1),
+			])
+			.fingerprint(0.01),
+		);
+	}
+}
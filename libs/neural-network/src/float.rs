@@ -0,0 +1,8 @@
+use rand::distributions::uniform::SampleUniform;
+use core::fmt::Debug;
+use num_traits::float::TotalOrder;
+
+/// Floating point precision a network can operate in, i.e. `f32` or `f64`.
+pub trait Float: num_traits::Float + num_traits::NumAssign + TotalOrder + SampleUniform + Debug {}
+
+impl<T> Float for T where T: num_traits::Float + num_traits::NumAssign + TotalOrder + SampleUniform + Debug {}
@@ -0,0 +1,101 @@
+use crate::Float;
+use core::ops::RangeInclusive;
+
+/// Strategy used to draw the initial biases and weights of a network.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Init {
+	/// Draws from `[-range, range]` regardless of the layer's shape.
+	Uniform { range: f32 },
+	/// Glorot & Bengio: keeps the variance at `2 / (fan_in + fan_out)`.
+	Xavier,
+	/// He et al.: keeps the variance at `2 / fan_in`, suited for ReLU.
+	He,
+}
+
+impl Init {
+	pub(crate) fn range<T: Float>(&self, fan_in: usize, fan_out: usize) -> RangeInclusive<T> {
+		let cast = |value| T::from(value).expect("got a value out of range");
+
+		// Variance of U(-limit, limit) is limit^2 / 3
+		let limit = match *self {
+			Self::Uniform { range } => cast(range as f64),
+			Self::Xavier => (cast(6.0) / cast((fan_in + fan_out) as f64)).sqrt(),
+			Self::He => (cast(6.0) / cast(fan_in as f64)).sqrt(),
+		};
+
+		-limit..=limit
+	}
+}
+
+impl Default for Init {
+	fn default() -> Self {
+		Self::Uniform { range: 1.0 }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::*;
+	use approx::assert_relative_eq;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn weight_variance(init: Init, fan_in: usize, fan_out: usize) -> f32 {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let network = Network::random_with(
+			&mut rng,
+			&[
+				LayerTopology::new(fan_in),
+				LayerTopology::new(fan_out),
+			],
+			init,
+		);
+
+		let weights: Vec<f32> = network.layers()[0]
+			.neurons()
+			.iter()
+			.flat_map(|neuron| neuron.weights().iter().copied())
+			.collect();
+
+		let mean = weights.iter().sum::<f32>() / weights.len() as f32;
+		weights.iter().map(|w| (w - mean).powi(2)).sum::<f32>() / weights.len() as f32
+	}
+
+	#[test]
+	fn default_matches_random() {
+		let topology = [
+			LayerTopology::new(3),
+			LayerTopology::new(2),
+		];
+
+		let a: Network = Network::random(&mut ChaCha8Rng::from_seed(Default::default()), &topology);
+		let b = Network::random_with(
+			&mut ChaCha8Rng::from_seed(Default::default()),
+			&topology,
+			Init::default(),
+		);
+
+		assert_eq!(a, b);
+	}
+
+	#[test]
+	fn xavier() {
+		let variance = weight_variance(Init::Xavier, 1000, 100);
+
+		assert_relative_eq!(variance, 2.0 / 1100.0, max_relative = 0.05);
+	}
+
+	#[test]
+	fn he() {
+		let variance = weight_variance(Init::He, 1000, 100);
+
+		assert_relative_eq!(variance, 2.0 / 1000.0, max_relative = 0.05);
+	}
+
+	#[test]
+	fn uniform() {
+		let variance = weight_variance(Init::Uniform { range: 0.5 }, 1000, 100);
+
+		assert_relative_eq!(variance, 0.25 / 3.0, max_relative = 0.05);
+	}
+}
@@ -0,0 +1,194 @@
+use crate::*;
+
+/// Standardizes inputs (subtracts their mean and divides by their standard
+/// deviation) before they reach the network, so that sensors of very
+/// different scales all end up around [-1, 1].
+///
+/// Inputs with zero standard deviation are only centered.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputScaler<T = f32> {
+	pub means: Vec<T>,
+	pub stds: Vec<T>,
+}
+
+impl<T: Float> InputScaler<T> {
+	pub fn new(means: Vec<T>, stds: Vec<T>) -> Self {
+		assert_eq!(means.len(), stds.len());
+		assert!(stds.iter().all(|std| *std >= T::zero()));
+
+		Self { means, stds }
+	}
+
+	/// Computes the mean and standard deviation of each input over `samples`.
+	pub fn fit(samples: &[Vec<T>]) -> Self {
+		assert!(!samples.is_empty());
+
+		let count = T::from(samples.len()).unwrap();
+		let size = samples[0].len();
+		let mut means = vec![T::zero(); size];
+		let mut stds = vec![T::zero(); size];
+
+		for sample in samples {
+			assert_eq!(sample.len(), size);
+
+			for (mean, input) in means.iter_mut().zip(sample) {
+				*mean += *input;
+			}
+		}
+
+		for mean in &mut means {
+			*mean /= count;
+		}
+
+		for sample in samples {
+			for ((std, mean), input) in stds.iter_mut().zip(&means).zip(sample) {
+				*std += (*input - *mean).powi(2);
+			}
+		}
+
+		for std in &mut stds {
+			*std = (*std / count).sqrt();
+		}
+
+		Self { means, stds }
+	}
+
+	pub fn len(&self) -> usize {
+		self.means.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.means.is_empty()
+	}
+
+	pub fn apply(&self, inputs: &mut [T]) {
+		assert_eq!(inputs.len(), self.len());
+
+		for (i, input) in inputs.iter_mut().enumerate() {
+			*input = (*input - self.means[i]) * self.slope(i);
+		}
+	}
+
+	// Derivative of the `i`-th scaled input with respect to the raw one
+	pub(crate) fn slope(&self, i: usize) -> T {
+		if self.stds[i].is_zero() {
+			T::one()
+		} else {
+			self.stds[i].recip()
+		}
+	}
+}
+
+impl<T: Float> Network<T> {
+	/// Makes `propagate()` (and friends) pass inputs through `scaler` first.
+	pub fn with_input_scaler(mut self, scaler: InputScaler<T>) -> Self {
+		self.set_input_scaler(Some(scaler));
+		self
+	}
+
+	pub fn set_input_scaler(&mut self, scaler: Option<InputScaler<T>>) {
+		if let (Some(scaler), Some(layer)) = (&scaler, self.layers.first()) {
+			assert_eq!(scaler.len(), layer.input_size());
+		}
+
+		self.input_scaler = scaler;
+	}
+
+	pub fn input_scaler(&self) -> Option<&InputScaler<T>> {
+		self.input_scaler.as_ref()
+	}
+
+	pub(crate) fn scale_inputs(&self, inputs: &mut [T]) {
+		if let Some(scaler) = &self.input_scaler {
+			scaler.apply(inputs);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use approx::assert_relative_eq;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn samples() -> Vec<Vec<f32>> {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+		// Vision-like, energy-like and a constant input
+		(0..200)
+			.map(|_| vec![rng.gen_range(0.0..1.0), rng.gen_range(50.0..250.0), 3.0])
+			.collect()
+	}
+
+	#[test]
+	fn fit() {
+		let samples = samples();
+		let scaler = InputScaler::fit(&samples);
+
+		let scaled: Vec<Vec<f32>> = samples
+			.iter()
+			.map(|sample| {
+				let mut sample = sample.clone();
+				scaler.apply(&mut sample);
+				sample
+			})
+			.collect();
+
+		for i in 0..2 {
+			let mean = scaled.iter().map(|sample| sample[i]).sum::<f32>() / 200.0;
+			let variance = scaled.iter().map(|sample| (sample[i] - mean).powi(2)).sum::<f32>() / 200.0;
+
+			assert_relative_eq!(mean, 0.0, epsilon = 1e-4);
+			assert_relative_eq!(variance.sqrt(), 1.0, epsilon = 1e-4);
+		}
+
+		// Constant input only gets centered
+		assert_eq!(scaler.stds[2], 0.0);
+		assert!(scaled.iter().all(|sample| sample[2] == 0.0));
+	}
+
+	#[test]
+	fn propagate() {
+		let topology = [LayerTopology::new(3), LayerTopology::new(2)];
+		let scaler = InputScaler::new(vec![1.0, 100.0, 3.0], vec![0.5, 50.0, 0.0]);
+
+		let mut plain: Network = Network::constant(&topology, 0.5);
+		let mut scaled = Network::constant(&topology, 0.5).with_input_scaler(scaler);
+
+		let expected = plain.propagate(vec![4.0, 4.0, 1.0]);
+
+		assert_eq!(scaled.propagate(vec![3.0, 300.0, 4.0]), expected);
+
+		let mut buffer = Vec::new();
+		scaled.propagate_into(&[3.0, 300.0, 4.0], &mut buffer);
+		assert_eq!(buffer, expected);
+	}
+
+	#[test]
+	fn input_sensitivity() {
+		let topology = [LayerTopology::new(2), LayerTopology::new(1)];
+		let network: Network = Network::constant(&topology, 1.0)
+			.with_input_scaler(InputScaler::new(vec![0.0, 0.0], vec![2.0, 0.0]));
+
+		assert_eq!(network.input_sensitivity(vec![1.0, 1.0]), vec![0.5, 1.0]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn rejects_scaler_of_wrong_size() {
+		let network: Network = Network::zeros(&[LayerTopology::new(3), LayerTopology::new(1)]);
+
+		network.with_input_scaler(InputScaler::new(vec![0.0], vec![1.0]));
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde() {
+		let scaler = InputScaler::fit(&samples());
+		let json = serde_json::to_string(&scaler).unwrap();
+
+		assert_eq!(serde_json::from_str::<InputScaler>(&json).unwrap(), scaler);
+	}
+}
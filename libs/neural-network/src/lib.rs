@@ -1,71 +1,643 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "dense-layer")]
+mod dense_layer;
+mod activation;
+mod diff;
+mod dot;
+mod evolution_strategies;
+#[cfg(feature = "io")]
+mod export;
+mod fingerprint;
+mod float;
+mod init;
+mod input_scaler;
+mod noise;
+mod plastic;
+mod quantized;
+mod sensitivity;
+mod summary;
+mod surgery;
+mod trace;
+mod training;
+
+#[cfg(feature = "dense-layer")]
+pub use self::dense_layer::*;
+pub use self::{
+	activation::*, diff::*, dot::*, float::*, init::*, input_scaler::*, plastic::*, quantized::*,
+	trace::*, training::*,
+};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::RangeInclusive;
+// Without std, `f64` has no inherent `abs()`, `sqrt()` and so on
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
 use rand::{Rng, RngCore};
+use rand_distr::{Distribution, Normal};
 
-#[derive(Debug)]
-pub struct Network {
-	layers: Vec<Layer>,
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+	feature = "serde",
+	serde(try_from = "RawNetwork<T>", bound(deserialize = "T: Float + serde::Deserialize<'de>"))
+)]
+pub struct Network<T = f32> {
+	layers: Vec<Layer<T>>,
+	input_scaler: Option<InputScaler<T>>,
 }
 
-impl Network {
-	pub fn new(layers: Vec<Layer>) -> Self {
-		Self { layers }
+impl<T: Float> Network<T> {
+	/// Panics if the layers don't fit together; see `try_new()`.
+	pub fn new(layers: Vec<Layer<T>>) -> Self {
+		Self::try_new(layers).unwrap_or_else(|err| panic!("{}", err))
+	}
+
+	/// Builds a network out of `layers`, making sure there's at least one
+	/// and that each of them takes as many inputs as the previous one
+	/// (or the one it skips from) outputs.
+	pub fn try_new(layers: Vec<Layer<T>>) -> Result<Self, TopologyError> {
+		if layers.is_empty() {
+			return Err(TopologyError::NoLayers);
+		}
+
+		// Output sizes so far, starting with the network's input size
+		let mut sizes = Vec::with_capacity(layers.len() + 1);
+
+		for (i, layer) in layers.iter().enumerate() {
+			if layer.neurons.is_empty() {
+				return Err(TopologyError::EmptyLayer { layer: i });
+			}
+
+			let fan_in = layer.neurons[0].weights.len();
+
+			for neuron in &layer.neurons {
+				if neuron.weights.len() != fan_in {
+					return Err(TopologyError::InputSizeMismatch {
+						layer: i,
+						expected: fan_in,
+						actual: neuron.weights.len(),
+					});
+				}
+			}
+
+			let input_size = match layer.checked_input_size() {
+				Some(size) => size,
+				None => return Err(TopologyError::MissingWeights { layer: i }),
+			};
+
+			if i == 0 {
+				sizes.push(input_size);
+			}
+
+			if input_size != sizes[i] {
+				return Err(TopologyError::InputSizeMismatch {
+					layer: i,
+					expected: input_size,
+					actual: sizes[i],
+				});
+			}
+
+			if let Some(skip) = layer.skip {
+				match sizes.get(skip.from) {
+					None => return Err(TopologyError::SkipFromLaterLayer { layer: i }),
+					Some(&size) if size != skip.size => {
+						return Err(TopologyError::InputSizeMismatch {
+							layer: i,
+							expected: sizes[i] + skip.size,
+							actual: sizes[i] + size,
+						});
+					}
+					Some(_) => (),
+				}
+			}
+
+			sizes.push(layer.neurons.len());
+		}
+
+		Ok(Self {
+			layers,
+			input_scaler: None,
+		})
 	}
 
 	pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology]) -> Self {
-		assert!(layers.len() > 1);
+		Self::random_with(rng, layers, Init::default())
+	}
+
+	/// Same as `random()`, but reports invalid topologies instead of
+	/// panicking.
+	pub fn try_random(
+		rng: &mut dyn RngCore,
+		layers: &[LayerTopology],
+	) -> Result<Self, TopologyError> {
+		LayerTopology::validate(layers)?;
+
+		Ok(Self::random(rng, layers))
+	}
+
+	pub fn random_with(rng: &mut dyn RngCore, layers: &[LayerTopology], init: Init) -> Self {
+		Self::random_layers(rng, layers, |input_size, output_size| {
+			init.range(input_size, output_size)
+		})
+	}
+
+	/// Builds a network with every bias and weight set to zero.
+	pub fn zeros(layers: &[LayerTopology]) -> Self {
+		Self::constant(layers, T::zero())
+	}
+
+	/// Builds a network with every bias and weight set to `value`.
+	pub fn constant(layers: &[LayerTopology], value: T) -> Self {
+		let count = LayerTopology::param_count(layers);
+
+		Self::from_weights(layers, core::iter::repeat(value).take(count))
+	}
+
+	/// Builds a network where each neuron passes its own input through, i.e.
+	/// square layers get an identity weight matrix (and rectangular ones as
+	/// close to it as possible); biases are zero.
+	pub fn identity_like(layers: &[LayerTopology]) -> Self {
+		let mut network = Self::zeros(layers);
+
+		for layer in &mut network.layers {
+			let input_size = layer.input_size();
+
+			for (n, neuron) in layer.neurons.iter_mut().enumerate().take(input_size) {
+				neuron.weights[n] = T::one();
+			}
+
+			layer.sync_mirrored();
+		}
+
+		network
+	}
+
+	/// Draws every bias and weight uniformly from `range`.
+	pub fn random_in(
+		rng: &mut dyn RngCore,
+		layers: &[LayerTopology],
+		range: RangeInclusive<T>,
+	) -> Self {
+		assert!(!range.is_empty(), "got an empty range");
+		assert!(
+			range.start().is_finite() && range.end().is_finite(),
+			"got an infinite range",
+		);
+
+		Self::random_layers(rng, layers, |_, _| range.clone())
+	}
+
+	/// Like `random`, but every weight is disabled (set to zero) with
+	/// probability `sparsity` and the others are drawn from a normal
+	/// distribution of variance `2 / (fan_in + fan_out)`; biases are always
+	/// kept.
+	pub fn from_random_sparse(
+		rng: &mut dyn RngCore,
+		layers: &[LayerTopology],
+		sparsity: f32,
+	) -> Self {
+		assert!((0.0..=1.0).contains(&sparsity));
+
+		let mut network = Self::random(rng, layers);
+
+		for layer in &mut network.layers {
+			let fan_in = layer.neurons[0].weights.len();
+			let fan_out = layer.output_size();
+			let std = (2.0 / (fan_in + fan_out) as f64).sqrt();
+			let normal = Normal::new(0.0, std).unwrap();
+
+			for neuron in &mut layer.neurons {
+				for weight in &mut neuron.weights {
+					*weight = if rng.gen_bool(sparsity as f64) {
+						T::zero()
+					} else {
+						T::from(normal.sample(&mut *rng)).unwrap()
+					};
+				}
+			}
+
+			layer.sync_mirrored();
+		}
+
+		network
+	}
+
+	fn random_layers(
+		rng: &mut dyn RngCore,
+		layers: &[LayerTopology],
+		mut range: impl FnMut(usize, usize) -> RangeInclusive<T>,
+	) -> Self {
+		LayerTopology::validate(layers).unwrap_or_else(|err| panic!("{}", err));
 		let mut built_layers = Vec::new();
 
 		for i in 0..(layers.len() - 1) {
 			let input_size = layers[i].neurons;
 			let output_size = layers[i + 1].neurons;
+			let skip = Skip::of(layers, i + 1);
+			let fan_in = input_size + skip.map_or(0, |skip| skip.size);
 
 			built_layers.push(Layer::random(
 				rng,
 				input_size,
-				output_size,
+				skip,
+				&layers[i + 1],
+				range(fan_in, output_size),
 			));
 		}
 
-		Self { layers: built_layers }
+		Self {
+			layers: built_layers,
+			input_scaler: None,
+		}
 	}
 
-	pub fn propagate(&self, mut inputs: Vec<f32>) -> Vec<f32> {
-		for layer in &self.layers {
-			inputs = layer.propagate(inputs);
+	pub fn layers(&self) -> &[Layer<T>] {
+		&self.layers
+	}
+
+	pub fn into_layers(self) -> Vec<Layer<T>> {
+		self.layers
+	}
+
+	pub fn propagate(&mut self, mut inputs: Vec<T>) -> Vec<T> {
+		self.scale_inputs(&mut inputs);
+
+		if !self.has_skip_connections() {
+			for layer in &mut self.layers {
+				inputs = layer.propagate(inputs);
+			}
+
+			return inputs;
 		}
 
-		inputs
+		// Outputs of every layer so far, starting with the network's inputs
+		let mut outputs = vec![inputs];
+
+		for layer in &mut self.layers {
+			let inputs = layer.gather_inputs(&outputs);
+			outputs.push(layer.propagate(inputs));
+		}
+
+		outputs.pop().unwrap()
+	}
+
+	pub fn has_skip_connections(&self) -> bool {
+		self.layers.iter().any(|layer| layer.skip.is_some())
+	}
+
+	/// Same as `propagate()`, but works within `buffer` instead of allocating
+	/// new vectors; once this returns, `buffer` contains the outputs.
+	///
+	/// The buffer is resized to `required_buffer_size()` on every call, so
+	/// reusing it across calls avoids allocations altogether.
+	pub fn propagate_into(&mut self, inputs: &[T], buffer: &mut Vec<T>) {
+		// Skip connections need earlier outputs to stay around, which the
+		// buffer doesn't have room for
+		if self.has_skip_connections() {
+			let outputs = self.propagate(inputs.to_vec());
+			buffer.clear();
+			buffer.extend(outputs);
+			return;
+		}
+
+		let width = self.required_buffer_size() / 2;
+
+		buffer.clear();
+		buffer.resize(2 * width, T::zero());
+
+		// Each layer reads from `front` and writes to `back`
+		let (front, back) = buffer.split_at_mut(width);
+		front[..inputs.len()].copy_from_slice(inputs);
+		self.scale_inputs(&mut front[..inputs.len()]);
+		let mut len = inputs.len();
+
+		for layer in &mut self.layers {
+			let fan_in = len + layer.state.len();
+			front[len..fan_in].copy_from_slice(&layer.state);
+
+			len = layer.output_size();
+			layer.forward(&front[..fan_in], &mut back[..len]);
+			front[..len].copy_from_slice(&back[..len]);
+		}
+
+		buffer.truncate(len);
+	}
+
+	/// Size of the buffer `propagate_into()` works with.
+	pub fn required_buffer_size(&self) -> usize {
+		let width = self
+			.layers
+			.iter()
+			.map(|layer| {
+				let fan_in = layer.input_size()
+					+ layer.skip.map_or(0, |skip| skip.size)
+					+ layer.state.len();
+
+				fan_in.max(layer.output_size())
+			})
+			.max()
+			.unwrap_or(0);
+
+		2 * width
+	}
+
+	/// Forgets everything recurrent layers remember from previous calls.
+	pub fn reset_state(&mut self) {
+		for layer in &mut self.layers {
+			layer.reset_state();
+		}
+	}
+
+	pub fn weights(&self) -> Vec<T> {
+		self.layers.iter().flat_map(|layer| layer.params()).copied().collect()
+	}
+
+	/// Same as `weights()`, but split into one group per layer.
+	pub fn weights_by_layer(&self) -> Vec<Vec<T>> {
+		self.layers
+			.iter()
+			.map(|layer| layer.params().copied().collect())
+			.collect()
+	}
+
+	/// Same as `Layer::weight_matrix()`, for every layer.
+	pub fn weight_matrices(&self) -> Vec<Vec<Vec<T>>> {
+		self.layers.iter().map(Layer::weight_matrix).collect()
+	}
+
+	/// Returns how many consecutive entries of `weights()` belong to each
+	/// neuron, i.e. its bias (if any) and fan-in; neurons of mirrored layers
+	/// that have no parameters of their own are left out.
+	pub fn segment_lengths(&self) -> Vec<usize> {
+		self.layers
+			.iter()
+			.flat_map(|layer| {
+				layer.neurons.iter().enumerate().filter_map(|(n, neuron)| {
+					let (bias, weights) = layer.own_params(n);
+					let len = (bias && neuron.has_bias()) as usize + weights;
+
+					(len > 0).then_some(len)
+				})
+			})
+			.collect()
+	}
+
+	/// Copies biases and weights from `source` wherever both networks have a
+	/// counterpart (same layer, neuron and input index), leaving the rest of
+	/// `self` untouched; the networks don't need to be of the same shape.
+	pub fn copy_weights_from(&mut self, source: &Network<T>) {
+		for (layer, source) in self.layers.iter_mut().zip(&source.layers) {
+			for (neuron, source) in layer.neurons.iter_mut().zip(&source.neurons) {
+				if let (Some(bias), Some(source)) = (&mut neuron.bias, source.bias) {
+					*bias = source;
+				}
+
+				for (weight, source) in neuron.weights.iter_mut().zip(&source.weights) {
+					*weight = *source;
+				}
+			}
+
+			layer.sync_mirrored();
+		}
+	}
+
+	/// Calls `f` on every bias and weight, in the same order as `weights()`,
+	/// letting it modify them in place.
+	pub fn mutate_weights(
+		&mut self,
+		rng: &mut dyn RngCore,
+		mut f: impl FnMut(&mut T, &mut dyn RngCore),
+	) {
+		for layer in &mut self.layers {
+			for param in layer.params_mut() {
+				f(param, rng);
+			}
+
+			layer.sync_mirrored();
+		}
+	}
+
+	/// Same as `mutate_weights()`, but leaves the biases alone.
+	pub fn mutate_weights_excluding_biases(
+		&mut self,
+		rng: &mut dyn RngCore,
+		mut f: impl FnMut(&mut T, &mut dyn RngCore),
+	) {
+		for layer in &mut self.layers {
+			for weight in layer.weights_mut() {
+				f(weight, rng);
+			}
+
+			layer.sync_mirrored();
+		}
+	}
+
+	/// Nudges each parameter, with probability `chance`, by up to `coeff` in
+	/// either direction - the same way `GaussianMutation` treats genes, but
+	/// without going through a chromosome.
+	pub fn perturb_weights(&mut self, rng: &mut dyn RngCore, chance: f32, coeff: T) {
+		assert!((0.0..=1.0).contains(&chance));
+
+		self.mutate_weights(rng, |weight, rng| {
+			let sign = if rng.gen_bool(0.5) { -T::one() } else { T::one() };
+
+			if rng.gen_bool(chance as f64) {
+				*weight += sign * coeff * rng.gen_range(T::zero()..T::one());
+			}
+		});
+	}
+
+	/// Zeroes every weight whose magnitude is below `threshold`, returning
+	/// how many of them got zeroed; biases are left alone.
+	pub fn prune(&mut self, threshold: T) -> usize {
+		self.prune_params(threshold, false)
+	}
+
+	/// Same as `prune()`, but zeroes small biases too.
+	pub fn prune_including_biases(&mut self, threshold: T) -> usize {
+		self.prune_params(threshold, true)
+	}
+
+	fn prune_params(&mut self, threshold: T, include_biases: bool) -> usize {
+		let mut pruned = 0;
+
+		let mut prune = |param: &mut T| {
+			if !param.is_zero() && param.abs() < threshold {
+				*param = T::zero();
+				pruned += 1;
+			}
+		};
+
+		for layer in &mut self.layers {
+			for neuron in &mut layer.neurons {
+				if let (true, Some(bias)) = (include_biases, &mut neuron.bias) {
+					prune(bias);
+				}
+
+				neuron.weights.iter_mut().for_each(&mut prune);
+			}
+		}
+
+		pruned
+	}
+
+	/// Clamps every bias and weight into `range`.
+	pub fn clamp_weights(&mut self, range: RangeInclusive<T>) {
+		let (min, max) = range.into_inner();
+		assert!(min <= max, "got an empty range");
+
+		let clamp = |param: &mut T| *param = param.max(min).min(max);
+
+		for layer in &mut self.layers {
+			for neuron in &mut layer.neurons {
+				if let Some(bias) = &mut neuron.bias {
+					clamp(bias);
+				}
+
+				neuron.weights.iter_mut().for_each(clamp);
+			}
+		}
+	}
+
+	/// Rescales each neuron's incoming weights (not its bias) so that their
+	/// L2 norm doesn't exceed `max_norm`; neurons within the limit are left
+	/// as they are.
+	pub fn apply_max_norm(&mut self, max_norm: T) {
+		assert!(max_norm >= T::zero());
+
+		for layer in &mut self.layers {
+			for neuron in &mut layer.neurons {
+				let norm = neuron
+					.weights
+					.iter()
+					.fold(T::zero(), |sum, weight| sum + *weight * *weight)
+					.sqrt();
+
+				if norm > max_norm {
+					let scale = max_norm / norm;
+
+					for weight in &mut neuron.weights {
+						*weight *= scale;
+					}
+				}
+			}
+		}
+	}
+
+	/// L2 norm of all parameters, biases included.
+	pub fn l2_norm(&self) -> T {
+		self.params().fold(T::zero(), |sum, param| sum + *param * *param).sqrt()
+	}
+
+	/// L1 norm (sum of magnitudes) of all parameters, biases included.
+	pub fn l1_norm(&self) -> T {
+		self.params().fold(T::zero(), |sum, param| sum + param.abs())
+	}
+
+	fn params(&self) -> impl Iterator<Item = &T> {
+		self.layers
+			.iter()
+			.flat_map(|layer| &layer.neurons)
+			.flat_map(|neuron| neuron.bias.iter().chain(&neuron.weights))
+	}
+
+	/// Shrinks every weight towards zero by multiplying it with
+	/// `1 - factor`; biases are left alone.
+	pub fn decay_weights(&mut self, factor: T) {
+		self.decay_params(factor, false);
+	}
+
+	/// Same as `decay_weights()`, but shrinks biases too.
+	pub fn decay_weights_including_biases(&mut self, factor: T) {
+		self.decay_params(factor, true);
+	}
+
+	fn decay_params(&mut self, factor: T, include_biases: bool) {
+		assert!(factor >= T::zero() && factor <= T::one(), "got a factor outside [0, 1]");
+
+		let scale = T::one() - factor;
+
+		for layer in &mut self.layers {
+			for neuron in &mut layer.neurons {
+				if let (true, Some(bias)) = (include_biases, &mut neuron.bias) {
+					*bias *= scale;
+				}
+
+				for weight in &mut neuron.weights {
+					*weight *= scale;
+				}
+			}
+		}
+	}
+
+	/// Fraction of weights (not counting biases) that are zero.
+	pub fn sparsity(&self) -> f32 {
+		let (zeros, total) = self
+			.layers
+			.iter()
+			.flat_map(|layer| &layer.neurons)
+			.flat_map(|neuron| &neuron.weights)
+			.fold((0, 0), |(zeros, total), weight| {
+				(zeros + weight.is_zero() as usize, total + 1)
+			});
+
+		if total == 0 {
+			0.0
+		} else {
+			zeros as f32 / total as f32
+		}
 	}
 
-	pub fn weights(&self) -> Vec<f32> {
-		let mut weights = Vec::new();
+	/// Tells, in the same order as `weights()`, which parameters are active,
+	/// i.e. non-zero; biases always count as active.
+	pub fn connection_mask(&self) -> Vec<bool> {
+		let mut mask = Vec::new();
 
 		for layer in &self.layers {
-			for neuron in &layer.neurons {
-				weights.push(neuron.bias);
+			for (n, neuron) in layer.neurons.iter().enumerate() {
+				let (bias, weights) = layer.own_params(n);
+
+				if bias && neuron.has_bias() {
+					mask.push(true);
+				}
 
-				for weight in &neuron.weights {
-					weights.push(*weight);
+				for weight in &neuron.weights[..weights] {
+					mask.push(!weight.is_zero());
 				}
 			}
 		}
 
-		weights
+		mask
+	}
+
+	/// Same as `from_weights()`, but reports invalid topologies instead of
+	/// panicking; a wrong number of weights still causes a panic.
+	pub fn try_from_weights(
+		layers: &[LayerTopology],
+		weights: impl IntoIterator<Item = T>,
+	) -> Result<Self, TopologyError> {
+		LayerTopology::validate(layers)?;
+
+		Ok(Self::from_weights(layers, weights))
 	}
 
 	pub fn from_weights(
 		layers: &[LayerTopology],
-		weight: impl IntoIterator<Item = f32>,
+		weight: impl IntoIterator<Item = T>,
 	) -> Self {
-		assert!(layers.len() > 1);
+		LayerTopology::validate(layers).unwrap_or_else(|err| panic!("{}", err));
 
 		let mut weights = weight.into_iter();
 
-		let layers = layers
-			.windows(2)
-			.map(|layers| {
+		let layers = (1..layers.len())
+			.map(|i| {
 				Layer::from_weights(
-					layers[0].neurons,
-					layers[1].neurons,
+					layers[i - 1].neurons,
+					Skip::of(layers, i),
+					&layers[i],
 					&mut weights,
 				)
 			})
@@ -75,119 +647,2354 @@ impl Network {
 			panic!("got too many weights");
 		}
 
-		Self {layers}
+		Self {
+			layers,
+			input_scaler: None,
+		}
 	}
-}
 
-#[derive(Debug)]
-pub struct Layer {
-	neurons: Vec<Neuron>
-}
+	/// Same as `from_weights()`, but checks the number of weights up front
+	/// and copies them over slice by slice, which is faster.
+	pub fn from_weight_slice(layers: &[LayerTopology], weights: &[T]) -> Result<Self, WeightsError> {
+		LayerTopology::validate(layers)?;
 
-impl Layer {
-	fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
-		let mut outputs = Vec::new();
-		for neuron in &self.neurons {
-			let output = neuron.propagate(&inputs);
-			outputs.push(output);
-		}
-		outputs
-	}
+		let expected = LayerTopology::param_count(layers);
 
-	fn random(rng: &mut dyn RngCore, input_size: usize, output_size: usize) -> Self {
-		let mut neurons = Vec::new();
-		for _ in 0..output_size {
-			neurons.push(Neuron::random(rng, input_size));
+		if weights.len() != expected {
+			return Err(WeightsError::CountMismatch {
+				expected,
+				actual: weights.len(),
+			});
 		}
-		Self {neurons}
-	}
 
-	fn from_weights(
-		input_size: usize,
-		output_size: usize,
-		weights: &mut impl Iterator<Item = f32>,
-	) -> Self {
-		let neurons = (0..output_size)
-			.map(|_| Neuron::from_weights(input_size, weights))
+		let mut weights = weights;
+
+		let layers = (1..layers.len())
+			.map(|i| {
+				Layer::from_weight_slice(
+					layers[i - 1].neurons,
+					Skip::of(layers, i),
+					&layers[i],
+					&mut weights,
+				)
+			})
 			.collect();
 
-		Self {neurons}
+		Ok(Self {
+			layers,
+			input_scaler: None,
+		})
 	}
 
-}
+	/// Returns whether both networks share the same topology and all of
+	/// their biases and weights are within `epsilon` of each other.
+	pub fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+		self.params_match(other, |a, b| (a - b).abs() <= epsilon)
+	}
 
-#[derive(Debug)]
-struct Neuron {
-	bias: f32,
-	weights: Vec<f32>
-}
+	/// Builds a network of the same shape, with `f` applied to every bias and
+	/// weight, in the same order as `weights()`.
+	pub fn map(&self, mut f: impl FnMut(T) -> T) -> Self {
+		self.map_layers(|layer| {
+			layer
+				.neurons
+				.iter()
+				.enumerate()
+				.map(|(n, neuron)| {
+					let (bias, weights) = layer.own_params(n);
 
-impl Neuron {
-	fn propagate(&self, inputs: &[f32]) -> f32 {
-		assert_eq!(inputs.len(), self.weights.len());
-		let mut output = 0.0;
+					Neuron {
+						bias: neuron.bias.map(|b| if bias { f(b) } else { b }),
+						weights: neuron
+							.weights
+							.iter()
+							.enumerate()
+							.map(|(i, weight)| if i < weights { f(*weight) } else { *weight })
+							.collect(),
+					}
+				})
+				.collect()
+		})
+	}
 
-		for i in 0..inputs.len() {
-			output += inputs[i] * self.weights[i];
+	/// Builds a network of the same shape, with `f` applied to every pair of
+	/// corresponding biases and weights of `self` and `other`.
+	pub fn zip_map(
+		&self,
+		other: &Self,
+		mut f: impl FnMut(T, T) -> T,
+	) -> Result<Self, TopologyMismatch> {
+		if !self.params_match(other, |_, _| true) {
+			return Err(TopologyMismatch);
 		}
-		
-		(self.bias + output).max(0.0)
-	}
 
-	fn random(rng: &mut dyn RngCore, input_size: usize) -> Self {
-		// let mut rng = rand::thread_rng();
-		let bias = rng.gen_range(-1.0..=1.0);
+		let mut other_layers = other.layers.iter();
 
-		let weights = (0..input_size)
-			.map(|_| rng.gen_range(-1.0..=1.0))
-			.collect();
+		Ok(self.map_layers(|layer| {
+			let other = other_layers.next().unwrap();
 
-		Self {bias, weights}
+			layer
+				.neurons
+				.iter()
+				.zip(&other.neurons)
+				.enumerate()
+				.map(|(n, (a, b))| {
+					let (bias, weights) = layer.own_params(n);
+
+					Neuron {
+						bias: a.bias.zip(b.bias).map(|(a, b)| if bias { f(a, b) } else { a }),
+						weights: a
+							.weights
+							.iter()
+							.zip(&b.weights)
+							.enumerate()
+							.map(|(i, (a, b))| if i < weights { f(*a, *b) } else { *a })
+							.collect(),
+					}
+				})
+				.collect()
+		}))
 	}
 
-	fn from_weights(
-		input_size: usize,
-		weights: &mut dyn Iterator<Item = f32>,
-	) -> Self {
-		let bias = weights.next().expect("got not enough weights");
+	fn map_layers(&self, mut f: impl FnMut(&Layer<T>) -> Vec<Neuron<T>>) -> Self {
+		let layers = self
+			.layers
+			.iter()
+			.map(|layer| {
+				let mut mapped = Layer {
+					skip: layer.skip,
+					mirrored: layer.mirrored,
+					..Layer::new(f(layer), layer.kind, layer.activation)
+				};
 
-		let weights = (0..input_size)
-			.map(|_| weights.next().expect("got not enough weights"))
+				mapped.sync_mirrored();
+				mapped
+			})
 			.collect();
 
-		Self { bias, weights }
+		Self {
+			input_scaler: self.input_scaler.clone(),
+			..Self::new(layers)
+		}
+	}
+
+	/// Compares networks parameter by parameter; networks of different
+	/// shapes never match.
+	fn params_match(&self, other: &Self, mut f: impl FnMut(T, T) -> bool) -> bool {
+		self.layers.len() == other.layers.len()
+			&& self
+				.layers
+				.iter()
+				.zip(&other.layers)
+				.all(|(a, b)| a.params_match(b, &mut f))
 	}
 }
 
 #[derive(Debug)]
-pub struct LayerTopology {
-	pub neurons: usize,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "T: serde::Deserialize<'de>")))]
+pub struct Layer<T = f32> {
+	neurons: Vec<Neuron<T>>,
+	kind: LayerKind,
+	activation: Activation,
+	skip: Option<Skip>,
+	// Whether the weights are tied so that mirroring the inputs mirrors the
+	// outputs, see `LayerTopology::with_mirrored_weights()`
+	mirrored: bool,
+	// Outputs of the previous call, fed back into recurrent layers; not
+	// serialized, see `RawNetwork`
+	#[cfg_attr(feature = "serde", serde(skip))]
+	state: Vec<T>,
 }
 
-#[cfg(test)]
-mod tests {
-	use super::*;
-	use approx::assert_relative_eq;
-	use rand::SeedableRng;
-	use rand_chacha::ChaCha8Rng;
+// Network as it comes out of the deserializer, before its layers and input
+// scaler are checked to fit together
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(bound(deserialize = "T: serde::Deserialize<'de>"))]
+struct RawNetwork<T> {
+	layers: Vec<Layer<T>>,
+	input_scaler: Option<InputScaler<T>>,
+}
 
-	#[test]
-	fn random() {
-		let mut rng = ChaCha8Rng::from_seed(Default::default());
-		let neuron = Neuron::random(&mut rng, 4);
-		assert_relative_eq!(neuron.bias, -0.6255188);
-		assert_relative_eq!(neuron.weights.as_slice(), [0.67383957, 0.8181262, 0.26284897, 0.5238807].as_ref());
-	}
+#[cfg(feature = "serde")]
+impl<T: Float> TryFrom<RawNetwork<T>> for Network<T> {
+	type Error = TopologyError;
 
-	#[test]
-	fn propagate() {
-		let neuron = Neuron {
-			bias: 0.5,
-			weights: vec![-0.3, 0.8],
-		};
+	fn try_from(raw: RawNetwork<T>) -> Result<Self, Self::Error> {
+		let mut network = Self::try_new(raw.layers)?;
 
-		assert_relative_eq!(neuron.propagate(&[-10.0, -10.0]), 0.0);
-		assert_relative_eq!(neuron.propagate(&[0.5, 1.0]), (0.5 * -0.3 + 1.0 * 0.8 + 0.5));
-	} 
-	// TODO: test weight
-}
\ No newline at end of file
+		if let Some(scaler) = &raw.input_scaler {
+			let input_size = network.layers[0].input_size();
+
+			for len in [scaler.means.len(), scaler.stds.len()] {
+				if len != input_size {
+					return Err(TopologyError::InputSizeMismatch {
+						layer: 0,
+						expected: input_size,
+						actual: len,
+					});
+				}
+			}
+		}
+
+		// Layers come back without their state, so recurrent ones get a
+		// fresh one
+		for layer in &mut network.layers {
+			layer.reset_state();
+		}
+
+		network.input_scaler = raw.input_scaler;
+		Ok(network)
+	}
+}
+
+// The state only reflects the last inputs, so it doesn't tell layers apart
+impl<T: PartialEq> PartialEq for Layer<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.neurons == other.neurons
+			&& self.kind == other.kind
+			&& self.activation == other.activation
+			&& self.skip == other.skip
+			&& self.mirrored == other.mirrored
+	}
+}
+
+// Earlier layer whose outputs a layer receives on top of the previous one's
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Skip {
+	// Index into the network's outputs, where 0 stands for its inputs
+	from: usize,
+	size: usize,
+}
+
+impl Skip {
+	fn of(layers: &[LayerTopology], i: usize) -> Option<Self> {
+		layers[i].skip_from.map(|from| Self {
+			from,
+			size: layers[from].neurons,
+		})
+	}
+}
+
+impl<T: Float> Layer<T> {
+	fn new(neurons: Vec<Neuron<T>>, kind: LayerKind, activation: Activation) -> Self {
+		let mut layer = Self {
+			neurons,
+			kind,
+			activation,
+			skip: None,
+			mirrored: false,
+			state: Vec::new(),
+		};
+
+		layer.reset_state();
+		layer
+	}
+
+	pub fn neurons(&self) -> &[Neuron<T>] {
+		&self.neurons
+	}
+
+	pub fn kind(&self) -> LayerKind {
+		self.kind
+	}
+
+	pub fn activation(&self) -> Activation {
+		self.activation
+	}
+
+	pub fn is_mirrored(&self) -> bool {
+		self.mirrored
+	}
+
+	/// Index of the earlier layer (0 being the network's inputs) whose
+	/// outputs this layer receives as well, if any.
+	pub fn skip_from(&self) -> Option<usize> {
+		self.skip.map(|skip| skip.from)
+	}
+
+	/// Number of inputs the layer takes from the previous one; zero for
+	/// layers whose neurons lack weights for their skip or recurrent inputs,
+	/// which `Network::try_new()` rejects.
+	pub fn input_size(&self) -> usize {
+		self.checked_input_size().unwrap_or(0)
+	}
+
+	// Same as `input_size()`, but `None` when the neurons are too short to
+	// fit the skip and recurrent inputs
+	fn checked_input_size(&self) -> Option<usize> {
+		let fan_in = self.neurons.first().map_or(0, |neuron| neuron.weights.len());
+		let skipped = self.skip.map_or(0, |skip| skip.size);
+
+		match self.kind {
+			LayerKind::Dense => fan_in.checked_sub(skipped),
+			LayerKind::Recurrent => fan_in
+				.checked_sub(skipped)
+				.and_then(|size| size.checked_sub(self.neurons.len())),
+			LayerKind::Normalize => self.neurons.len().checked_sub(skipped),
+		}
+	}
+
+	/// Number of outputs the layer gives, one per neuron.
+	pub fn output_size(&self) -> usize {
+		self.neurons.len()
+	}
+
+	/// Whether the layer can gain or lose neurons, along with inputs, on its
+	/// own; normalize layers output as many values as they take in and
+	/// mirrored layers pair up their neurons and inputs.
+	pub fn is_resizable(&self) -> bool {
+		self.kind != LayerKind::Normalize && !self.mirrored
+	}
+
+	/// Weights of every neuron (biases left out), one row per neuron; rows
+	/// of layers with skip or recurrent inputs are longer than
+	/// `input_size()`, since they hold those inputs' weights too.
+	pub fn weight_matrix(&self) -> Vec<Vec<T>> {
+		self.neurons.iter().map(|neuron| neuron.weights.clone()).collect()
+	}
+
+	/// Bias of every neuron, zero for layers built without them.
+	pub fn bias_vector(&self) -> Vec<T> {
+		self.neurons.iter().map(Neuron::bias).collect()
+	}
+
+	// Concatenates the previous layer's outputs with the skipped-over ones
+	fn gather_inputs(&self, outputs: &[Vec<T>]) -> Vec<T> {
+		let mut inputs = outputs.last().unwrap().clone();
+
+		if let Some(skip) = self.skip {
+			inputs.extend_from_slice(&outputs[skip.from]);
+		}
+
+		inputs
+	}
+
+	fn propagate(&mut self, mut inputs: Vec<T>) -> Vec<T> {
+		inputs.extend_from_slice(&self.state);
+
+		let mut outputs = vec![T::zero(); self.output_size()];
+		self.forward(&inputs, &mut outputs);
+		outputs
+	}
+
+	// Writes the layer's outputs into `outputs`, given inputs that already
+	// include its state, and remembers them when it's a recurrent one
+	fn forward(&mut self, inputs: &[T], outputs: &mut [T]) {
+		match self.kind {
+			LayerKind::Dense | LayerKind::Recurrent => {
+				for (output, neuron) in outputs.iter_mut().zip(&self.neurons) {
+					*output = neuron.propagate(inputs, self.activation);
+				}
+			}
+			LayerKind::Normalize => normalize(inputs, outputs),
+		}
+
+		if self.kind == LayerKind::Recurrent {
+			self.state.copy_from_slice(outputs);
+		}
+	}
+
+	// Outputs before the activation is applied, given inputs that already
+	// include the layer's state
+	fn sums(&self, inputs: &[T]) -> Vec<T> {
+		if self.kind == LayerKind::Normalize {
+			let mut outputs = vec![T::zero(); inputs.len()];
+			normalize(inputs, &mut outputs);
+			return outputs;
+		}
+
+		self.neurons
+			.iter()
+			.map(|neuron| neuron.weighted_sum(inputs))
+			.collect()
+	}
+
+	fn activate(&self, sum: T) -> T {
+		match self.kind {
+			LayerKind::Dense | LayerKind::Recurrent => self.activation.apply(sum),
+			LayerKind::Normalize => sum,
+		}
+	}
+
+	fn activation_derivative(&self, sum: T) -> T {
+		match self.kind {
+			LayerKind::Dense | LayerKind::Recurrent => self.activation.derivative(sum),
+			LayerKind::Normalize => T::one(),
+		}
+	}
+
+	// Zeroes the state of recurrent layers, sizing it to the layer's outputs
+	fn reset_state(&mut self) {
+		self.state.clear();
+
+		if self.kind == LayerKind::Recurrent {
+			self.state.resize(self.output_size(), T::zero());
+		}
+	}
+
+	// Whether the `neuron`-th neuron's bias is its own, and how many of its
+	// leading weights are; the rest mirror another neuron's
+	fn own_params(&self, neuron: usize) -> (bool, usize) {
+		let fan_in = self.neurons[neuron].weights.len();
+
+		own_params(self.mirrored, self.neurons.len(), neuron, fan_in)
+	}
+
+	// Biases and weights that aren't mirrored from others, in the same
+	// order as `Network::weights()`
+	fn params(&self) -> impl Iterator<Item = &T> {
+		self.neurons.iter().enumerate().flat_map(move |(n, neuron)| {
+			let (bias, weights) = self.own_params(n);
+
+			neuron.bias.iter().filter(move |_| bias).chain(&neuron.weights[..weights])
+		})
+	}
+
+	// Same as `params()`; once done, call `sync_mirrored()`
+	fn params_mut(&mut self) -> impl Iterator<Item = &mut T> {
+		let (mirrored, size) = (self.mirrored, self.neurons.len());
+
+		self.neurons.iter_mut().enumerate().flat_map(move |(n, neuron)| {
+			let (bias, weights) = own_params(mirrored, size, n, neuron.weights.len());
+
+			neuron
+				.bias
+				.iter_mut()
+				.filter(move |_| bias)
+				.chain(&mut neuron.weights[..weights])
+		})
+	}
+
+	// Same as `params_mut()`, without the biases
+	fn weights_mut(&mut self) -> impl Iterator<Item = &mut T> {
+		let (mirrored, size) = (self.mirrored, self.neurons.len());
+
+		self.neurons.iter_mut().enumerate().flat_map(move |(n, neuron)| {
+			let (_, weights) = own_params(mirrored, size, n, neuron.weights.len());
+
+			&mut neuron.weights[..weights]
+		})
+	}
+
+	// Copies the parameters of the first half of a mirrored layer onto the
+	// second one: neuron `size - 1 - n` gets neuron `n`'s bias and its
+	// weights in reverse order
+	fn sync_mirrored(&mut self) {
+		if !self.mirrored {
+			return;
+		}
+
+		let size = self.neurons.len();
+
+		for n in 0..size / 2 {
+			let (sources, mirrors) = self.neurons.split_at_mut(size - 1 - n);
+			let (source, mirror) = (&sources[n], &mut mirrors[0]);
+
+			mirror.bias = source.bias;
+
+			for (weight, source) in mirror.weights.iter_mut().zip(source.weights.iter().rev()) {
+				*weight = *source;
+			}
+		}
+
+		if size % 2 == 1 {
+			let center = &mut self.neurons[size / 2].weights;
+			let len = center.len();
+
+			for i in 0..len / 2 {
+				center[len - 1 - i] = center[i];
+			}
+		}
+	}
+
+	fn random(
+		rng: &mut dyn RngCore,
+		input_size: usize,
+		skip: Option<Skip>,
+		topology: &LayerTopology,
+		range: RangeInclusive<T>,
+	) -> Self {
+		let input_size = Self::fan_in(input_size, skip, topology);
+
+		let mut neurons = Vec::new();
+		for _ in 0..topology.neurons {
+			neurons.push(Neuron::random(rng, input_size, topology.has_bias(), &range));
+		}
+
+		let mut layer = Self {
+			skip,
+			mirrored: topology.mirrored,
+			..Self::new(neurons, topology.kind, topology.activation)
+		};
+
+		layer.sync_mirrored();
+		layer
+	}
+
+	fn from_weights(
+		input_size: usize,
+		skip: Option<Skip>,
+		topology: &LayerTopology,
+		weights: &mut impl Iterator<Item = T>,
+	) -> Self {
+		let input_size = Self::fan_in(input_size, skip, topology);
+
+		let neurons = (0..topology.neurons)
+			.map(|n| {
+				let (bias, own) = own_params(topology.mirrored, topology.neurons, n, input_size);
+
+				Neuron::from_weights(own, bias && topology.has_bias(), weights)
+					.padded(input_size, topology.has_bias())
+			})
+			.collect();
+
+		let mut layer = Self {
+			skip,
+			mirrored: topology.mirrored,
+			..Self::new(neurons, topology.kind, topology.activation)
+		};
+
+		layer.sync_mirrored();
+		layer
+	}
+
+	fn from_weight_slice(
+		input_size: usize,
+		skip: Option<Skip>,
+		topology: &LayerTopology,
+		weights: &mut &[T],
+	) -> Self {
+		let input_size = Self::fan_in(input_size, skip, topology);
+
+		let neurons = (0..topology.neurons)
+			.map(|n| {
+				let (bias, own) = own_params(topology.mirrored, topology.neurons, n, input_size);
+
+				Neuron::from_weight_slice(own, bias && topology.has_bias(), weights)
+					.padded(input_size, topology.has_bias())
+			})
+			.collect();
+
+		let mut layer = Self {
+			skip,
+			mirrored: topology.mirrored,
+			..Self::new(neurons, topology.kind, topology.activation)
+		};
+
+		layer.sync_mirrored();
+		layer
+	}
+
+	fn fan_in(input_size: usize, skip: Option<Skip>, topology: &LayerTopology) -> usize {
+		let input_size = input_size + skip.map_or(0, |skip| skip.size);
+
+		topology.kind.fan_in(input_size, topology.neurons)
+	}
+
+	fn params_match(&self, other: &Self, f: &mut impl FnMut(T, T) -> bool) -> bool {
+		self.kind == other.kind
+			&& self.activation == other.activation
+			&& self.skip == other.skip
+			&& self.mirrored == other.mirrored
+			&& self.neurons.len() == other.neurons.len()
+			&& self
+				.neurons
+				.iter()
+				.zip(&other.neurons)
+				.all(|(a, b)| a.params_match(b, f))
+	}
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Neuron<T = f32> {
+	// None for layers built without bias
+	bias: Option<T>,
+	weights: Vec<T>
+}
+
+impl<T: Float> Neuron<T> {
+	/// Returns the neuron's bias, or zero if its layer has none.
+	pub fn bias(&self) -> T {
+		self.bias.unwrap_or_else(T::zero)
+	}
+
+	pub fn has_bias(&self) -> bool {
+		self.bias.is_some()
+	}
+
+	pub fn weights(&self) -> &[T] {
+		&self.weights
+	}
+
+	#[inline]
+	fn propagate(&self, inputs: &[T], activation: Activation) -> T {
+		activation.apply(self.weighted_sum(inputs))
+	}
+
+	/// Returns the neuron's output before the activation is applied.
+	#[inline]
+	fn weighted_sum(&self, inputs: &[T]) -> T {
+		assert_eq!(inputs.len(), self.weights.len());
+
+		self.bias() + dot(inputs, &self.weights)
+	}
+
+	fn random(
+		rng: &mut dyn RngCore,
+		input_size: usize,
+		bias: bool,
+		range: &RangeInclusive<T>,
+	) -> Self {
+		// let mut rng = rand::thread_rng();
+		let bias = bias.then(|| rng.gen_range(range.clone()));
+
+		let weights = (0..input_size)
+			.map(|_| rng.gen_range(range.clone()))
+			.collect();
+
+		Self {bias, weights}
+	}
+
+	fn from_weights(
+		input_size: usize,
+		bias: bool,
+		weights: &mut dyn Iterator<Item = T>,
+	) -> Self {
+		let bias = bias.then(|| weights.next().expect("got not enough weights"));
+
+		let weights = (0..input_size)
+			.map(|_| weights.next().expect("got not enough weights"))
+			.collect();
+
+		Self { bias, weights }
+	}
+
+	// Takes this neuron's parameters off the front of `weights`, which must
+	// already be known to be long enough
+	fn from_weight_slice(input_size: usize, bias: bool, weights: &mut &[T]) -> Self {
+		let (bias, rest) = if bias {
+			(Some(weights[0]), &weights[1..])
+		} else {
+			(None, *weights)
+		};
+
+		let (own, rest) = rest.split_at(input_size);
+		*weights = rest;
+
+		Self {
+			bias,
+			weights: own.to_vec(),
+		}
+	}
+
+	// Fills in the parameters a mirrored layer's neuron doesn't own with
+	// zeros, until `Layer::sync_mirrored()` copies them over
+	fn padded(mut self, input_size: usize, bias: bool) -> Self {
+		self.weights.resize(input_size, T::zero());
+
+		if bias {
+			self.bias.get_or_insert_with(T::zero);
+		}
+
+		self
+	}
+
+	fn params_match(&self, other: &Self, f: &mut impl FnMut(T, T) -> bool) -> bool {
+		self.weights.len() == other.weights.len()
+			&& match (self.bias, other.bias) {
+				(Some(a), Some(b)) => f(a, b),
+				(None, None) => true,
+				_ => false,
+			}
+			&& self
+				.weights
+				.iter()
+				.zip(&other.weights)
+				.all(|(a, b)| f(*a, *b))
+	}
+}
+
+#[inline]
+fn dot<T: Float>(inputs: &[T], weights: &[T]) -> T {
+	// Four independent accumulators let LLVM vectorize the loop
+	let mut sums = [T::zero(); 4];
+	let inputs_chunks = inputs.chunks_exact(4);
+	let weights_chunks = weights.chunks_exact(4);
+	let inputs_rest = inputs_chunks.remainder();
+	let weights_rest = weights_chunks.remainder();
+
+	for (inputs, weights) in inputs_chunks.zip(weights_chunks) {
+		for i in 0..4 {
+			sums[i] = mul_add(inputs[i], weights[i], sums[i]);
+		}
+	}
+
+	let mut output = (sums[0] + sums[1]) + (sums[2] + sums[3]);
+	for (input, weight) in inputs_rest.iter().zip(weights_rest) {
+		output = mul_add(*input, *weight, output);
+	}
+
+	output
+}
+
+// Without hardware FMA (e.g. on wasm32) `mul_add` turns into a slow
+// software routine, so it's only used when the target supports it
+#[inline(always)]
+fn mul_add<T: Float>(a: T, b: T, c: T) -> T {
+	if cfg!(target_feature = "fma") {
+		a.mul_add(b, c)
+	} else {
+		a * b + c
+	}
+}
+
+// See `Layer::own_params()`; in a mirrored layer, the first half of the
+// neurons own all their parameters, while a center neuron owns its bias and
+// the first half of its weights (the rest mirror them)
+fn own_params(mirrored: bool, size: usize, neuron: usize, fan_in: usize) -> (bool, usize) {
+	if !mirrored || neuron < size / 2 {
+		(true, fan_in)
+	} else if 2 * neuron + 1 == size {
+		(true, (fan_in + 1) / 2)
+	} else {
+		(false, 0)
+	}
+}
+
+// Shifts and scales `inputs` to a mean of zero and a variance of one
+fn normalize<T: Float>(inputs: &[T], outputs: &mut [T]) {
+	let (mean, std) = mean_and_std(inputs);
+
+	for (output, input) in outputs.iter_mut().zip(inputs) {
+		*output = (*input - mean) / std;
+	}
+}
+
+// The standard deviation is padded with `NORMALIZE_EPSILON`, which keeps
+// `normalize()` from dividing by zero for constant inputs
+fn mean_and_std<T: Float>(inputs: &[T]) -> (T, T) {
+	let len = T::from(inputs.len()).unwrap();
+	let mean = inputs.iter().fold(T::zero(), |sum, input| sum + *input) / len;
+
+	let variance = inputs
+		.iter()
+		.fold(T::zero(), |sum, input| sum + (*input - mean).powi(2))
+		/ len;
+
+	(mean, (variance + T::from(NORMALIZE_EPSILON).unwrap()).sqrt())
+}
+
+const NORMALIZE_EPSILON: f64 = 1e-5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayerKind {
+	#[default]
+	Dense,
+	/// Each neuron also receives the layer's previous outputs as inputs.
+	Recurrent,
+	/// Rescales its inputs to a mean of zero and a variance of one; has no
+	/// parameters, ignores its activation and must have as many neurons as
+	/// it has inputs.
+	Normalize,
+}
+
+impl LayerKind {
+	fn fan_in(&self, input_size: usize, output_size: usize) -> usize {
+		match self {
+			Self::Dense => input_size,
+			Self::Recurrent => input_size + output_size,
+			Self::Normalize => 0,
+		}
+	}
+
+	fn has_params(&self) -> bool {
+		*self != Self::Normalize
+	}
+}
+
+impl fmt::Display for LayerKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Dense => write!(f, "dense"),
+			Self::Recurrent => write!(f, "recurrent"),
+			Self::Normalize => write!(f, "normalize"),
+		}
+	}
+}
+
+/// Returned when combining networks of different shapes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TopologyMismatch;
+
+impl fmt::Display for TopologyMismatch {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "networks have different topologies")
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TopologyMismatch {}
+
+/// Returned when a network can't be built out of the given layers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TopologyError {
+	/// A network needs at least one layer.
+	NoLayers,
+	/// A topology needs at least an input and an output entry.
+	TooFewLayers,
+	EmptyLayer {
+		layer: usize,
+	},
+	SkipFromLaterLayer {
+		layer: usize,
+	},
+	/// Only dense layers without skip connections can be mirrored.
+	InvalidMirroring {
+		layer: usize,
+	},
+	InputSizeMismatch {
+		layer: usize,
+		expected: usize,
+		actual: usize,
+	},
+	/// The layer's neurons have fewer weights than its skip and recurrent
+	/// inputs alone need.
+	MissingWeights {
+		layer: usize,
+	},
+}
+
+impl fmt::Display for TopologyError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NoLayers => write!(f, "got no layers"),
+			Self::TooFewLayers => write!(f, "got fewer than two layers"),
+			Self::EmptyLayer { layer } => write!(f, "layer {} has no neurons", layer),
+			Self::SkipFromLaterLayer { layer } => {
+				write!(f, "got a skip connection from a later layer into layer {}", layer)
+			}
+			Self::InvalidMirroring { layer } => {
+				write!(f, "layer {} can't be mirrored, as it's not a plain dense layer", layer)
+			}
+			Self::InputSizeMismatch {
+				layer,
+				expected,
+				actual,
+			} => write!(
+				f,
+				"layer {} takes {} inputs, but gets {}",
+				layer, expected, actual
+			),
+			Self::MissingWeights { layer } => {
+				write!(f, "layer {} has too few weights for its skip and recurrent inputs", layer)
+			}
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TopologyError {}
+
+/// Returned when a network can't be built out of the given weights.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeightsError {
+	Topology(TopologyError),
+	CountMismatch {
+		expected: usize,
+		actual: usize,
+	},
+}
+
+impl From<TopologyError> for WeightsError {
+	fn from(err: TopologyError) -> Self {
+		Self::Topology(err)
+	}
+}
+
+impl fmt::Display for WeightsError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Topology(err) => err.fmt(f),
+			Self::CountMismatch { expected, actual } => {
+				write!(f, "expected {} weights, got {}", expected, actual)
+			}
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WeightsError {}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LayerTopology {
+	pub neurons: usize,
+	// Whether this layer's neurons have a bias; ignored for the input layer
+	pub bias: bool,
+	// Ignored for the input layer
+	pub kind: LayerKind,
+	// Ignored for the input layer
+	pub activation: Activation,
+	// Index of an earlier entry whose outputs this layer receives on top of
+	// the previous entry's, with 0 being the network's inputs
+	pub skip_from: Option<usize>,
+	// Ignored for the input layer
+	pub mirrored: bool,
+}
+
+impl LayerTopology {
+	pub fn new(neurons: usize) -> Self {
+		Self {
+			neurons,
+			bias: true,
+			kind: LayerKind::Dense,
+			activation: Activation::Relu,
+			skip_from: None,
+			mirrored: false,
+		}
+	}
+
+	pub fn without_bias(mut self) -> Self {
+		self.bias = false;
+		self
+	}
+
+	pub fn with_kind(mut self, kind: LayerKind) -> Self {
+		self.kind = kind;
+		self
+	}
+
+	pub fn with_activation(mut self, activation: Activation) -> Self {
+		self.activation = activation;
+		self
+	}
+
+	pub fn with_skip_from(mut self, layer: usize) -> Self {
+		self.skip_from = Some(layer);
+		self
+	}
+
+	/// Ties the layer's weights so that reversing its inputs reverses its
+	/// outputs: the weight of neuron `j` for input `i` is the weight of
+	/// neuron `m - 1 - j` for input `n - 1 - i`, and the two share a bias.
+	///
+	/// E.g. with a left-right symmetric eye as the input, both halves of the
+	/// field of view get treated alike. Only half of the parameters remain
+	/// (a center input or neuron, if any, keeps its own), which is what
+	/// `weights()` and `from_weights()` deal with.
+	///
+	/// Only dense layers without skip connections can be mirrored.
+	pub fn with_mirrored_weights(mut self) -> Self {
+		self.mirrored = true;
+		self
+	}
+
+	/// Number of biases and weights of a network built out of `layers`.
+	pub fn param_count(layers: &[Self]) -> usize {
+		(1..layers.len())
+			.map(|i| {
+				let skip = Skip::of(layers, i);
+				let fan_in = Layer::<f32>::fan_in(layers[i - 1].neurons, skip, &layers[i]);
+
+				(0..layers[i].neurons)
+					.map(|n| {
+						let (bias, weights) = own_params(layers[i].mirrored, layers[i].neurons, n, fan_in);
+
+						(bias && layers[i].has_bias()) as usize + weights
+					})
+					.sum::<usize>()
+			})
+			.sum()
+	}
+
+	// Normalize layers have no parameters, so no bias either
+	fn has_bias(&self) -> bool {
+		self.bias && self.kind.has_params()
+	}
+
+	fn validate(layers: &[Self]) -> Result<(), TopologyError> {
+		if layers.len() < 2 {
+			return Err(TopologyError::TooFewLayers);
+		}
+
+		for (i, layer) in layers.iter().enumerate() {
+			if layer.neurons == 0 {
+				return Err(TopologyError::EmptyLayer { layer: i });
+			}
+
+			if i > 0 && layer.skip_from.map_or(false, |from| from >= i) {
+				return Err(TopologyError::SkipFromLaterLayer { layer: i });
+			}
+
+			if i > 0 && layer.mirrored && (layer.kind != LayerKind::Dense || layer.skip_from.is_some()) {
+				return Err(TopologyError::InvalidMirroring { layer: i });
+			}
+
+			if i > 0 && layer.kind == LayerKind::Normalize {
+				let input_size = layers[i - 1].neurons + Skip::of(layers, i).map_or(0, |skip| skip.size);
+
+				if layer.neurons != input_size {
+					return Err(TopologyError::InputSizeMismatch {
+						layer: i,
+						expected: layer.neurons,
+						actual: input_size,
+					});
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "approx")]
+impl<T> approx::AbsDiffEq for Network<T>
+where
+	T: Float + approx::AbsDiffEq<Epsilon = T>,
+{
+	type Epsilon = T;
+
+	fn default_epsilon() -> T {
+		<T as approx::AbsDiffEq>::default_epsilon()
+	}
+
+	fn abs_diff_eq(&self, other: &Self, epsilon: T) -> bool {
+		self.params_match(other, |a, b| approx::AbsDiffEq::abs_diff_eq(&a, &b, epsilon))
+	}
+}
+
+#[cfg(feature = "approx")]
+impl<T> approx::RelativeEq for Network<T>
+where
+	T: Float + approx::RelativeEq<Epsilon = T>,
+{
+	fn default_max_relative() -> T {
+		<T as approx::RelativeEq>::default_max_relative()
+	}
+
+	fn relative_eq(&self, other: &Self, epsilon: T, max_relative: T) -> bool {
+		self.params_match(other, |a, b| {
+			approx::RelativeEq::relative_eq(&a, &b, epsilon, max_relative)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use approx::assert_relative_eq;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	#[test]
+	fn random() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let neuron: Neuron = Neuron::random(&mut rng, 4, true, &(-1.0..=1.0));
+		assert_relative_eq!(neuron.bias(), -0.6255188);
+		assert_relative_eq!(neuron.weights.as_slice(), [0.67383957, 0.8181262, 0.26284897, 0.5238807].as_ref());
+	}
+
+	#[test]
+	fn propagate() {
+		let neuron = Neuron {
+			bias: Some(0.5),
+			weights: vec![-0.3, 0.8],
+		};
+
+		assert_relative_eq!(neuron.propagate(&[-10.0, -10.0], Activation::Relu), 0.0);
+		assert_relative_eq!(neuron.propagate(&[0.5, 1.0], Activation::Relu), (0.5 * -0.3 + 1.0 * 0.8 + 0.5));
+	} 
+	// TODO: test weight
+
+	#[test]
+	fn propagate_matches_naive_dot_product() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+		for input_size in [1, 4, 9, 513] {
+			let neuron: Neuron = Neuron::random(&mut rng, input_size, true, &(-1.0..=1.0));
+			let inputs: Vec<f32> = (0..input_size).map(|_| rng.gen_range(0.0..=1.0)).collect();
+
+			let expected = inputs
+				.iter()
+				.zip(neuron.weights())
+				.fold(neuron.bias(), |sum, (input, weight)| sum + input * weight)
+				.max(0.0);
+
+			assert_relative_eq!(neuron.propagate(&inputs, Activation::Relu), expected, max_relative = 1e-5);
+		}
+	}
+
+	#[test]
+	fn double_precision() {
+		let topology = [
+			LayerTopology::new(2),
+			LayerTopology::new(2),
+			LayerTopology::new(1),
+		];
+		let weights = vec![0.1, 0.2, 0.3, -0.4, 0.5, 0.6, 0.7, 0.8, -0.9];
+		let mut network: Network<f64> = Network::from_weights(&topology, weights.clone());
+
+		let (x0, x1) = (1.0 / 3.0, 2.0 / 3.0);
+		let h0 = f64::max(0.1 + 0.2 * x0 + 0.3 * x1, 0.0);
+		let h1 = f64::max(-0.4 + 0.5 * x0 + 0.6 * x1, 0.0);
+		let expected = f64::max(0.7 + 0.8 * h0 - 0.9 * h1, 0.0);
+
+		assert_relative_eq!(network.propagate(vec![x0, x1])[0], expected, epsilon = 1e-12);
+		assert_eq!(network.weights(), weights);
+	}
+
+	mod bias {
+		use super::*;
+
+		fn topology() -> [LayerTopology; 4] {
+			[
+				LayerTopology::new(3),
+				LayerTopology::new(4).without_bias(),
+				LayerTopology::new(3),
+				LayerTopology::new(2).without_bias(),
+			]
+		}
+
+		#[test]
+		fn weights_skip_disabled_biases() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let network: Network = Network::random(&mut rng, &topology());
+
+			assert_eq!(network.weights().len(), 4 * 3 + 3 * 5 + 2 * 3);
+			assert!(network.layers()[0].neurons().iter().all(|n| !n.has_bias()));
+			assert!(network.layers()[1].neurons().iter().all(|n| n.has_bias()));
+		}
+
+		#[test]
+		fn weights_round_trip() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let network: Network = Network::random(&mut rng, &topology());
+			let rebuilt = Network::from_weights(&topology(), network.weights());
+
+			assert_eq!(rebuilt, network);
+			assert_eq!(rebuilt.weights(), network.weights());
+		}
+
+		#[test]
+		#[should_panic(expected = "got too many weights")]
+		fn from_weights_does_not_consume_a_bias() {
+			let topology = [LayerTopology::new(2), LayerTopology::new(1).without_bias()];
+
+			Network::from_weights(&topology, vec![0.1, 0.2, 0.3]);
+		}
+
+		#[test]
+		fn propagate_without_bias() {
+			let topology = [LayerTopology::new(2), LayerTopology::new(1).without_bias()];
+			let mut network = Network::from_weights(&topology, vec![0.5, 0.25]);
+
+			assert_relative_eq!(network.propagate(vec![2.0, 4.0])[0], 2.0);
+		}
+	}
+
+	mod recurrent {
+		use super::*;
+
+		fn topology() -> [LayerTopology; 2] {
+			[
+				LayerTopology::new(1),
+				LayerTopology::new(1).with_kind(LayerKind::Recurrent),
+			]
+		}
+
+		#[test]
+		fn remembers_previous_outputs() {
+			// bias, input weight, recurrent weight
+			let mut network = Network::from_weights(&topology(), vec![0.0, 1.0, 0.5]);
+
+			assert_relative_eq!(network.propagate(vec![1.0])[0], 1.0);
+			assert_relative_eq!(network.propagate(vec![1.0])[0], 1.5);
+			assert_relative_eq!(network.propagate(vec![1.0])[0], 1.75);
+		}
+
+		#[test]
+		fn reset_state() {
+			let mut network = Network::from_weights(&topology(), vec![0.0, 1.0, 0.5]);
+
+			network.propagate(vec![1.0]);
+			network.reset_state();
+
+			assert_relative_eq!(network.propagate(vec![1.0])[0], 1.0);
+		}
+
+		#[test]
+		fn state_is_ignored_by_eq() {
+			let mut network = Network::from_weights(&topology(), vec![0.0, 1.0, 0.5]);
+			network.propagate(vec![1.0]);
+
+			assert_eq!(network, Network::from_weights(&topology(), vec![0.0, 1.0, 0.5]));
+		}
+
+		#[test]
+		fn weights_include_recurrent_weights() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let topology = [
+				LayerTopology::new(3),
+				LayerTopology::new(4).with_kind(LayerKind::Recurrent),
+				LayerTopology::new(2),
+			];
+
+			let network: Network = Network::random(&mut rng, &topology);
+			let weights = network.weights();
+			assert_eq!(weights.len(), 4 * (1 + 3 + 4) + 2 * (1 + 4));
+
+			let rebuilt = Network::from_weights(&topology, weights);
+			assert_eq!(rebuilt, network);
+		}
+	}
+
+	mod mirrored {
+		use super::*;
+
+		fn topology(cells: usize, hidden: usize) -> [LayerTopology; 3] {
+			[
+				LayerTopology::new(cells),
+				LayerTopology::new(hidden)
+					.with_activation(Activation::Tanh)
+					.with_mirrored_weights(),
+				LayerTopology::new(2),
+			]
+		}
+
+		fn assert_mirrored(network: &Network, inputs: Vec<f32>) {
+			let reversed: Vec<f32> = inputs.iter().rev().copied().collect();
+
+			let hidden = network.propagate_traced(inputs).activations(0).to_vec();
+			let mirrored: Vec<f32> = hidden.into_iter().rev().collect();
+
+			let trace = network.propagate_traced(reversed);
+			assert_relative_eq!(trace.activations(0), mirrored.as_slice(), epsilon = 1e-6);
+		}
+
+		#[test]
+		fn mirrored_inputs_give_mirrored_activations() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+			for (cells, hidden) in [(4, 6), (5, 6), (4, 3), (5, 3)] {
+				let network: Network = Network::random(&mut rng, &topology(cells, hidden));
+				let inputs = (0..cells).map(|i| (i as f32 * 0.7).sin()).collect();
+
+				assert_mirrored(&network, inputs);
+			}
+		}
+
+		#[test]
+		fn only_unique_params_are_exposed() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+			// Three pairs of neurons, then the outputs
+			assert_eq!(LayerTopology::param_count(&topology(5, 6)), 3 * (1 + 5) + 2 * (1 + 6));
+
+			// One pair, plus a center neuron owning the center cell's weight
+			assert_eq!(LayerTopology::param_count(&topology(5, 3)), (1 + 5) + (1 + 3) + 2 * (1 + 3));
+
+			let network: Network = Network::random(&mut rng, &topology(5, 3));
+			let weights = network.weights();
+
+			assert_eq!(weights.len(), network.param_count());
+			assert_eq!(weights.len(), network.segment_lengths().iter().sum::<usize>());
+			assert_eq!(Network::from_weights(&topology(5, 3), weights.clone()), network);
+			assert_eq!(Network::from_weight_slice(&topology(5, 3), &weights).unwrap(), network);
+		}
+
+		#[test]
+		fn mutations_keep_weights_tied() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut network: Network = Network::random(&mut rng, &topology(5, 3));
+
+			network.perturb_weights(&mut rng, 1.0, 0.5);
+			assert_mirrored(&network, vec![0.1, 0.9, -0.4, 0.3, 0.0]);
+
+			let (perturbed, _) = network.perturb(&mut rng, 0.1);
+			assert_mirrored(&perturbed, vec![0.1, 0.9, -0.4, 0.3, 0.0]);
+		}
+
+		#[test]
+		fn rejects_non_dense_layers() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut topology = topology(4, 4);
+			topology[1].kind = LayerKind::Recurrent;
+
+			assert_eq!(
+				Network::<f32>::try_random(&mut rng, &topology).unwrap_err(),
+				TopologyError::InvalidMirroring { layer: 1 },
+			);
+		}
+	}
+
+	mod normalize {
+		use super::*;
+
+		fn topology() -> [LayerTopology; 4] {
+			[
+				LayerTopology::new(3),
+				LayerTopology::new(4).with_activation(Activation::Tanh),
+				LayerTopology::new(7).with_kind(LayerKind::Normalize).with_skip_from(0),
+				LayerTopology::new(2),
+			]
+		}
+
+		#[test]
+		fn outputs_have_zero_mean_and_unit_variance() {
+			let mut network: Network<f64> = Network::zeros(&[
+				LayerTopology::new(4),
+				LayerTopology::new(4).with_kind(LayerKind::Normalize),
+			]);
+
+			let outputs = network.propagate(vec![1.0, 2.0, 3.0, 6.0]);
+			let mean = outputs.iter().sum::<f64>() / 4.0;
+			let variance = outputs.iter().map(|output| output.powi(2)).sum::<f64>() / 4.0;
+
+			assert_relative_eq!(mean, 0.0, epsilon = 1e-9);
+			assert_relative_eq!(variance, 1.0, epsilon = 1e-4);
+			assert!(outputs[0] < outputs[1] && outputs[2] < outputs[3]);
+
+			// Constant inputs don't divide by zero
+			assert_eq!(network.propagate(vec![0.5; 4]), vec![0.0; 4]);
+		}
+
+		#[test]
+		fn has_no_params() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let network: Network = Network::random(&mut rng, &topology());
+
+			assert_eq!(LayerTopology::param_count(&topology()), 4 * (1 + 3) + 2 * (1 + 7));
+			assert_eq!(network.weights().len(), LayerTopology::param_count(&topology()));
+			assert_eq!(network.layers()[1].input_size(), 4);
+			assert_eq!(Network::from_weights(&topology(), network.weights()), network);
+		}
+
+		#[test]
+		fn propagate_into_matches_propagate() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let topology = [
+				LayerTopology::new(3),
+				LayerTopology::new(5),
+				LayerTopology::new(5).with_kind(LayerKind::Normalize),
+				LayerTopology::new(2),
+			];
+			let mut network: Network = Network::random(&mut rng, &topology);
+			let mut buffer = Vec::new();
+
+			let inputs = [0.5, -0.25, 1.0];
+			network.propagate_into(&inputs, &mut buffer);
+
+			assert_eq!(buffer, network.propagate(inputs.to_vec()));
+		}
+
+		#[test]
+		fn rejects_size_mismatch() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut topology = topology();
+			topology[2].neurons = 4;
+
+			assert_eq!(
+				Network::<f32>::try_random(&mut rng, &topology).unwrap_err(),
+				TopologyError::InputSizeMismatch {
+					layer: 2,
+					expected: 4,
+					actual: 7,
+				},
+			);
+		}
+	}
+
+	#[test]
+	fn from_random_sparse() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let topology = [
+			LayerTopology::new(100),
+			LayerTopology::new(100),
+			LayerTopology::new(100),
+		];
+		let network: Network = Network::from_random_sparse(&mut rng, &topology, 0.3);
+
+		let weights: Vec<f32> = network
+			.layers()
+			.iter()
+			.flat_map(|layer| layer.neurons())
+			.flat_map(|neuron| neuron.weights().iter().copied())
+			.collect();
+		let sparsity = weights.iter().filter(|w| **w == 0.0).count() as f32 / weights.len() as f32;
+		assert!((sparsity - 0.3).abs() < 0.05, "sparsity = {}", sparsity);
+
+		let kept: Vec<f32> = weights.into_iter().filter(|w| *w != 0.0).collect();
+		let variance = kept.iter().map(|w| w.powi(2)).sum::<f32>() / kept.len() as f32;
+		assert_relative_eq!(variance, 2.0 / 200.0, max_relative = 0.1);
+
+		let mask = network.connection_mask();
+		assert_eq!(mask.len(), network.weights().len());
+		for (active, weight) in mask.iter().zip(network.weights()) {
+			assert_eq!(*active, weight != 0.0);
+		}
+	}
+
+	#[test]
+	fn copy_weights_from() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let source: Network = Network::random(
+			&mut rng,
+			&[LayerTopology::new(2), LayerTopology::new(3)],
+		);
+		let mut target: Network = Network::random(
+			&mut rng,
+			&[
+				LayerTopology::new(3),
+				LayerTopology::new(2),
+				LayerTopology::new(1),
+			],
+		);
+		let original = target.weights();
+
+		target.copy_weights_from(&source);
+
+		let source = source.layers()[0].neurons();
+		let target_layers = target.layers();
+		for (n, neuron) in target_layers[0].neurons().iter().enumerate() {
+			assert_eq!(neuron.bias(), source[n].bias());
+			assert_eq!(neuron.weights()[..2], source[n].weights()[..]);
+		}
+
+		// Third input of the first layer and the whole second layer have no
+		// counterpart in the source
+		let weights = target.weights();
+		assert_eq!(weights[3], original[3]);
+		assert_eq!(weights[7], original[7]);
+		assert_eq!(weights[8..], original[8..]);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let topology = [
+			LayerTopology::new(2),
+			LayerTopology::new(3).with_kind(LayerKind::Recurrent),
+			LayerTopology::new(2).with_skip_from(0),
+		];
+
+		let scaler = InputScaler::new(vec![1.0, -2.0], vec![0.5, 2.0]);
+		let mut network: Network = Network::random(&mut rng, &topology).with_input_scaler(scaler);
+		network.propagate(vec![0.3, 0.7]);
+
+		let json = serde_json::to_string(&network).unwrap();
+		let mut restored: Network = serde_json::from_str(&json).unwrap();
+
+		// The state isn't kept, so the restored network starts afresh
+		network.reset_state();
+		assert_eq!(restored, network);
+		assert_eq!(restored.propagate(vec![0.3, 0.7]), network.propagate(vec![0.3, 0.7]));
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_rejects_malformed_networks() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let topology = [LayerTopology::new(2), LayerTopology::new(3), LayerTopology::new(2)];
+		let scaler = InputScaler::new(vec![0.0, 0.0], vec![1.0, 1.0]);
+		let network: Network = Network::random(&mut rng, &topology).with_input_scaler(scaler);
+		let json = serde_json::to_value(&network).unwrap();
+
+		// The second layer takes 3 inputs, but the first one gives 2
+		let mut missing_neuron = json.clone();
+		missing_neuron["layers"][0]["neurons"].as_array_mut().unwrap().pop();
+		assert!(serde_json::from_value::<Network>(missing_neuron).is_err());
+
+		let mut wide_scaler = json.clone();
+		wide_scaler["input_scaler"]["means"].as_array_mut().unwrap().push(0.0.into());
+		assert!(serde_json::from_value::<Network>(wide_scaler).is_err());
+
+		let mut short_scaler = json.clone();
+		short_scaler["input_scaler"]["stds"].as_array_mut().unwrap().pop();
+		assert!(serde_json::from_value::<Network>(short_scaler).is_err());
+
+		assert_eq!(serde_json::from_value::<Network>(json).unwrap(), network);
+	}
+
+	mod mutate_weights {
+		use super::*;
+
+		fn network() -> Network {
+			Network::from_weights(
+				&[
+					LayerTopology::new(2),
+					LayerTopology::new(2).without_bias(),
+					LayerTopology::new(1),
+				],
+				(1..=7).map(|n| n as f32),
+			)
+		}
+
+		#[test]
+		fn visits_parameters_in_weights_order() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut network = network();
+			let mut visited = Vec::new();
+			let mut index = 0.0;
+
+			network.mutate_weights(&mut rng, |weight, _| {
+				visited.push(*weight);
+				*weight = index;
+				index += 1.0;
+			});
+
+			assert_eq!(visited, self::network().weights());
+			assert_eq!(network.weights(), vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+		}
+
+		#[test]
+		fn excluding_biases_skips_the_output_bias() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut network = network();
+			let mut visited = Vec::new();
+
+			network.mutate_weights_excluding_biases(&mut rng, |weight, _| {
+				visited.push(*weight);
+				*weight = 0.0;
+			});
+
+			assert_eq!(visited, vec![1.0, 2.0, 3.0, 4.0, 6.0, 7.0]);
+			assert_eq!(network.weights(), vec![0.0, 0.0, 0.0, 0.0, 5.0, 0.0, 0.0]);
+		}
+
+		#[test]
+		fn perturb_weights_with_zero_chance_is_a_no_op() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut network = network();
+
+			network.perturb_weights(&mut rng, 0.0, 1.0);
+
+			assert_eq!(network, self::network());
+		}
+
+		#[test]
+		fn perturb_weights_with_max_chance_changes_every_parameter() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut network = network();
+
+			network.perturb_weights(&mut rng, 1.0, 0.5);
+
+			for (actual, original) in network.weights().iter().zip(self::network().weights()) {
+				assert_ne!(*actual, original);
+				assert!((actual - original).abs() <= 0.5);
+			}
+		}
+	}
+
+	mod zip_map {
+		use super::*;
+
+		fn network(weights: [f32; 6]) -> Network {
+			Network::from_weights(
+				&[LayerTopology::new(1), LayerTopology::new(2), LayerTopology::new(1).without_bias()],
+				weights,
+			)
+		}
+
+		#[test]
+		fn averages_networks() {
+			let a = network([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+			let b = network([3.0, 2.0, -3.0, 0.0, 1.0, -6.0]);
+
+			let average = a.zip_map(&b, |a, b| (a + b) / 2.0).unwrap();
+
+			assert_eq!(average.weights(), vec![2.0, 2.0, 0.0, 2.0, 3.0, 0.0]);
+		}
+
+		#[test]
+		fn map() {
+			let network = network([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+			assert_eq!(
+				network.map(|weight| -weight).weights(),
+				vec![-1.0, -2.0, -3.0, -4.0, -5.0, -6.0]
+			);
+		}
+
+		#[test]
+		fn rejects_different_topologies() {
+			let a = network([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+			let b: Network = Network::from_weights(
+				&[LayerTopology::new(1), LayerTopology::new(2), LayerTopology::new(1)],
+				[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0],
+			);
+
+			assert_eq!(a.zip_map(&b, |a, _| a), Err(TopologyMismatch));
+		}
+	}
+
+	mod propagate_into {
+		use super::*;
+
+		#[test]
+		fn matches_propagate() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let mut network: Network = Network::random(
+				&mut rng,
+				&[
+					LayerTopology::new(3),
+					LayerTopology::new(8).with_kind(LayerKind::Recurrent),
+					LayerTopology::new(5),
+					LayerTopology::new(2),
+				],
+			);
+			let mut copy = Network::from_weights(&topology(&network), network.weights());
+			let mut buffer = Vec::new();
+
+			for inputs in [[0.5, -1.0, 0.25], [1.0, 0.0, 0.75], [-0.5, 0.5, 0.0]] {
+				copy.propagate_into(&inputs, &mut buffer);
+				assert_eq!(buffer, network.propagate(inputs.to_vec()));
+			}
+		}
+
+		#[test]
+		fn required_buffer_size() {
+			let network: Network = Network::from_weights(
+				&[LayerTopology::new(3), LayerTopology::new(4), LayerTopology::new(2)],
+				vec![0.0; 4 * 4 + 2 * 5],
+			);
+
+			assert_eq!(network.required_buffer_size(), 8);
+		}
+
+		fn topology(network: &Network) -> Vec<LayerTopology> {
+			let mut topology = vec![LayerTopology::new(network.layers()[0].input_size())];
+
+			topology.extend(
+				network
+					.layers()
+					.iter()
+					.map(|layer| LayerTopology::new(layer.neurons().len()).with_kind(layer.kind())),
+			);
+
+			topology
+		}
+	}
+
+	mod prune {
+		use super::*;
+
+		fn network() -> Network {
+			Network::from_weights(
+				&[LayerTopology::new(2), LayerTopology::new(2), LayerTopology::new(1)],
+				vec![
+					0.05, 1.0, 0.01, //
+					0.5, -0.02, 1.0, //
+					-0.03, 1.0, 0.5,
+				],
+			)
+		}
+
+		#[test]
+		fn zeroes_small_weights() {
+			let mut network = network();
+			let before = network.propagate(vec![1.0, 1.0]);
+
+			assert_eq!(network.prune(0.1), 2);
+			assert_eq!(
+				network.weights(),
+				vec![0.05, 1.0, 0.0, 0.5, 0.0, 1.0, -0.03, 1.0, 0.5]
+			);
+			assert_relative_eq!(network.sparsity(), 2.0 / 6.0);
+
+			let after = network.propagate(vec![1.0, 1.0]);
+			assert_relative_eq!(before[0] - after[0], 0.01 - 0.02 * 0.5);
+		}
+
+		#[test]
+		fn zeroes_small_biases_on_request() {
+			let mut network = network();
+
+			assert_eq!(network.prune_including_biases(0.1), 4);
+			assert_eq!(
+				network.weights(),
+				vec![0.0, 1.0, 0.0, 0.5, 0.0, 1.0, 0.0, 1.0, 0.5]
+			);
+			assert_relative_eq!(network.sparsity(), 2.0 / 6.0);
+		}
+
+		#[test]
+		fn pruning_again_prunes_nothing() {
+			let mut network = network();
+			network.prune(0.1);
+
+			assert_eq!(network.prune(0.1), 0);
+		}
+	}
+
+	mod constraints {
+		use super::*;
+
+		fn network() -> Network {
+			Network::from_weights(
+				&[LayerTopology::new(2), LayerTopology::new(2)],
+				vec![
+					60.0, 30.0, -40.0, //
+					0.1, 0.3, -0.4,
+				],
+			)
+		}
+
+		#[test]
+		fn clamp_weights() {
+			let mut network = network();
+			network.clamp_weights(-5.0..=5.0);
+
+			assert_eq!(network.weights(), vec![5.0, 5.0, -5.0, 0.1, 0.3, -0.4]);
+		}
+
+		#[test]
+		fn apply_max_norm() {
+			let mut network = network();
+			network.apply_max_norm(1.0);
+
+			let neurons = network.layers()[0].neurons();
+			let norm = |neuron: &Neuron| neuron.weights().iter().map(|w| w * w).sum::<f32>().sqrt();
+
+			assert_relative_eq!(norm(&neurons[0]), 1.0);
+			assert_relative_eq!(neurons[0].weights()[0], 0.6);
+			assert_relative_eq!(neurons[0].bias(), 60.0);
+
+			// Already compliant, so left untouched
+			assert_eq!(neurons[1], self::network().layers()[0].neurons()[1]);
+		}
+
+		#[test]
+		fn apply_max_norm_keeps_ratios() {
+			let mut network: Network = Network::from_weights(
+				&[LayerTopology::new(3), LayerTopology::new(1)],
+				vec![0.5, 6.0, 0.0, -8.0],
+			);
+			network.apply_max_norm(1.0);
+
+			let weights = network.layers()[0].neurons()[0].weights();
+
+			assert_eq!(weights.iter().map(|w| w * w).sum::<f32>().sqrt(), 1.0);
+			assert_relative_eq!(weights[0] / weights[2], -0.75);
+			assert_eq!(weights[1], 0.0);
+		}
+
+		#[test]
+		fn norms() {
+			let network = network();
+
+			assert_relative_eq!(network.l1_norm(), 60.0 + 30.0 + 40.0 + 0.1 + 0.3 + 0.4);
+			assert_relative_eq!(
+				network.l2_norm(),
+				(3600.0f32 + 900.0 + 1600.0 + 0.01 + 0.09 + 0.16).sqrt(),
+			);
+		}
+
+		#[test]
+		fn decay_weights() {
+			let mut network = network();
+			network.decay_weights(0.5);
+
+			assert_eq!(network.weights(), vec![60.0, 15.0, -20.0, 0.1, 0.15, -0.2]);
+
+			network.decay_weights_including_biases(0.5);
+			assert_eq!(network.weights(), vec![30.0, 7.5, -10.0, 0.05, 0.075, -0.1]);
+		}
+
+		#[test]
+		fn decay_by_zero_is_a_no_op() {
+			let mut network = network();
+			network.decay_weights_including_biases(0.0);
+
+			assert_eq!(network, self::network());
+		}
+	}
+
+	mod skip_connections {
+		use super::*;
+
+		fn topology() -> [LayerTopology; 3] {
+			[
+				LayerTopology::new(2),
+				LayerTopology::new(2),
+				LayerTopology::new(1).with_skip_from(0),
+			]
+		}
+
+		fn network() -> Network<f64> {
+			Network::from_weights(
+				&topology(),
+				vec![
+					0.1, 0.2, 0.3, //
+					-0.4, 0.5, 0.6, //
+					0.7, 0.8, -0.9, 1.0, -1.1,
+				],
+			)
+		}
+
+		#[test]
+		fn propagate() {
+			let (x0, x1) = (0.5, 0.25);
+
+			let h0 = f64::max(0.1 + 0.2 * x0 + 0.3 * x1, 0.0);
+			let h1 = f64::max(-0.4 + 0.5 * x0 + 0.6 * x1, 0.0);
+			let expected = f64::max(0.7 + 0.8 * h0 - 0.9 * h1 + 1.0 * x0 - 1.1 * x1, 0.0);
+
+			let mut network = network();
+			assert_relative_eq!(network.propagate(vec![x0, x1])[0], expected);
+
+			let mut buffer = Vec::new();
+			network.propagate_into(&[x0, x1], &mut buffer);
+			assert_relative_eq!(buffer[0], expected);
+		}
+
+		#[test]
+		fn weights_round_trip() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let network: Network = Network::random(&mut rng, &topology());
+
+			assert_eq!(network.weights().len(), 2 * 3 + (1 + 2 + 2));
+			assert_eq!(network.layers()[1].skip_from(), Some(0));
+			assert_eq!(network.layers()[1].input_size(), 2);
+			assert_eq!(Network::from_weights(&topology(), network.weights()), network);
+		}
+
+		#[test]
+		#[should_panic(expected = "got not enough weights")]
+		fn from_weights_accounts_for_larger_fan_in() {
+			Network::<f32>::from_weights(&topology(), vec![0.0; 2 * 3 + 3]);
+		}
+
+		#[test]
+		#[should_panic(expected = "got a skip connection from a later layer")]
+		fn rejects_skipping_forward() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+			Network::<f32>::random(
+				&mut rng,
+				&[
+					LayerTopology::new(2),
+					LayerTopology::new(2).with_skip_from(2),
+					LayerTopology::new(1),
+				],
+			);
+		}
+	}
+
+	mod validation {
+		use super::*;
+
+		fn layer(input_size: usize, neurons: usize) -> Layer {
+			Network::from_weights(
+				&[LayerTopology::new(input_size), LayerTopology::new(neurons)],
+				vec![0.0; (input_size + 1) * neurons],
+			)
+			.into_layers()
+			.remove(0)
+		}
+
+		#[test]
+		fn rejects_too_few_layers() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+			assert_eq!(
+				Network::<f32>::try_random(&mut rng, &[LayerTopology::new(3)]),
+				Err(TopologyError::TooFewLayers)
+			);
+			assert_eq!(
+				Network::<f32>::try_from_weights(&[], vec![]),
+				Err(TopologyError::TooFewLayers)
+			);
+		}
+
+		#[test]
+		fn rejects_empty_layers() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let topology = [
+				LayerTopology::new(3),
+				LayerTopology::new(0),
+				LayerTopology::new(1),
+			];
+
+			assert_eq!(
+				Network::<f32>::try_random(&mut rng, &topology),
+				Err(TopologyError::EmptyLayer { layer: 1 })
+			);
+			assert_eq!(
+				Network::<f32>::try_from_weights(&topology, vec![]),
+				Err(TopologyError::EmptyLayer { layer: 1 })
+			);
+		}
+
+		#[test]
+		#[should_panic(expected = "layer 0 has no neurons")]
+		fn random_panics_on_empty_input() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+			Network::<f32>::random(&mut rng, &[LayerTopology::new(0), LayerTopology::new(1)]);
+		}
+
+		#[test]
+		fn rejects_skipping_forward() {
+			assert_eq!(
+				Network::<f32>::try_from_weights(
+					&[LayerTopology::new(1), LayerTopology::new(1).with_skip_from(1)],
+					vec![]
+				),
+				Err(TopologyError::SkipFromLaterLayer { layer: 1 })
+			);
+		}
+
+		#[test]
+		fn new_rejects_no_layers() {
+			assert_eq!(Network::<f32>::try_new(vec![]), Err(TopologyError::NoLayers));
+		}
+
+		#[test]
+		fn new_rejects_mismatched_layers() {
+			assert_eq!(
+				Network::try_new(vec![layer(2, 3), layer(4, 1)]),
+				Err(TopologyError::InputSizeMismatch {
+					layer: 1,
+					expected: 4,
+					actual: 3
+				})
+			);
+		}
+
+		#[test]
+		fn new_accepts_matching_layers() {
+			let network = Network::new(vec![layer(2, 3), layer(3, 1)]);
+
+			assert_eq!(network.layers().len(), 2);
+		}
+
+		#[test]
+		#[should_panic(expected = "layer 1 takes 4 inputs, but gets 3")]
+		fn new_panics_on_mismatched_layers() {
+			Network::new(vec![layer(2, 3), layer(4, 1)]);
+		}
+
+		#[test]
+		fn new_rejects_layers_missing_weights() {
+			let mut recurrent = layer(1, 3);
+			recurrent.kind = LayerKind::Recurrent;
+
+			assert_eq!(recurrent.input_size(), 0);
+			assert_eq!(
+				Network::try_new(vec![recurrent]),
+				Err(TopologyError::MissingWeights { layer: 0 })
+			);
+
+			let mut skipping = layer(3, 2);
+			skipping.skip = Some(Skip { from: 0, size: 5 });
+
+			assert_eq!(
+				Network::try_new(vec![layer(2, 3), skipping]),
+				Err(TopologyError::MissingWeights { layer: 1 })
+			);
+		}
+	}
+
+	mod from_weight_slice {
+		use super::*;
+
+		fn topology() -> [LayerTopology; 4] {
+			[
+				LayerTopology::new(3),
+				LayerTopology::new(4).with_kind(LayerKind::Recurrent),
+				LayerTopology::new(2).without_bias(),
+				LayerTopology::new(1).with_skip_from(0),
+			]
+		}
+
+		#[test]
+		fn exact() {
+			let weights: Vec<f32> = (0..LayerTopology::param_count(&topology()))
+				.map(|i| i as f32 / 10.0)
+				.collect();
+
+			assert_eq!(
+				Network::from_weight_slice(&topology(), &weights),
+				Ok(Network::from_weights(&topology(), weights))
+			);
+		}
+
+		#[test]
+		fn too_short() {
+			let count = LayerTopology::param_count(&topology());
+
+			assert_eq!(
+				Network::from_weight_slice(&topology(), &vec![0.0; count - 1]),
+				Err(WeightsError::CountMismatch {
+					expected: count,
+					actual: count - 1
+				})
+			);
+		}
+
+		#[test]
+		fn too_long() {
+			let count = LayerTopology::param_count(&topology());
+
+			assert_eq!(
+				Network::from_weight_slice(&topology(), &vec![0.0; count + 1]),
+				Err(WeightsError::CountMismatch {
+					expected: count,
+					actual: count + 1
+				})
+			);
+		}
+
+		#[test]
+		fn invalid_topology() {
+			assert_eq!(
+				Network::<f32>::from_weight_slice(&[LayerTopology::new(2)], &[]),
+				Err(WeightsError::Topology(TopologyError::TooFewLayers))
+			);
+		}
+	}
+
+	mod weight_groups {
+		use super::*;
+
+		fn network() -> Network {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+			Network::random(
+				&mut rng,
+				&[
+					LayerTopology::new(3),
+					LayerTopology::new(4).with_kind(LayerKind::Recurrent),
+					LayerTopology::new(2).without_bias(),
+				],
+			)
+		}
+
+		#[test]
+		fn weights_by_layer() {
+			let network = self::network();
+			let groups = network.weights_by_layer();
+
+			assert_eq!(groups.iter().map(Vec::len).collect::<Vec<_>>(), vec![32, 8]);
+			assert_eq!(groups.concat(), network.weights());
+		}
+
+		#[test]
+		fn segment_lengths() {
+			let network = self::network();
+			let lengths = network.segment_lengths();
+
+			assert_eq!(lengths, vec![8, 8, 8, 8, 4, 4]);
+
+			let mut weights = network.weights().into_iter();
+			let segments: Vec<Vec<f32>> = lengths
+				.iter()
+				.map(|&length| weights.by_ref().take(length).collect())
+				.collect();
+
+			assert_eq!(segments.concat(), network.weights());
+			assert_eq!(segments[4][..], network.layers()[1].neurons()[0].weights()[..]);
+		}
+
+		#[test]
+		fn weight_matrix() {
+			let mut rng = ChaCha8Rng::from_seed(Default::default());
+			let network: Network = Network::random(&mut rng, &[LayerTopology::new(3), LayerTopology::new(4)]);
+			let layer = &network.layers()[0];
+			let matrix = layer.weight_matrix();
+
+			assert_eq!(matrix.len(), 4);
+			assert_eq!(matrix[0].len(), 3);
+			assert_eq!(matrix[2][..], layer.neurons()[2].weights()[..]);
+			assert_eq!(layer.bias_vector().len(), 4);
+			assert_eq!(layer.bias_vector()[1], layer.neurons()[1].bias());
+		}
+
+		#[test]
+		fn weight_matrices() {
+			let network = self::network();
+			let matrices = network.weight_matrices();
+			let shape = |matrix: &Vec<Vec<f32>>| (matrix.len(), matrix[0].len());
+
+			// The recurrent layer sees its own 4 outputs on top of 3 inputs
+			assert_eq!(matrices.iter().map(shape).collect::<Vec<_>>(), vec![(4, 7), (2, 4)]);
+			assert_eq!(network.layers()[1].bias_vector(), vec![0.0, 0.0]);
+		}
+	}
+
+	mod constant {
+		use super::*;
+
+		#[test]
+		fn zeros() {
+			let mut network: Network = Network::zeros(&[
+				LayerTopology::new(3),
+				LayerTopology::new(4),
+				LayerTopology::new(2),
+			]);
+
+			assert!(network.weights().iter().all(|weight| *weight == 0.0));
+			assert_eq!(network.propagate(vec![1.0, -5.0, 10.0]), vec![0.0, 0.0]);
+		}
+
+		#[test]
+		fn constant() {
+			let mut network: Network<f64> = Network::constant(
+				&[
+					LayerTopology::new(2),
+					LayerTopology::new(2),
+					LayerTopology::new(1),
+				],
+				0.5,
+			);
+			let (x0, x1) = (1.0, -3.0);
+
+			let h = f64::max(0.5 + 0.5 * x0 + 0.5 * x1, 0.0);
+			let expected = f64::max(0.5 + 0.5 * h + 0.5 * h, 0.0);
+
+			assert_eq!(network.weights().len(), 2 * 3 + 3);
+			assert_relative_eq!(network.propagate(vec![x0, x1])[0], expected);
+		}
+
+		#[test]
+		fn identity_like() {
+			let mut network: Network = Network::identity_like(&[
+				LayerTopology::new(3),
+				LayerTopology::new(3),
+				LayerTopology::new(2),
+			]);
+
+			assert_eq!(
+				network.weights(),
+				vec![
+					0.0, 1.0, 0.0, 0.0, //
+					0.0, 0.0, 1.0, 0.0, //
+					0.0, 0.0, 0.0, 1.0, //
+					0.0, 1.0, 0.0, 0.0, //
+					0.0, 0.0, 1.0, 0.0,
+				]
+			);
+			assert_eq!(network.propagate(vec![0.5, 2.0, 3.0]), vec![0.5, 2.0]);
+		}
+
+		#[test]
+		fn param_count() {
+			let topology = [
+				LayerTopology::new(3),
+				LayerTopology::new(4).without_bias(),
+				LayerTopology::new(2).with_kind(LayerKind::Recurrent).with_skip_from(0),
+			];
+
+			assert_eq!(LayerTopology::param_count(&topology), 4 * 3 + 2 * (1 + 4 + 3 + 2));
+		}
+	}
+
+	#[test]
+	fn random_in() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let network = Network::random_in(
+			&mut rng,
+			&[
+				LayerTopology::new(9),
+				LayerTopology::new(18),
+				LayerTopology::new(2),
+			],
+			-0.1..=0.1,
+		);
+
+		let weights = network.weights();
+		assert_eq!(weights.len(), 18 * 10 + 2 * 19);
+		assert!(weights.iter().all(|w| (-0.1..=0.1).contains(w)));
+	}
+
+	#[test]
+	#[should_panic(expected = "got an empty range")]
+	fn random_in_rejects_empty_range() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		#[allow(clippy::reversed_empty_ranges)]
+		let range = 0.1..=-0.1;
+
+		Network::random_in(
+			&mut rng,
+			&[LayerTopology::new(1), LayerTopology::new(1)],
+			range,
+		);
+	}
+
+	#[test]
+	#[should_panic(expected = "got an infinite range")]
+	fn random_in_rejects_infinite_range() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		Network::random_in(
+			&mut rng,
+			&[LayerTopology::new(1), LayerTopology::new(1)],
+			0.0..=f32::INFINITY,
+		);
+	}
+
+	#[test]
+	fn layers_and_neurons() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let network = Network::random(
+			&mut rng,
+			&[
+				LayerTopology::new(3),
+				LayerTopology::new(4),
+				LayerTopology::new(2),
+			],
+		);
+
+		let layers = network.layers();
+		assert_eq!(layers.len(), 2);
+		assert_eq!(layers[0].neurons().len(), 4);
+		assert_eq!(layers[1].neurons().len(), 2);
+
+		let flattened: Vec<f32> = layers
+			.iter()
+			.flat_map(|layer| layer.neurons())
+			.flat_map(|neuron| std::iter::once(neuron.bias()).chain(neuron.weights().iter().copied()))
+			.collect();
+
+		assert_eq!(flattened, network.weights());
+	}
+
+	mod approx_eq {
+		use super::*;
+
+		fn network(weights: &[f32]) -> Network {
+			Network::from_weights(
+				&[
+					LayerTopology::new(2),
+					LayerTopology::new(1),
+				],
+				weights.iter().copied(),
+			)
+		}
+
+		#[test]
+		fn equal_networks() {
+			let a = network(&[0.1, 0.2, 0.3]);
+			let b = network(&[0.1, 0.2, 0.3]);
+
+			assert!(a.approx_eq(&b, 0.0));
+		}
+
+		#[test]
+		fn slightly_perturbed_networks() {
+			let a = network(&[0.1, 0.2, 0.3]);
+			let b = network(&[0.1, 0.2, 0.301]);
+
+			assert!(a.approx_eq(&b, 0.01));
+			assert!(!a.approx_eq(&b, 0.0001));
+		}
+
+		#[test]
+		fn differently_shaped_networks() {
+			let a = network(&[0.1, 0.2, 0.3]);
+			let b = Network::from_weights(
+				&[
+					LayerTopology::new(2),
+					LayerTopology::new(2),
+				],
+				vec![0.1, 0.2, 0.3, 0.1, 0.2, 0.3],
+			);
+			let c = Network::from_weights(
+				&[
+					LayerTopology::new(2),
+					LayerTopology::new(1),
+					LayerTopology::new(1),
+				],
+				vec![0.1, 0.2, 0.3, 0.0, 0.0],
+			);
+
+			assert!(!a.approx_eq(&b, 1.0));
+			assert!(!a.approx_eq(&c, 1.0));
+		}
+
+		#[cfg(feature = "approx")]
+		#[test]
+		fn assert_relative_eq_on_networks() {
+			let a = network(&[0.1, 0.2, 0.3]);
+			let b = network(&[0.1, 0.2, 0.3]);
+
+			assert_relative_eq!(a, b);
+			approx::assert_relative_ne!(a, network(&[0.1, 0.2, 0.4]));
+		}
+	}
+
+	mod properties {
+		use super::*;
+		use proptest::prelude::*;
+		use std::panic;
+
+		fn topology() -> impl Strategy<Value = Vec<LayerTopology>> {
+			prop::collection::vec(1..=20usize, 2..=5)
+				.prop_map(|sizes| sizes.into_iter().map(LayerTopology::new).collect())
+		}
+
+		fn weight_count(topology: &[LayerTopology]) -> usize {
+			topology
+				.windows(2)
+				.map(|layers| (layers[0].neurons + 1) * layers[1].neurons)
+				.sum()
+		}
+
+		prop_compose! {
+			fn network()(topology in topology())
+				(weights in prop::collection::vec(-1.0f32..=1.0, weight_count(&topology)),
+				 topology in Just(topology)) -> (Vec<LayerTopology>, Vec<f32>) {
+				(topology, weights)
+			}
+		}
+
+		prop_compose! {
+			fn network_with_inputs()((topology, weights) in network())
+				(inputs in prop::collection::vec(-10.0f32..=10.0, topology[0].neurons),
+				 topology in Just(topology),
+				 weights in Just(weights)) -> (Vec<LayerTopology>, Vec<f32>, Vec<f32>) {
+				(topology, weights, inputs)
+			}
+		}
+
+		proptest! {
+			#[test]
+			fn round_trip_through_weights((topology, weights, inputs) in network_with_inputs()) {
+				let mut original: Network = Network::from_weights(&topology, weights);
+				let mut restored: Network = Network::from_weights(&topology, original.weights());
+
+				prop_assert_eq!(original.propagate(inputs.clone()), restored.propagate(inputs));
+			}
+
+			#[test]
+			fn from_weights_panics_only_on_wrong_count(
+				topology in topology(),
+				offset in -3isize..=3,
+			) {
+				let count = (weight_count(&topology) as isize + offset) as usize;
+
+				let result = panic::catch_unwind(|| {
+					Network::<f32>::from_weights(&topology, vec![0.5; count])
+				});
+
+				prop_assert_eq!(result.is_err(), offset != 0);
+			}
+
+			#[test]
+			fn output_length_matches_last_layer(
+				(topology, weights, inputs) in network_with_inputs(),
+			) {
+				let mut network: Network = Network::from_weights(&topology, weights);
+
+				prop_assert_eq!(
+					network.propagate(inputs).len(),
+					topology.last().unwrap().neurons
+				);
+			}
+		}
+	}
+}
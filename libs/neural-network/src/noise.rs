@@ -0,0 +1,66 @@
+use crate::*;
+use rand_distr::StandardNormal;
+
+impl<T: Float> Network<T> {
+	/// Returns a copy with gaussian noise of standard deviation `sigma`
+	/// added to every bias and weight, e.g. to measure how robust the
+	/// network is.
+	pub fn with_noise(&self, rng: &mut dyn RngCore, sigma: T) -> Self {
+		self.map(|weight| weight + gaussian(rng, sigma))
+	}
+
+	/// Same as `propagate()`, but adds gaussian noise of standard deviation
+	/// `input_sigma` to the inputs first, e.g. to simulate noisy sensors.
+	pub fn propagate_noisy(&mut self, inputs: Vec<T>, rng: &mut dyn RngCore, input_sigma: T) -> Vec<T> {
+		let inputs = inputs
+			.into_iter()
+			.map(|input| input + gaussian(rng, input_sigma))
+			.collect();
+
+		self.propagate(inputs)
+	}
+}
+
+pub(crate) fn gaussian<T: Float>(rng: &mut dyn RngCore, sigma: T) -> T {
+	assert!(sigma >= T::zero());
+
+	let sample: f64 = rng.sample(StandardNormal);
+	T::from(sample).unwrap() * sigma
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn network(rng: &mut dyn RngCore) -> Network {
+		Network::random(
+			rng,
+			&[LayerTopology::new(3), LayerTopology::new(4), LayerTopology::new(2)],
+		)
+	}
+
+	#[test]
+	fn with_noise() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let network = network(&mut rng);
+
+		assert_eq!(network.with_noise(&mut rng, 0.0), network);
+
+		let noisy = network.with_noise(&mut rng, 0.1);
+		assert_ne!(noisy, network);
+		assert!(noisy.approx_eq(&network, 0.5));
+	}
+
+	#[test]
+	fn propagate_noisy() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network = network(&mut rng);
+		let inputs = vec![0.5, 0.25, 1.0];
+		let expected = network.propagate(inputs.clone());
+
+		assert_eq!(network.propagate_noisy(inputs.clone(), &mut rng, 0.0), expected);
+		assert_ne!(network.propagate_noisy(inputs, &mut rng, 0.5), expected);
+	}
+}
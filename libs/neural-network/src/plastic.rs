@@ -0,0 +1,228 @@
+use crate::*;
+
+/// Network whose weights keep adapting while it runs, following the
+/// Hebbian rule `w += eta * (a * pre * post + b * pre + c * post)`.
+///
+/// Every connection carries its own `(a, b, c)` coefficients; biases are
+/// not plastic. Mirrored layers aren't supported, since plasticity would
+/// untie their weights.
+#[derive(Debug)]
+pub struct PlasticNetwork<T = f32> {
+	network: Network<T>,
+	// Per connection, in the order of `Network::weights()` (minus biases)
+	coefficients: Vec<[T; 3]>,
+	// Weights the network started with, per connection as well
+	baseline: Vec<T>,
+	eta: T,
+}
+
+impl<T: Float> PlasticNetwork<T> {
+	/// Draws weights and Hebbian coefficients uniformly from `-1.0..=1.0`.
+	pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology], eta: T) -> Self {
+		let network = Network::random(rng, layers);
+		let range = -T::one()..=T::one();
+
+		let coefficients = (0..Self::connections(&network).count())
+			.map(|_| {
+				[
+					rng.gen_range(range.clone()),
+					rng.gen_range(range.clone()),
+					rng.gen_range(range.clone()),
+				]
+			})
+			.collect();
+
+		Self::new(network, coefficients, eta)
+	}
+
+	/// Counterpart of `weights()`.
+	pub fn from_weights(
+		layers: &[LayerTopology],
+		eta: T,
+		weights: impl IntoIterator<Item = T>,
+	) -> Self {
+		LayerTopology::validate(layers).unwrap_or_else(|err| panic!("{}", err));
+		assert!(!layers.iter().any(|layer| layer.mirrored), "got a mirrored layer");
+
+		let mut weights = weights.into_iter();
+		let mut next = || weights.next().expect("got not enough weights");
+
+		let mut plain = Vec::new();
+		let mut coefficients = Vec::new();
+
+		for i in 1..layers.len() {
+			let skip = Skip::of(layers, i);
+			let fan_in = Layer::<T>::fan_in(layers[i - 1].neurons, skip, &layers[i]);
+
+			for _ in 0..layers[i].neurons {
+				if layers[i].has_bias() {
+					plain.push(next());
+				}
+
+				for _ in 0..fan_in {
+					plain.push(next());
+					coefficients.push([next(), next(), next()]);
+				}
+			}
+		}
+
+		if weights.next().is_some() {
+			panic!("got too many weights");
+		}
+
+		Self::new(Network::from_weights(layers, plain), coefficients, eta)
+	}
+
+	fn new(network: Network<T>, coefficients: Vec<[T; 3]>, eta: T) -> Self {
+		// Hebbian updates would untie mirrored weights
+		assert!(!network.layers.iter().any(|layer| layer.mirrored), "got a mirrored layer");
+
+		let baseline = Self::connections(&network).copied().collect();
+
+		Self {
+			network,
+			coefficients,
+			baseline,
+			eta,
+		}
+	}
+
+	/// The network with its weights as they are right now.
+	pub fn network(&self) -> &Network<T> {
+		&self.network
+	}
+
+	/// Returns the bias of each neuron followed by `w, a, b, c` of each of
+	/// its connections, where `w` is the baseline (not the adapted) weight.
+	pub fn weights(&self) -> Vec<T> {
+		let mut weights = Vec::new();
+		let mut connections = self.baseline.iter().zip(&self.coefficients);
+
+		for layer in &self.network.layers {
+			for neuron in &layer.neurons {
+				weights.extend(neuron.bias);
+
+				for (weight, [a, b, c]) in connections.by_ref().take(neuron.weights.len()) {
+					weights.extend([*weight, *a, *b, *c]);
+				}
+			}
+		}
+
+		weights
+	}
+
+	/// Propagates `inputs` and then adapts every connection's weight.
+	pub fn propagate(&mut self, inputs: Vec<T>) -> Vec<T> {
+		let mut coefficients = self.coefficients.iter();
+		let mut outputs = vec![inputs];
+
+		for layer in &mut self.network.layers {
+			let inputs = layer.gather_inputs(&outputs);
+
+			// Recurrent layers see their previous outputs as extra inputs
+			let mut pre = inputs.clone();
+			pre.extend_from_slice(&layer.state);
+
+			let layer_outputs = layer.propagate(inputs);
+
+			for (neuron, post) in layer.neurons.iter_mut().zip(&layer_outputs) {
+				for (weight, pre) in neuron.weights.iter_mut().zip(&pre) {
+					let [a, b, c] = *coefficients.next().unwrap();
+					*weight += self.eta * (a * *pre * *post + b * *pre + c * *post);
+				}
+			}
+
+			outputs.push(layer_outputs);
+		}
+
+		outputs.pop().unwrap()
+	}
+
+	/// Restores the weights the network started with, e.g. at generation
+	/// boundaries.
+	pub fn reset_plasticity(&mut self) {
+		let mut baseline = self.baseline.iter();
+
+		for layer in &mut self.network.layers {
+			for neuron in &mut layer.neurons {
+				for weight in &mut neuron.weights {
+					*weight = *baseline.next().unwrap();
+				}
+			}
+		}
+
+		self.network.reset_state();
+	}
+
+	fn connections(network: &Network<T>) -> impl Iterator<Item = &T> {
+		network
+			.layers
+			.iter()
+			.flat_map(|layer| &layer.neurons)
+			.flat_map(|neuron| &neuron.weights)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use approx::assert_relative_eq;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn network() -> PlasticNetwork {
+		PlasticNetwork::from_weights(
+			&[LayerTopology::new(1), LayerTopology::new(1)],
+			0.1,
+			// bias, then w, a, b, c
+			vec![0.0, 0.5, 1.0, 0.5, -0.25],
+		)
+	}
+
+	#[test]
+	fn propagate_applies_hebbian_rule() {
+		let mut network = network();
+
+		// post = relu(0.5 * 2.0) = 1.0
+		assert_relative_eq!(network.propagate(vec![2.0])[0], 1.0);
+
+		// w = 0.5 + 0.1 * (1.0 * 2.0 * 1.0 + 0.5 * 2.0 - 0.25 * 1.0)
+		let weight = network.network().layers()[0].neurons()[0].weights()[0];
+		assert_relative_eq!(weight, 0.775);
+		assert_relative_eq!(network.propagate(vec![2.0])[0], 1.55);
+	}
+
+	#[test]
+	fn reset_plasticity_restores_baseline() {
+		let mut network = network();
+
+		for _ in 0..3 {
+			network.propagate(vec![2.0]);
+		}
+		network.reset_plasticity();
+
+		assert_eq!(network.network().weights(), vec![0.0, 0.5]);
+		assert_relative_eq!(network.propagate(vec![2.0])[0], 1.0);
+	}
+
+	#[test]
+	fn weights_round_trip() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let topology = [
+			LayerTopology::new(3),
+			LayerTopology::new(2).without_bias(),
+			LayerTopology::new(1),
+		];
+		let mut network: PlasticNetwork = PlasticNetwork::random(&mut rng, &topology, 0.01);
+		let weights = network.weights();
+
+		assert_eq!(weights.len(), 2 * 3 * 4 + (1 + 2 * 4));
+
+		// Adapted weights don't leak into what the GA sees
+		network.propagate(vec![1.0, 0.5, 0.25]);
+		assert_eq!(network.weights(), weights);
+
+		let restored = PlasticNetwork::from_weights(&topology, 0.01, weights.clone());
+		assert_eq!(restored.weights(), weights);
+	}
+}
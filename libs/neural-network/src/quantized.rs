@@ -0,0 +1,282 @@
+use crate::*;
+
+/// A network with its weights stored as `i8`s (plus one scale per layer),
+/// which is four times smaller than `f32`s and lets most of propagation
+/// happen on integers; see `Network::quantize()`.
+///
+/// Biases are kept as floats, since there's only one per neuron.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantizedNetwork<T = f32> {
+	layers: Vec<QuantizedLayer<T>>,
+	input_scaler: Option<InputScaler<T>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct QuantizedLayer<T> {
+	// Row-major, `fan_in` weights per neuron
+	weights: Vec<i8>,
+	biases: Vec<Option<T>>,
+	fan_in: usize,
+	// Value of a single step of the quantized weights
+	scale: T,
+	activation: Activation,
+	skip: Option<Skip>,
+	mirrored: bool,
+}
+
+impl<T: Float> Network<T> {
+	/// Rounds the weights of each layer to 255 evenly spaced values,
+	/// covering the range of the layer's largest weight.
+	///
+	/// Non-finite weights don't count towards that range: NaNs become zero
+	/// and infinities saturate to the largest value of their sign.
+	///
+	/// Only dense layers can be quantized; recurrent ones carry state and
+	/// normalize ones have no weights to round, so both cause a panic.
+	pub fn quantize(&self) -> QuantizedNetwork<T> {
+		let layers = self
+			.layers
+			.iter()
+			.map(|layer| {
+				assert!(layer.kind == LayerKind::Dense, "got a {} layer", layer.kind);
+
+				let max = layer
+					.neurons
+					.iter()
+					.flat_map(|neuron| &neuron.weights)
+					.filter(|weight| weight.is_finite())
+					.fold(T::zero(), |max, weight| max.max(weight.abs()));
+
+				let scale = if max.is_zero() {
+					T::one()
+				} else {
+					max / T::from(i8::MAX).unwrap()
+				};
+
+				QuantizedLayer {
+					weights: layer
+						.neurons
+						.iter()
+						.flat_map(|neuron| &neuron.weights)
+						.map(|weight| quantize(*weight / scale))
+						.collect(),
+					biases: layer.neurons.iter().map(|neuron| neuron.bias).collect(),
+					fan_in: layer.neurons[0].weights.len(),
+					scale,
+					activation: layer.activation,
+					skip: layer.skip,
+					mirrored: layer.mirrored,
+				}
+			})
+			.collect();
+
+		QuantizedNetwork {
+			layers,
+			input_scaler: self.input_scaler.clone(),
+		}
+	}
+}
+
+impl<T: Float> QuantizedNetwork<T> {
+	pub fn propagate(&self, inputs: &[T]) -> Vec<T> {
+		let mut inputs = inputs.to_vec();
+
+		if let Some(scaler) = &self.input_scaler {
+			scaler.apply(&mut inputs);
+		}
+
+		// Outputs of every layer so far, starting with the network's inputs
+		let mut outputs = vec![inputs];
+
+		for layer in &self.layers {
+			let mut inputs = outputs.last().unwrap().clone();
+
+			if let Some(skip) = layer.skip {
+				inputs.extend_from_slice(&outputs[skip.from]);
+			}
+
+			outputs.push(layer.propagate(&inputs));
+		}
+
+		outputs.pop().unwrap()
+	}
+
+	/// Turns the quantized weights back into floats.
+	pub fn dequantize(&self) -> Network<T> {
+		let layers = self
+			.layers
+			.iter()
+			.map(|layer| {
+				let neurons = layer
+					.weights
+					.chunks(layer.fan_in)
+					.zip(&layer.biases)
+					.map(|(weights, bias)| Neuron {
+						bias: *bias,
+						weights: weights
+							.iter()
+							.map(|weight| T::from(*weight).unwrap() * layer.scale)
+							.collect(),
+					})
+					.collect();
+
+				Layer {
+					skip: layer.skip,
+					mirrored: layer.mirrored,
+					..Layer::new(neurons, LayerKind::Dense, layer.activation)
+				}
+			})
+			.collect();
+
+		Network {
+			input_scaler: self.input_scaler.clone(),
+			..Network::new(layers)
+		}
+	}
+
+	/// Returns the largest difference between the outputs of `self` and
+	/// `network` over `samples` random inputs in [-1, 1].
+	pub fn max_error(&self, network: &mut Network<T>, rng: &mut dyn RngCore, samples: usize) -> T {
+		let input_size = self.layers[0].fan_in;
+		let mut max_error = T::zero();
+
+		for _ in 0..samples {
+			let inputs: Vec<T> = (0..input_size)
+				.map(|_| rng.gen_range(-T::one()..=T::one()))
+				.collect();
+
+			let expected = network.propagate(inputs.clone());
+
+			for (actual, expected) in self.propagate(&inputs).into_iter().zip(expected) {
+				max_error = max_error.max((actual - expected).abs());
+			}
+		}
+
+		max_error
+	}
+}
+
+impl<T: Float> QuantizedLayer<T> {
+	fn propagate(&self, inputs: &[T]) -> Vec<T> {
+		assert_eq!(inputs.len(), self.fan_in);
+
+		// Inputs get quantized as well, so that products can be summed up
+		// as integers
+		let max = inputs
+			.iter()
+			.filter(|input| input.is_finite())
+			.fold(T::zero(), |max, input| max.max(input.abs()));
+
+		let input_scale = if max.is_zero() {
+			T::one()
+		} else {
+			max / T::from(i8::MAX).unwrap()
+		};
+
+		let inputs: Vec<i8> = inputs.iter().map(|input| quantize(*input / input_scale)).collect();
+
+		self.weights
+			.chunks(self.fan_in)
+			.zip(&self.biases)
+			.map(|(weights, bias)| {
+				let sum: i32 = weights
+					.iter()
+					.zip(&inputs)
+					.map(|(weight, input)| *weight as i32 * *input as i32)
+					.sum();
+
+				let sum = T::from(sum).unwrap() * self.scale * input_scale;
+
+				self.activation.apply(sum + bias.unwrap_or_else(T::zero))
+			})
+			.collect()
+	}
+}
+
+// NaNs become zero and values out of range (infinities included) saturate
+fn quantize<T: Float>(value: T) -> i8 {
+	if value.is_nan() {
+		return 0;
+	}
+
+	let max = T::from(i8::MAX).unwrap();
+
+	value.round().max(-max).min(max).to_i8().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn topology() -> [LayerTopology; 4] {
+		[
+			LayerTopology::new(9),
+			LayerTopology::new(18),
+			LayerTopology::new(6).without_bias().with_activation(Activation::Tanh),
+			LayerTopology::new(2).with_skip_from(1),
+		]
+	}
+
+	#[test]
+	fn max_error() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network: Network = Network::random(&mut rng, &topology());
+		let quantized = network.quantize();
+
+		let max_error = quantized.max_error(&mut network, &mut rng, 100);
+
+		assert!(max_error > 0.0);
+		assert!(max_error < 0.05, "max error: {}", max_error);
+	}
+
+	#[test]
+	fn dequantize() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let network: Network = Network::random(&mut rng, &topology());
+		let restored = network.quantize().dequantize();
+
+		assert!(restored.approx_eq(&network, 0.01));
+		assert_eq!(restored.segment_lengths(), network.segment_lengths());
+
+		for (a, b) in restored.layers().iter().zip(network.layers()) {
+			assert_eq!(a.activation(), b.activation());
+			assert_eq!(a.skip_from(), b.skip_from());
+		}
+
+		// Already quantized weights survive another round trip
+		assert_eq!(restored.quantize(), network.quantize());
+	}
+
+	#[test]
+	fn zero_weights() {
+		let network: Network = Network::zeros(&topology());
+
+		assert_eq!(network.quantize().propagate(&[1.0; 9]), vec![0.0, 0.0]);
+		assert_eq!(network.quantize().dequantize(), network);
+	}
+
+	#[test]
+	fn non_finite_weights() {
+		let network: Network = Network::from_weights(
+			&[LayerTopology::new(4), LayerTopology::new(1)],
+			vec![0.0, 0.5, f32::NAN, f32::INFINITY, f32::NEG_INFINITY],
+		);
+		let restored = network.quantize().dequantize();
+
+		assert_eq!(restored.layers()[0].neurons()[0].weights(), [0.5, 0.0, 0.5, -0.5]);
+		assert_eq!(network.quantize().propagate(&[1.0, 1.0, f32::NAN, 0.0]), vec![0.5]);
+	}
+
+	#[test]
+	#[should_panic(expected = "got a recurrent layer")]
+	fn rejects_recurrent_layers() {
+		let network: Network = Network::zeros(&[
+			LayerTopology::new(2),
+			LayerTopology::new(2).with_kind(LayerKind::Recurrent),
+		]);
+
+		network.quantize();
+	}
+}
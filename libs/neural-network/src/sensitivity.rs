@@ -0,0 +1,196 @@
+use crate::*;
+
+impl<T: Float> Neuron<T> {
+	/// Returns the derivative of the neuron's output with respect to each of
+	/// `inputs` (i.e. its row of the layer's Jacobian), given its layer's
+	/// `activation`.
+	///
+	/// For ReLU that's simply the weights when the neuron is active and
+	/// zeros otherwise.
+	pub fn activation_sensitivity(&self, inputs: &[T], activation: Activation) -> Vec<T> {
+		let slope = activation.derivative(self.weighted_sum(inputs));
+
+		self.weights.iter().map(|weight| *weight * slope).collect()
+	}
+}
+
+impl<T: Float> Layer<T> {
+	// Gradient with respect to `inputs` (the state of recurrent layers left
+	// out), given the one with respect to the layer's sums and its outputs
+	fn input_gradient(&self, inputs: &[T], outputs: &[T], deltas: &[T]) -> Vec<T> {
+		let mut grads = vec![T::zero(); inputs.len()];
+
+		match self.kind {
+			LayerKind::Dense | LayerKind::Recurrent => {
+				for (neuron, delta) in self.neurons.iter().zip(deltas) {
+					for (grad, weight) in grads.iter_mut().zip(&neuron.weights) {
+						*grad += *weight * *delta;
+					}
+				}
+			}
+
+			LayerKind::Normalize => {
+				// d/dx of (x - mean) / std is (g - mean(g) - y * mean(g * y)) / std
+				let (_, std) = mean_and_std(inputs);
+				let len = T::from(deltas.len()).unwrap();
+				let mean_delta = deltas.iter().fold(T::zero(), |sum, delta| sum + *delta) / len;
+
+				let mean_product = deltas
+					.iter()
+					.zip(outputs)
+					.fold(T::zero(), |sum, (delta, output)| sum + *delta * *output)
+					/ len;
+
+				for (grad, (delta, output)) in grads.iter_mut().zip(deltas.iter().zip(outputs)) {
+					*grad = (*delta - mean_delta - *output * mean_product) / std;
+				}
+			}
+		}
+
+		grads
+	}
+}
+
+impl<T: Float> Network<T> {
+	/// Returns how much the sum of the network's outputs changes with each
+	/// of `inputs`, using the chain rule through all layers.
+	///
+	/// This is only a local (and, for recurrent layers, approximate)
+	/// measure: their state is treated as a constant.
+	pub fn input_sensitivity(&self, mut inputs: Vec<T>) -> Vec<T> {
+		self.scale_inputs(&mut inputs);
+
+		// Forward pass, starting with the network's (scaled) inputs
+		let mut outputs = vec![inputs];
+		let mut sums = Vec::with_capacity(self.layers.len());
+
+		for layer in &self.layers {
+			let mut inputs = layer.gather_inputs(&outputs);
+			inputs.extend_from_slice(&layer.state);
+
+			let layer_sums = layer.sums(&inputs);
+
+			outputs.push(layer_sums.iter().map(|sum| layer.activate(*sum)).collect());
+			sums.push(layer_sums);
+		}
+
+		// Backward pass; `grads[k]` is the gradient with respect to `outputs[k]`
+		let mut grads: Vec<Vec<T>> = outputs
+			.iter()
+			.map(|outputs| vec![T::zero(); outputs.len()])
+			.collect();
+		grads.last_mut().unwrap().fill(T::one());
+
+		for (l, layer) in self.layers.iter().enumerate().rev() {
+			let input_size = outputs[l].len();
+
+			let deltas: Vec<T> = grads[l + 1]
+				.iter()
+				.zip(&sums[l])
+				.map(|(grad, sum)| *grad * layer.activation_derivative(*sum))
+				.collect();
+
+			let inputs = layer.gather_inputs(&outputs[..=l]);
+			let input_grads = layer.input_gradient(&inputs, &outputs[l + 1], &deltas);
+
+			// Gradients of skipped-over inputs go to the layer they came from
+			for (i, grad) in input_grads.into_iter().enumerate() {
+				if i < input_size {
+					grads[l][i] += grad;
+				} else if let Some(skip) = layer.skip {
+					grads[skip.from][i - input_size] += grad;
+				}
+			}
+		}
+
+		let mut grads = grads.swap_remove(0);
+
+		if let Some(scaler) = &self.input_scaler {
+			for (i, grad) in grads.iter_mut().enumerate() {
+				*grad *= scaler.slope(i);
+			}
+		}
+
+		grads
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use approx::assert_relative_eq;
+
+	#[test]
+	fn activation_sensitivity() {
+		let network: Network = Network::from_weights(
+			&[LayerTopology::new(2), LayerTopology::new(1)],
+			vec![0.5, 2.0, -3.0],
+		);
+		let neuron = &network.layers()[0].neurons()[0];
+
+		// 0.5 + 2.0 * 1.0 - 3.0 * 0.25 > 0
+		assert_eq!(neuron.activation_sensitivity(&[1.0, 0.25], Activation::Relu), vec![2.0, -3.0]);
+
+		// 0.5 + 2.0 * 0.0 - 3.0 * 1.0 < 0
+		assert_eq!(neuron.activation_sensitivity(&[0.0, 1.0], Activation::Relu), vec![0.0, 0.0]);
+
+		let slope = 1.0 - (0.5f32 + 2.0 - 0.75).tanh().powi(2);
+		let tanh = neuron.activation_sensitivity(&[1.0, 0.25], Activation::Tanh);
+		assert_relative_eq!(tanh[0], 2.0 * slope);
+		assert_relative_eq!(tanh[1], -3.0 * slope);
+	}
+
+	#[test]
+	fn input_sensitivity_matches_finite_differences() {
+		let topology = [
+			LayerTopology::new(3),
+			LayerTopology::new(4).with_activation(Activation::Tanh),
+			LayerTopology::new(2).with_skip_from(0),
+		];
+		let weights = (0..LayerTopology::param_count(&topology)).map(|n| ((n * 7 % 11) as f64 - 4.0) / 10.0);
+		let mut network: Network<f64> = Network::from_weights(&topology, weights);
+
+		let inputs = vec![0.3, -0.2, 0.9];
+		let actual = network.input_sensitivity(inputs.clone());
+		assert!(actual.iter().all(|grad| grad.abs() > 0.01));
+
+		for i in 0..inputs.len() {
+			let h = 1e-6;
+			let mut shifted = |delta: f64| {
+				let mut inputs = inputs.clone();
+				inputs[i] += delta;
+				network.propagate(inputs).iter().sum::<f64>()
+			};
+
+			let expected = (shifted(h) - shifted(-h)) / (2.0 * h);
+			assert_relative_eq!(actual[i], expected, epsilon = 1e-6);
+		}
+	}
+
+	#[test]
+	fn input_sensitivity_through_normalize_layer() {
+		let topology = [
+			LayerTopology::new(3),
+			LayerTopology::new(4).with_activation(Activation::Tanh),
+			LayerTopology::new(7).with_kind(LayerKind::Normalize).with_skip_from(0),
+			LayerTopology::new(2).with_activation(Activation::Tanh),
+		];
+		let weights = (0..LayerTopology::param_count(&topology)).map(|n| ((n * 7 % 11) as f64 - 4.0) / 10.0);
+		let mut network: Network<f64> = Network::from_weights(&topology, weights);
+
+		let inputs = vec![0.3, -0.2, 0.9];
+		let actual = network.input_sensitivity(inputs.clone());
+
+		for i in 0..inputs.len() {
+			let h = 1e-6;
+			let mut shifted = |delta: f64| {
+				let mut inputs = inputs.clone();
+				inputs[i] += delta;
+				network.propagate(inputs).iter().sum::<f64>()
+			};
+
+			let expected = (shifted(h) - shifted(-h)) / (2.0 * h);
+			assert_relative_eq!(actual[i], expected, epsilon = 1e-6);
+		}
+	}
+}
@@ -0,0 +1,125 @@
+use crate::*;
+use core::fmt;
+
+impl<T: Float> Network<T> {
+	/// Total number of weights and biases.
+	pub fn param_count(&self) -> usize {
+		self.layers.iter().map(|layer| layer.param_count()).sum()
+	}
+}
+
+impl<T: Float> Layer<T> {
+	/// Number of biases and weights, not counting mirrored ones.
+	pub fn param_count(&self) -> usize {
+		self.params().count()
+	}
+}
+
+/// Prints one line per layer (shape, parameter count and weight statistics),
+/// followed by the network's total parameter count.
+impl<T: Float> fmt::Display for Network<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for (l, layer) in self.layers.iter().enumerate() {
+			write!(
+				f,
+				"Layer {}: {} -> {} ({}",
+				l,
+				layer.input_size(),
+				layer.neurons.len(),
+				layer.activation
+			)?;
+
+			if layer.kind != LayerKind::Dense {
+				write!(f, ", {}", layer.kind)?;
+			}
+
+			if layer.mirrored {
+				write!(f, ", mirrored")?;
+			}
+
+			if let Some(from) = layer.skip_from() {
+				write!(f, ", skip from {}", from)?;
+			}
+
+			write!(f, "), {} params", layer.param_count())?;
+
+			let weights = layer
+				.neurons
+				.iter()
+				.flat_map(|neuron| &neuron.weights)
+				.map(|weight| weight.to_f64().unwrap_or(f64::NAN));
+
+			let (mut min, mut max, mut sum, mut sum_sq, mut count) =
+				(f64::INFINITY, f64::NEG_INFINITY, 0.0, 0.0, 0);
+
+			for weight in weights {
+				min = min.min(weight);
+				max = max.max(weight);
+				sum += weight;
+				sum_sq += weight * weight;
+				count += 1;
+			}
+
+			if count > 0 {
+				let mean = sum / count as f64;
+				let std = (sum_sq / count as f64 - mean * mean).max(0.0).sqrt();
+
+				write!(
+					f,
+					", weights min {:.3} max {:.3} mean {:.3} std {:.3}",
+					min, max, mean, std
+				)?;
+			}
+
+			writeln!(f, ";")?;
+		}
+
+		write!(f, "total {} params", self.param_count())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn display() {
+		let network: Network = Network::from_weights(
+			&[
+				LayerTopology::new(2),
+				LayerTopology::new(2),
+				LayerTopology::new(1).without_bias(),
+			],
+			vec![
+				0.0, 0.5, -0.5, //
+				0.0, 1.0, 1.0, //
+				2.0, -2.0,
+			],
+		);
+
+		assert_eq!(
+			network.to_string(),
+			"Layer 0: 2 -> 2 (ReLU), 6 params, weights min -0.500 max 1.000 mean 0.500 std 0.612;\n\
+			 Layer 1: 2 -> 1 (ReLU), 2 params, weights min -2.000 max 2.000 mean 0.000 std 2.000;\n\
+			 total 8 params"
+		);
+	}
+
+	#[test]
+	fn display_single_layer() {
+		let network = Network::new(vec![Layer::new(
+			vec![Neuron {
+				bias: Some(0.0),
+				weights: vec![1.0, 1.0],
+			}],
+			LayerKind::Recurrent,
+			Activation::Relu,
+		)]);
+
+		assert_eq!(
+			network.to_string(),
+			"Layer 0: 1 -> 1 (ReLU, recurrent), 3 params, weights min 1.000 max 1.000 mean 1.000 std 0.000;\n\
+			 total 3 params"
+		);
+	}
+}
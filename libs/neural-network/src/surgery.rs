@@ -0,0 +1,322 @@
+use crate::*;
+
+// Incoming weights of new neurons are drawn from [-NEW_WEIGHT, NEW_WEIGHT]
+const NEW_WEIGHT: f32 = 0.1;
+
+impl<T: Float> Network<T> {
+	/// Appends a neuron with small random incoming weights to the `layer`-th
+	/// layer; layers that take its outputs get a zero weight for it, so the
+	/// network's outputs don't change.
+	///
+	/// Panics when given the output layer or a layer tied to a normalize
+	/// or mirrored layer.
+	pub fn add_neuron(&mut self, layer: usize, rng: &mut dyn RngCore) {
+		assert!(layer + 1 < self.layers.len(), "can't resize the output layer");
+		assert!(!self.is_fixed_size(layer), "can't resize a layer tied to a normalize or mirrored layer");
+
+		let new_weight = T::from(NEW_WEIGHT).unwrap();
+		let target = &mut self.layers[layer];
+		let size = target.output_size();
+		target.push_neuron(|| rng.gen_range(-new_weight..=new_weight));
+
+		self.for_each_downstream_weights(layer, |weights, i| weights.insert(i + size, T::zero()));
+	}
+
+	/// Removes the `neuron`-th neuron of the `layer`-th layer, along with the
+	/// weights other layers had for its output.
+	///
+	/// Panics when given the output layer, a layer tied to a normalize or
+	/// mirrored layer or the only neuron of a layer.
+	pub fn remove_neuron(&mut self, layer: usize, neuron: usize) {
+		assert!(layer + 1 < self.layers.len(), "can't resize the output layer");
+		assert!(!self.is_fixed_size(layer), "can't resize a layer tied to a normalize or mirrored layer");
+
+		let target = &mut self.layers[layer];
+		let size = target.output_size();
+
+		assert!(neuron < size);
+		assert!(size > 1, "can't remove the only neuron of a layer");
+
+		target.remove_neuron(neuron);
+
+		self.for_each_downstream_weights(layer, |weights, i| {
+			weights.remove(i + neuron);
+		});
+	}
+
+	/// Inserts a dense layer of `neuron_count` neurons right after the
+	/// `after_layer`-th layer; the layer that used to come next gets new,
+	/// random weights for its inputs (its skip connections are kept).
+	///
+	/// Unlike `add_neuron()`, this does change the network's outputs.
+	///
+	/// Panics when given the output layer or when the next layer is a
+	/// normalize or mirrored one.
+	pub fn add_layer(&mut self, rng: &mut dyn RngCore, after_layer: usize, neuron_count: usize) {
+		assert!(after_layer + 1 < self.layers.len(), "can't add a layer after the output layer");
+		assert!(neuron_count > 0, "got no neurons");
+
+		let next = &self.layers[after_layer + 1];
+		assert!(next.is_resizable(), "can't change the inputs of a normalize or mirrored layer");
+
+		let input_size = self.layers[after_layer].output_size();
+		let output_size = next.output_size();
+
+		let layer = Layer::random(
+			rng,
+			input_size,
+			None,
+			&LayerTopology::new(neuron_count),
+			Init::default().range(input_size, neuron_count),
+		);
+
+		let range = Init::default().range(neuron_count, output_size);
+
+		for neuron in &mut self.layers[after_layer + 1].neurons {
+			neuron.weights.splice(
+				..input_size,
+				(0..neuron_count).map(|_| rng.gen_range(range.clone())),
+			);
+		}
+
+		// Outputs of the layers past the new one have moved one further
+		for skip in self.layers.iter_mut().filter_map(|layer| layer.skip.as_mut()) {
+			if skip.from > after_layer + 1 {
+				skip.from += 1;
+			}
+		}
+
+		self.layers.insert(after_layer + 1, layer);
+	}
+
+	// Layers that can't be resized tie the size of the layers feeding them
+	// too
+	fn is_fixed_size(&self, layer: usize) -> bool {
+		!self.layers[layer].is_resizable()
+			|| self.layers[layer + 1..].iter().enumerate().any(|(m, downstream)| {
+				!downstream.is_resizable() && (m == 0 || downstream.skip_from() == Some(layer + 1))
+			})
+	}
+
+	// Calls `f` with the weights of every neuron that gets the `layer`-th
+	// layer's outputs, along with the index of the first such weight; skip
+	// connections get their sizes updated to the layer's current size first.
+	fn for_each_downstream_weights(&mut self, layer: usize, mut f: impl FnMut(&mut Vec<T>, usize)) {
+		let size = self.layers[layer].neurons.len();
+
+		for m in layer + 1..self.layers.len() {
+			let input_size = self.layers[m - 1].neurons.len();
+			let downstream = &mut self.layers[m];
+
+			if m == layer + 1 {
+				for neuron in &mut downstream.neurons {
+					f(&mut neuron.weights, 0);
+				}
+			}
+
+			if let Some(skip) = downstream.skip.as_mut().filter(|skip| skip.from == layer + 1) {
+				skip.size = size;
+
+				for neuron in &mut downstream.neurons {
+					f(&mut neuron.weights, input_size);
+				}
+			}
+		}
+	}
+}
+
+impl<T: Float> Layer<T> {
+	// Appends a neuron whose bias (when the layer has them) and incoming
+	// weights come from `new_weight`; recurrent layers get a zero weight for
+	// its previous output too
+	fn push_neuron(&mut self, mut new_weight: impl FnMut() -> T) {
+		let has_bias = self.neurons[0].has_bias();
+
+		if self.kind == LayerKind::Recurrent {
+			for neuron in &mut self.neurons {
+				neuron.weights.push(T::zero());
+			}
+
+			self.state.push(T::zero());
+		}
+
+		let fan_in = self.neurons[0].weights.len();
+
+		self.neurons.push(Neuron {
+			bias: has_bias.then(&mut new_weight),
+			weights: (0..fan_in).map(|_| new_weight()).collect(),
+		});
+	}
+
+	// Removes the `neuron`-th neuron, along with the weights recurrent
+	// layers have for its previous output
+	fn remove_neuron(&mut self, neuron: usize) {
+		if self.kind == LayerKind::Recurrent {
+			let state_start = self.neurons[0].weights.len() - self.output_size();
+
+			for other in &mut self.neurons {
+				other.weights.remove(state_start + neuron);
+			}
+
+			self.state.remove(neuron);
+		}
+
+		self.neurons.remove(neuron);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use approx::assert_relative_eq;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	// Same network every time
+	fn network() -> Network {
+		Network::random(
+			&mut ChaCha8Rng::from_seed(Default::default()),
+			&[
+				LayerTopology::new(3),
+				LayerTopology::new(4).with_kind(LayerKind::Recurrent),
+				LayerTopology::new(5).without_bias(),
+				LayerTopology::new(2).with_skip_from(1),
+			],
+		)
+	}
+
+	fn assert_consistent(network: Network) {
+		assert!(Network::try_new(network.into_layers()).is_ok());
+	}
+
+	#[test]
+	fn add_neuron_keeps_outputs() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let inputs = vec![0.5, -0.25, 1.0];
+
+		for layer in 0..2 {
+			let mut original = network();
+			let mut grown = network();
+			grown.add_neuron(layer, &mut rng);
+
+			assert_eq!(grown.layers()[layer].neurons().len(), original.layers()[layer].neurons().len() + 1);
+			assert_ne!(grown.layers()[layer].neurons().last().unwrap().weights()[0], 0.0);
+
+			// Twice, so that recurrent layers get to use their state
+			for _ in 0..2 {
+				let expected = original.propagate(inputs.clone());
+				let actual = grown.propagate(inputs.clone());
+
+				assert_relative_eq!(actual.as_slice(), expected.as_slice(), epsilon = 1e-6);
+			}
+
+			assert_consistent(grown);
+		}
+	}
+
+	#[test]
+	fn remove_neuron_undoes_add_neuron() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+		for layer in 0..2 {
+			let mut network = network();
+			let size = network.layers()[layer].neurons().len();
+
+			network.add_neuron(layer, &mut rng);
+			network.remove_neuron(layer, size);
+
+			assert_eq!(network, self::network());
+		}
+	}
+
+	#[test]
+	fn remove_neuron() {
+		let mut network = network();
+
+		network.remove_neuron(1, 2);
+		network.remove_neuron(0, 0);
+
+		let shape: Vec<_> = network.layers().iter().map(|layer| layer.neurons().len()).collect();
+		assert_eq!(shape, vec![3, 4, 2]);
+		assert_eq!(network.propagate(vec![0.5, -0.25, 1.0]).len(), 2);
+
+		assert_consistent(network);
+	}
+
+	#[test]
+	#[should_panic(expected = "can't resize the output layer")]
+	fn remove_neuron_rejects_output_layer() {
+		network().remove_neuron(2, 0);
+	}
+
+	fn shape(network: &Network) -> Vec<usize> {
+		let mut shape = vec![network.layers()[0].input_size()];
+		shape.extend(network.layers().iter().map(|layer| layer.neurons().len()));
+		shape
+	}
+
+	#[test]
+	fn add_layer() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let topology = [LayerTopology::new(3), LayerTopology::new(4), LayerTopology::new(2)];
+		let mut network: Network = Network::random(&mut rng, &topology);
+		let param_count = network.param_count();
+
+		network.add_layer(&mut rng, 0, 6);
+
+		assert_eq!(shape(&network), vec![3, 4, 6, 2]);
+		assert_eq!(network.param_count(), param_count + 6 * (1 + 4) + 2 * (6 - 4));
+
+		let outputs = network.propagate(vec![0.5, -0.25, 1.0]);
+		assert_eq!(outputs.len(), 2);
+		assert!(outputs.iter().all(|output| output.is_finite()));
+
+		assert_consistent(network);
+	}
+
+	#[test]
+	fn add_layer_keeps_skip_connections() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+		for after_layer in 0..2 {
+			let mut network = network();
+			network.add_layer(&mut rng, after_layer, 7);
+
+			assert_eq!(network.layers()[after_layer + 1].neurons().len(), 7);
+			assert_eq!(network.layers()[3].skip_from(), Some(1));
+			assert_eq!(network.propagate(vec![0.5, -0.25, 1.0]).len(), 2);
+
+			assert_consistent(network);
+		}
+
+		let mut network: Network = Network::random(
+			&mut rng,
+			&[
+				LayerTopology::new(3),
+				LayerTopology::new(4),
+				LayerTopology::new(5),
+				LayerTopology::new(2).with_skip_from(2),
+			],
+		);
+		network.add_layer(&mut rng, 0, 6);
+
+		assert_eq!(network.layers()[3].skip_from(), Some(3));
+		assert_consistent(network);
+	}
+
+	#[test]
+	#[should_panic(expected = "can't add a layer after the output layer")]
+	fn add_layer_rejects_output_layer() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+		network().add_layer(&mut rng, 2, 3);
+	}
+
+	#[test]
+	#[should_panic(expected = "can't resize the output layer")]
+	fn add_neuron_rejects_output_layer() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+		network().add_neuron(2, &mut rng);
+	}
+}
@@ -0,0 +1,107 @@
+use crate::*;
+
+/// Everything computed during a single propagation, see
+/// `Network::propagate_traced()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropagationTrace<T = f32> {
+	inputs: Vec<T>,
+	// One entry per layer
+	sums: Vec<Vec<T>>,
+	activations: Vec<Vec<T>>,
+}
+
+impl<T: Float> PropagationTrace<T> {
+	/// The network's inputs, after going through its input scaler (if any).
+	pub fn inputs(&self) -> &[T] {
+		&self.inputs
+	}
+
+	pub fn layer_count(&self) -> usize {
+		self.sums.len()
+	}
+
+	/// Weighted sums of the `layer`-th layer's neurons, before the
+	/// activation is applied.
+	pub fn sums(&self, layer: usize) -> &[T] {
+		&self.sums[layer]
+	}
+
+	/// Outputs of the `layer`-th layer's neurons.
+	pub fn activations(&self, layer: usize) -> &[T] {
+		&self.activations[layer]
+	}
+
+	/// The network's outputs, i.e. the last layer's activations.
+	pub fn outputs(&self) -> &[T] {
+		self.activations.last().unwrap()
+	}
+}
+
+impl<T: Float> Network<T> {
+	/// Same as `propagate()`, but keeps every layer's intermediate results.
+	///
+	/// Recurrent layers see their current state, but don't update it.
+	pub fn propagate_traced(&self, mut inputs: Vec<T>) -> PropagationTrace<T> {
+		self.scale_inputs(&mut inputs);
+
+		// Outputs of every layer so far, starting with the network's inputs
+		let mut outputs = vec![inputs];
+		let mut sums = Vec::with_capacity(self.layers.len());
+
+		for layer in &self.layers {
+			let mut inputs = layer.gather_inputs(&outputs);
+			inputs.extend_from_slice(&layer.state);
+
+			let layer_sums = layer.sums(&inputs);
+
+			outputs.push(layer_sums.iter().map(|sum| layer.activate(*sum)).collect());
+			sums.push(layer_sums);
+		}
+
+		let inputs = outputs.remove(0);
+
+		PropagationTrace {
+			inputs,
+			sums,
+			activations: outputs,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	#[test]
+	fn propagate_traced() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network: Network = Network::random(
+			&mut rng,
+			&[
+				LayerTopology::new(3),
+				LayerTopology::new(5),
+				LayerTopology::new(4).with_activation(Activation::Tanh),
+				LayerTopology::new(2).with_skip_from(1),
+			],
+		);
+		let inputs = vec![0.5, -0.25, 1.0];
+
+		let trace = network.propagate_traced(inputs.clone());
+
+		assert_eq!(trace.inputs(), inputs.as_slice());
+		assert_eq!(trace.layer_count(), 3);
+		assert_eq!(trace.activations(1).len(), 4);
+		assert_eq!(trace.outputs(), network.propagate(inputs).as_slice());
+
+		for (l, layer) in network.layers().iter().enumerate() {
+			for (sum, activation) in trace.sums(l).iter().zip(trace.activations(l)) {
+				assert_eq!(layer.activation().apply(*sum), *activation);
+			}
+		}
+
+		// ReLU zeroes negative sums
+		assert!(trace.sums(0).iter().any(|sum| *sum < 0.0));
+	}
+}
@@ -0,0 +1,187 @@
+use crate::*;
+
+/// Supervised training via plain (per-sample) stochastic gradient descent on
+/// the mean squared error, e.g. to pretrain a brain before evolving it.
+#[derive(Clone, Debug)]
+pub struct Trainer<T = f32> {
+	learning_rate: T,
+}
+
+impl<T: Float> Trainer<T> {
+	pub fn new(learning_rate: T) -> Self {
+		assert!(learning_rate > T::zero());
+
+		Self { learning_rate }
+	}
+
+	/// Runs `epochs` passes over `samples` (pairs of inputs and expected
+	/// outputs) and returns the mean loss of the last one.
+	///
+	/// Only dense layers can be trained; other kinds (and mirrored layers)
+	/// cause a panic.
+	pub fn fit(&self, network: &mut Network<T>, samples: &[(Vec<T>, Vec<T>)], epochs: usize) -> T {
+		if let Some(layer) = network.layers.iter().find(|layer| layer.kind != LayerKind::Dense) {
+			panic!("got a {} layer", layer.kind);
+		}
+
+		assert!(!network.has_skip_connections(), "got a skip connection");
+		assert!(!network.layers.iter().any(|layer| layer.mirrored), "got a mirrored layer");
+
+		let mut loss = T::zero();
+
+		for _ in 0..epochs {
+			loss = T::zero();
+
+			for (inputs, targets) in samples {
+				loss += self.step(network, inputs, targets);
+			}
+
+			loss /= T::from(samples.len().max(1)).unwrap();
+		}
+
+		loss
+	}
+
+	// Updates the network on a single sample, returning its loss from
+	// before the update
+	fn step(&self, network: &mut Network<T>, inputs: &[T], targets: &[T]) -> T {
+		// Forward pass, remembering each layer's inputs and pre-activations
+		let mut activations = vec![inputs.to_vec()];
+		network.scale_inputs(&mut activations[0]);
+		let mut pre_activations = Vec::with_capacity(network.layers.len());
+
+		for layer in &network.layers {
+			let inputs = activations.last().unwrap();
+
+			let sums: Vec<T> = layer
+				.neurons
+				.iter()
+				.map(|neuron| neuron.weighted_sum(inputs))
+				.collect();
+
+			activations.push(sums.iter().map(|sum| layer.activation.apply(*sum)).collect());
+			pre_activations.push(sums);
+		}
+
+		let outputs = activations.last().unwrap();
+		assert_eq!(outputs.len(), targets.len(), "got targets of the wrong length");
+
+		let len = T::from(targets.len()).unwrap();
+		let two = T::one() + T::one();
+
+		let loss = outputs
+			.iter()
+			.zip(targets)
+			.fold(T::zero(), |loss, (output, target)| loss + (*output - *target).powi(2))
+			/ len;
+
+		// Backward pass; `deltas` starts as the gradient of the loss with
+		// respect to the layer's outputs
+		let mut deltas: Vec<T> = outputs
+			.iter()
+			.zip(targets)
+			.map(|(output, target)| two * (*output - *target) / len)
+			.collect();
+
+		for (l, layer) in network.layers.iter_mut().enumerate().rev() {
+			for (delta, sum) in deltas.iter_mut().zip(&pre_activations[l]) {
+				*delta *= layer.activation.derivative(*sum);
+			}
+
+			let inputs = &activations[l];
+			let mut input_deltas = vec![T::zero(); inputs.len()];
+
+			for (neuron, delta) in layer.neurons.iter_mut().zip(&deltas) {
+				if let Some(bias) = &mut neuron.bias {
+					*bias -= self.learning_rate * *delta;
+				}
+
+				for ((weight, input), input_delta) in
+					neuron.weights.iter_mut().zip(inputs).zip(&mut input_deltas)
+				{
+					*input_delta += *weight * *delta;
+					*weight -= self.learning_rate * *delta * *input;
+				}
+			}
+
+			deltas = input_deltas;
+		}
+
+		loss
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand_chacha::ChaCha8Rng;
+
+	fn xor() -> Vec<(Vec<f32>, Vec<f32>)> {
+		vec![
+			(vec![0.0, 0.0], vec![0.0]),
+			(vec![0.0, 1.0], vec![1.0]),
+			(vec![1.0, 0.0], vec![1.0]),
+			(vec![1.0, 1.0], vec![0.0]),
+		]
+	}
+
+	#[test]
+	fn fits_xor() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network: Network = Network::random_with(
+			&mut rng,
+			&[
+				LayerTopology::new(2),
+				LayerTopology::new(4).with_activation(Activation::Tanh),
+				LayerTopology::new(1).with_activation(Activation::Tanh),
+			],
+			Init::Xavier,
+		);
+
+		let loss = Trainer::new(0.1).fit(&mut network, &xor(), 2000);
+		assert!(loss < 0.01, "loss = {}", loss);
+
+		for (inputs, targets) in xor() {
+			let output = network.propagate(inputs)[0];
+			assert!((output - targets[0]).abs() < 0.2, "output = {}", output);
+		}
+	}
+
+	#[test]
+	fn fits_linear_function_with_relu() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network: Network = Network::random(
+			&mut rng,
+			&[LayerTopology::new(2), LayerTopology::new(1)],
+		);
+		let samples: Vec<_> = (0..10)
+			.map(|n| {
+				let (x0, x1) = (n as f32 / 10.0, (n % 3) as f32 / 3.0);
+				(vec![x0, x1], vec![0.5 * x0 + 0.25 * x1 + 0.1])
+			})
+			.collect();
+		let trainer = Trainer::new(0.1);
+
+		let before = trainer.fit(&mut network, &samples, 1);
+		let after = trainer.fit(&mut network, &samples, 1000);
+
+		assert!(after < before);
+		assert!(after < 1e-4, "loss = {}", after);
+	}
+
+	#[test]
+	#[should_panic(expected = "got a recurrent layer")]
+	fn rejects_recurrent_layers() {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let mut network: Network = Network::random(
+			&mut rng,
+			&[
+				LayerTopology::new(2),
+				LayerTopology::new(1).with_kind(LayerKind::Recurrent),
+			],
+		);
+
+		Trainer::new(0.1).fit(&mut network, &xor(), 1);
+	}
+}
@@ -1,10 +1,11 @@
 use lib_simulation as sim;
 use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub struct Simulation {
-	rng: ThreadRng,
+	rng: ChaCha8Rng,
 	sim: sim::Simulation,
 }
 
@@ -12,35 +13,152 @@ pub struct Simulation {
 impl Simulation {
 	#[wasm_bindgen(constructor)]
 	pub fn new() -> Self {
-		let mut rng = thread_rng();
-		let sim = sim::Simulation::random(&mut rng);
+		let rng = ChaCha8Rng::from_rng(thread_rng()).expect("failed to seed the rng");
 
-		Self { rng, sim }
+		Self::with_rng(rng)
+	}
+
+	// JavaScript numbers can't carry a full u64, so the seed is a u32 here
+	pub fn from_seed(seed: u32) -> Self {
+		Self::with_rng(ChaCha8Rng::seed_from_u64(seed as u64))
+	}
+
+	pub fn with_config(config: &SimulationConfig) -> Self {
+		let rng = ChaCha8Rng::from_rng(thread_rng()).expect("failed to seed the rng");
+
+		Self::with_rng_and_config(rng, config.into())
+	}
+
+	pub fn from_seed_with_config(seed: u32, config: &SimulationConfig) -> Self {
+		Self::with_rng_and_config(ChaCha8Rng::seed_from_u64(seed as u64), config.into())
 	}
 
 	pub fn world(&self) -> World {
 		World::from(self.sim.world())
 	}
 
-	pub fn step(&mut self) {
-		self.sim.step(&mut self.rng);
+	/// `[x, y, rotation]` of every animal, as a `Float32Array`.
+	pub fn animal_position_buffer(&self) -> Vec<f32> {
+		self.sim.animal_position_buffer()
+	}
+
+	/// `[x, y]` of every food, as a `Float32Array`.
+	pub fn food_position_buffer(&self) -> Vec<f32> {
+		self.sim.food_position_buffer()
+	}
+
+	/// Returns the statistics of the generation that has just ended, if
+	/// this step ended one.
+	pub fn step(&mut self) -> Option<GenerationStats> {
+		self.sim.step(&mut self.rng).as_ref().map(GenerationStats::from)
+	}
+
+	/// Fast-forwards to the next generation and returns the statistics of
+	/// the one that has just ended.
+	pub fn train(&mut self) -> GenerationStats {
+		GenerationStats::from(&self.sim.train(&mut self.rng))
 	}
 
 	pub fn generation(&self) -> usize {
 		self.sim.generation()
 	}
 
+	pub fn population_size(&self) -> usize {
+		self.sim.population_size()
+	}
+
+	pub fn food_count(&self) -> usize {
+		self.sim.food_count()
+	}
+
+	pub fn total_food_eaten(&self) -> usize {
+		self.sim.total_food_eaten()
+	}
+
+	pub fn foods_eaten_this_generation(&self) -> usize {
+		self.sim.foods_eaten_this_generation()
+	}
+
+	pub fn world_stats(&self) -> WorldStats {
+		let animals = self.sim.world().animals();
+		let total: usize = animals.iter().map(|animal| animal.fitness()).sum();
+		let best = animals.iter().map(|animal| animal.fitness()).max().unwrap_or(0);
+
+		WorldStats {
+			animal_count: animals.len(),
+			food_count: self.sim.world().food().len(),
+			best_satiation: best as u32,
+			mean_satiation: total as f32 / animals.len().max(1) as f32,
+			age: self.sim.age(),
+			generation: self.sim.generation(),
+		}
+	}
+
+	/// Weights of the `index`-th animal's brain, e.g. to render them as
+	/// heatmaps; panics if there's no such animal.
+	pub fn brain_weights(&self, index: usize) -> BrainWeights {
+		BrainWeights::from(self.sim.world().animals()[index].brain())
+	}
+
 	pub fn is_last_run(&self) -> bool{
 		self.sim.is_last_run()
 	}
 }
 
+impl Simulation {
+	fn with_rng(rng: ChaCha8Rng) -> Self {
+		Self::with_rng_and_config(rng, sim::SimulationConfig::default())
+	}
+
+	fn with_rng_and_config(mut rng: ChaCha8Rng, config: sim::SimulationConfig) -> Self {
+		let sim = sim::Simulation::new(config, &mut rng);
+
+		Self { rng, sim }
+	}
+}
+
+impl Default for Simulation {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl From<&SimulationConfig> for sim::SimulationConfig {
+	fn from(config: &SimulationConfig) -> Self {
+		Self {
+			animal_count: config.animal_count,
+			food_count: config.food_count,
+			poison_ratio: config.poison_ratio,
+			predator_count: config.predator_count,
+			..Default::default()
+		}
+	}
+}
+
+impl From<&sim::SimulationStats> for GenerationStats {
+	fn from(stats: &sim::SimulationStats) -> Self {
+		Self {
+			generation: stats.generation,
+			min_fitness: stats.min_fitness,
+			max_fitness: stats.max_fitness,
+			mean_fitness: stats.mean_fitness,
+			median_fitness: stats.median_fitness,
+			best_satiation: stats.best_satiation,
+		}
+	}
+}
+
 impl From<&sim::World> for World {
 	fn from(world: &sim::World) -> Self {
 		let animals = world.animals().iter().map(Animal::from).collect();
 		let foods = world.food().iter().map(Food::from).collect();
+		let predators = world.predators().iter().map(Animal::from).collect();
 
-		Self { animals, foods }
+		Self {
+			animals,
+			foods,
+			predators,
+		}
 	}
 }
 
@@ -51,6 +169,23 @@ impl From<&sim::Animal> for Animal {
 			y: animal.position().y,
 			rotation: animal.rotation().angle(),
 			fitness: animal.fitness(),
+			age: animal.age(),
+			energy: animal.energy(),
+			alive: animal.is_alive(),
+		}
+	}
+}
+
+impl From<&sim::Brain> for BrainWeights {
+	fn from(brain: &sim::Brain) -> Self {
+		let matrices = brain.weight_matrices();
+
+		Self {
+			shapes: matrices
+				.iter()
+				.flat_map(|matrix| [matrix.len(), matrix.first().map_or(0, Vec::len)])
+				.collect(),
+			weights: matrices.into_iter().flatten().flatten().collect(),
 		}
 	}
 }
@@ -59,10 +194,43 @@ impl From<&sim::Food> for Food {
 	fn from(food: &sim::Food) -> Self {
 		Self {
 			x: food.position().x,
-			y: food.position().y
+			y: food.position().y,
+			poison: food.kind() == sim::FoodKind::Poison,
+		}
+	}
+}
+
+/// The parts of the simulation's configuration JavaScript can change.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct SimulationConfig {
+	pub animal_count: usize,
+	pub food_count: usize,
+	pub poison_ratio: f32,
+	pub predator_count: usize,
+}
+
+#[wasm_bindgen]
+impl SimulationConfig {
+	#[wasm_bindgen(constructor)]
+	pub fn new() -> Self {
+		let config = sim::SimulationConfig::default();
+
+		Self {
+			animal_count: config.animal_count,
+			food_count: config.food_count,
+			poison_ratio: config.poison_ratio,
+			predator_count: config.predator_count,
 		}
 	}
 }
+
+impl Default for SimulationConfig {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
 #[wasm_bindgen]
 #[derive(Clone, Debug)]
 pub struct World {
@@ -70,6 +238,8 @@ pub struct World {
 	pub animals: Vec<Animal>,
 	#[wasm_bindgen(getter_with_clone)]
 	pub foods: Vec<Food>,
+	#[wasm_bindgen(getter_with_clone)]
+	pub predators: Vec<Animal>,
 }
 
 #[wasm_bindgen]
@@ -78,7 +248,10 @@ pub struct Animal {
 	pub x: f32,
 	pub y: f32,
 	pub rotation: f32,
-	pub fitness: usize
+	pub fitness: usize,
+	pub age: usize,
+	pub energy: f32,
+	pub alive: bool,
 }
 
 #[wasm_bindgen]
@@ -86,4 +259,127 @@ pub struct Animal {
 pub struct Food {
 	pub x: f32,
 	pub y: f32,
+	pub poison: bool,
+}
+
+/// Weight matrices of a brain, flattened row by row into one buffer.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct BrainWeights {
+	/// Every layer's matrix, one after another, as a `Float32Array`.
+	#[wasm_bindgen(getter_with_clone)]
+	pub weights: Vec<f32>,
+	/// `[rows, columns]` of every layer's matrix, i.e. its neurons and the
+	/// weights each of them has.
+	#[wasm_bindgen(getter_with_clone)]
+	pub shapes: Vec<usize>,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Debug, Copy)]
+pub struct WorldStats {
+	pub animal_count: usize,
+	pub food_count: usize,
+	pub best_satiation: u32,
+	pub mean_satiation: f32,
+	pub age: usize,
+	pub generation: usize,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Debug, Copy)]
+pub struct GenerationStats {
+	pub generation: usize,
+	pub min_fitness: f32,
+	pub max_fitness: f32,
+	pub mean_fitness: f32,
+	pub median_fitness: f32,
+	pub best_satiation: usize,
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+	use super::*;
+	use wasm_bindgen_test::*;
+
+	#[wasm_bindgen_test]
+	fn world_stats() {
+		let mut simulation = Simulation::from_seed(42);
+
+		for _ in 0..300 {
+			simulation.step();
+		}
+
+		let stats = simulation.world_stats();
+		let world = simulation.world();
+		let fitness: Vec<_> = world.animals.iter().map(|animal| animal.fitness).collect();
+
+		assert_eq!(stats.animal_count, world.animals.len());
+		assert_eq!(stats.food_count, world.foods.len());
+		assert_eq!(stats.best_satiation as usize, *fitness.iter().max().unwrap());
+		assert_eq!(
+			stats.mean_satiation,
+			fitness.iter().sum::<usize>() as f32 / fitness.len() as f32
+		);
+		assert_eq!(stats.age, 300);
+		assert_eq!(stats.generation, simulation.generation());
+
+		let satiation: usize = fitness.iter().sum();
+		assert_eq!(simulation.foods_eaten_this_generation(), satiation);
+		assert_eq!(simulation.total_food_eaten(), satiation);
+	}
+
+	#[wasm_bindgen_test]
+	fn train() {
+		let mut simulation = Simulation::from_seed(42);
+
+		let stats = simulation.train();
+
+		assert_eq!(stats.generation, 1);
+		assert_eq!(simulation.generation(), 2);
+		assert!(stats.min_fitness <= stats.mean_fitness);
+		assert!(stats.mean_fitness <= stats.max_fitness);
+	}
+
+	#[wasm_bindgen_test]
+	fn with_config() {
+		let mut config = SimulationConfig::new();
+		config.animal_count = 7;
+		config.food_count = 15;
+		config.poison_ratio = 0.2;
+		config.predator_count = 2;
+
+		let simulation = Simulation::from_seed_with_config(42, &config);
+		let world = simulation.world();
+
+		assert_eq!(world.animals.len(), 7);
+		assert_eq!(world.foods.len(), 15);
+		assert_eq!(simulation.population_size(), 7);
+		assert_eq!(simulation.food_count(), 15);
+		assert_eq!(world.foods.iter().filter(|food| food.poison).count(), 3);
+		assert_eq!(world.predators.len(), 2);
+	}
+
+	#[wasm_bindgen_test]
+	fn position_buffers() {
+		let simulation = Simulation::from_seed(42);
+		let world = simulation.world();
+		let animals = simulation.animal_position_buffer();
+		let foods = simulation.food_position_buffer();
+
+		assert_eq!(animals.len(), 3 * world.animals.len());
+		assert_eq!(foods.len(), 2 * world.foods.len());
+		assert_eq!(animals[3..6], [world.animals[1].x, world.animals[1].y, world.animals[1].rotation]);
+		assert_eq!(foods[2..4], [world.foods[1].x, world.foods[1].y]);
+	}
+
+	#[wasm_bindgen_test]
+	fn brain_weights() {
+		let simulation = Simulation::from_seed(42);
+		let brain = simulation.brain_weights(1);
+		let sizes: usize = brain.shapes.chunks(2).map(|shape| shape[0] * shape[1]).sum();
+
+		assert_eq!(brain.shapes.len(), 4);
+		assert_eq!(brain.weights.len(), sizes);
+	}
 }
\ No newline at end of file
@@ -0,0 +1,128 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use lib_simulation::{Eye, Food, Simulation, SimulationConfig, SpatialGrid};
+use nalgebra as na;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+fn process_vision(c: &mut Criterion) {
+	let mut group = c.benchmark_group("process_vision");
+	let eye = Eye::default();
+
+	for food_count in [10, 100, 1000] {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let foods: Vec<_> = (0..food_count).map(|_| Food::random(&mut rng)).collect();
+
+		group.bench_with_input(BenchmarkId::from_parameter(food_count), &foods, |b, foods| {
+			b.iter(|| {
+				eye.process_vision(
+					na::Point2::new(0.5, 0.5),
+					na::Rotation2::new(0.0),
+					black_box(foods),
+				)
+			})
+		});
+	}
+
+	group.finish();
+}
+
+// Same as `process_vision`, but with the foods bucketed the way the
+// simulation does it; building the grid is shared by every animal of a step,
+// so it's left out
+fn process_vision_with_grid(c: &mut Criterion) {
+	let mut group = c.benchmark_group("process_vision_with_grid");
+	let eye = Eye::default();
+
+	for food_count in [10, 100, 1000] {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let foods: Vec<_> = (0..food_count).map(|_| Food::random(&mut rng)).collect();
+		let grid = SpatialGrid::new(eye.config().fov_range, foods.iter().map(Food::position));
+
+		group.bench_with_input(BenchmarkId::from_parameter(food_count), &foods, |b, foods| {
+			b.iter(|| {
+				eye.process_vision_with_grid(
+					na::Point2::new(0.5, 0.5),
+					na::Rotation2::new(0.0),
+					black_box(foods),
+					&grid,
+				)
+			})
+		});
+	}
+
+	group.finish();
+}
+
+// Finding the foods an animal can eat, i.e. the ones within the collision
+// radius, among 1000 of them: checking every one versus the grid
+fn query_circle(c: &mut Criterion) {
+	let mut group = c.benchmark_group("query_circle");
+	let mut rng = ChaCha8Rng::from_seed(Default::default());
+	let points: Vec<na::Point2<f32>> = (0..1000).map(|_| rng.gen()).collect();
+	let center = na::Point2::new(0.5, 0.5);
+	let radius = 0.01;
+
+	group.bench_function("brute_force", |b| {
+		b.iter(|| {
+			(0..points.len())
+				.filter(|&index| na::distance(&points[index], black_box(&center)) < radius)
+				.collect::<Vec<_>>()
+		})
+	});
+
+	let grid = SpatialGrid::new(0.25, points.iter().copied());
+
+	group.bench_function("grid", |b| b.iter(|| grid.query_circle(black_box(center), radius)));
+	group.finish();
+}
+
+fn step(c: &mut Criterion) {
+	let mut group = c.benchmark_group("step");
+
+	// The world size stays fixed (40 animals, 40 foods), so the sweep goes
+	// over the number of steps taken per iteration instead
+	for steps in [1, 10, 100] {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let config = SimulationConfig {
+			animal_count: 40,
+			food_count: 40,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		group.bench_with_input(BenchmarkId::from_parameter(steps), &steps, |b, &steps| {
+			b.iter(|| {
+				for _ in 0..steps {
+					simulation.step(&mut rng);
+				}
+			})
+		});
+	}
+
+	group.finish();
+}
+
+// A single step of a crowded world, where the foods get looked up by
+// every animal
+fn step_crowded(c: &mut Criterion) {
+	let mut group = c.benchmark_group("step_crowded");
+
+	for food_count in [100, 1000] {
+		let mut rng = ChaCha8Rng::from_seed(Default::default());
+		let config = SimulationConfig {
+			animal_count: 200,
+			food_count,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		group.bench_function(BenchmarkId::from_parameter(food_count), |b| {
+			b.iter(|| simulation.step(&mut rng))
+		});
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, process_vision, process_vision_with_grid, query_circle, step, step_crowded);
+criterion_main!(benches);
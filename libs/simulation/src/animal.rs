@@ -1,6 +1,7 @@
 use lib_genetic_algorithm::Chromosome;
 
 use crate::*;
+use std::f32::consts::PI;
 
 #[derive(Debug)]
 pub struct Animal {
@@ -10,26 +11,109 @@ pub struct Animal {
 	pub(crate) eye: Eye,
 	pub(crate) brain: brain::Brain,
 	// Number of foods
-	pub(crate) satiation: usize
+	pub(crate) satiation: usize,
+	// Number of generations its genes have survived unchanged
+	pub(crate) age: usize,
+	// Since it was born, in units of the map's width
+	pub(crate) distance_traveled: f32,
+	// Share of a full stomach left; the animal starves once it runs out
+	pub(crate) energy: f32,
+	// Number of steps it has been alive for, since it was born
+	pub(crate) steps_alive: usize,
+	// Number of times a predator has caught it
+	pub(crate) times_caught: usize,
 }
 
 impl Animal {
 	pub fn random(rng: &mut dyn RngCore) -> Self {
-		let eye = Eye::default();
+		Self::random_with_eye(rng, Eye::default())
+	}
+
+	pub(crate) fn random_with_eye(rng: &mut dyn RngCore, eye: Eye) -> Self {
 		let brain = Brain::random(rng, &eye);
 		Self::new(eye, brain, rng)
 	}
 
 	pub(crate) fn from_chromosome(
 		chromosome: ga::Chromosome,
+		eye: Eye,
 		rng: &mut dyn RngCore,
 	) -> Self {
-		let eye = Eye::default();
 		let brain = Brain::from_chromosome(chromosome, &eye);
 
 		Self::new(eye, brain, rng)
 	}
 
+	/// Returns a newborn animal with the same eyes and brain, placed at a
+	/// random position and rotation.
+	pub fn clone_with_brain(&self, rng: &mut dyn RngCore) -> Animal {
+		Self::from_chromosome(self.as_chromosome(), self.eye.clone(), rng)
+	}
+
+	// Changes speed and rotation by up to the config's accelerations
+	pub(crate) fn accelerate(&mut self, config: &SimulationConfig, speed: f32, rotation: f32) {
+		if !self.is_alive() {
+			return;
+		}
+
+		let speed = speed.clamp(-config.speed_accel, config.speed_accel);
+		let rotation = rotation.clamp(-config.rotation_accel, config.rotation_accel);
+
+		self.speed = (self.speed + speed).clamp(config.speed_min, config.speed_max);
+		self.rotation = na::Rotation2::new(self.rotation.angle() + rotation);
+	}
+
+	// Moves forward by the animal's speed, burning energy on the way
+	pub(crate) fn advance(&mut self, config: &SimulationConfig) {
+		if !self.is_alive() {
+			return;
+		}
+
+		self.position += self.rotation * na::Vector2::new(0.0, self.speed);
+		self.distance_traveled += self.speed;
+		self.steps_alive += 1;
+
+		self.energy -= self.speed * config.energy_drain;
+		if self.energy <= 0.0 {
+			self.energy = 0.0;
+			self.speed = 0.0;
+		}
+
+		match config.boundary {
+			BoundaryMode::Wrap => {
+				self.position.x = na::wrap(self.position.x, 0.0, 1.0);
+				self.position.y = na::wrap(self.position.y, 0.0, 1.0);
+			}
+			BoundaryMode::Clamp => {
+				self.position.x = self.position.x.clamp(0.0, 1.0);
+				self.position.y = self.position.y.clamp(0.0, 1.0);
+			}
+			BoundaryMode::Bounce => self.bounce(),
+		}
+	}
+
+	// Mirrors the animal's position and rotation about every edge it has
+	// crossed; landing right on an edge doesn't count as crossing it.
+	//
+	// Animals face `rotation * (0, 1)`, i.e. `(-sin, cos)` of its angle, so
+	// flipping x negates the angle and flipping y takes it from π
+	fn bounce(&mut self) {
+		let (x, crossed_x) = reflect(self.position.x);
+		let (y, crossed_y) = reflect(self.position.y);
+		let mut angle = self.rotation.angle();
+
+		if crossed_x {
+			angle = -angle;
+		}
+
+		if crossed_y {
+			angle = PI - angle;
+		}
+
+		self.position = na::Point2::new(x, y);
+		self.rotation = na::Rotation2::new(angle);
+	}
+
 	pub(crate) fn as_chromosome(&self) -> Chromosome {
 		self.brain.as_chromosome()
 	}
@@ -42,6 +126,11 @@ impl Animal {
 			eye,
 			brain,
 			satiation: 0,
+			age: 0,
+			distance_traveled: 0.0,
+			energy: 1.0,
+			steps_alive: 0,
+			times_caught: 0,
 		}
 	}
 
@@ -57,8 +146,174 @@ impl Animal {
 		self.speed
 	}
 
+	pub fn brain(&self) -> &Brain {
+		&self.brain
+	}
+
+	/// Number of foods eaten, or of prey caught for predators.
 	pub fn fitness(&self) -> usize {
 		self.satiation
 	}
 
+	pub fn age(&self) -> usize {
+		self.age
+	}
+
+	pub fn distance_traveled(&self) -> f32 {
+		self.distance_traveled
+	}
+
+	/// Between 0 (starved) and 1 (full).
+	pub fn energy(&self) -> f32 {
+		self.energy
+	}
+
+	/// Dead animals stay where they starved, but don't move or eat anymore.
+	pub fn is_alive(&self) -> bool {
+		self.energy > 0.0
+	}
+
+	/// Number of steps the animal has been alive for so far.
+	pub fn survival_time(&self) -> usize {
+		self.steps_alive
+	}
+
+	/// Number of times a predator has caught this animal; always zero for
+	/// predators themselves.
+	pub fn times_caught(&self) -> usize {
+		self.times_caught
+	}
+
+}
+
+// Reflects a coordinate that has left [0, 1] back into it, telling whether
+// it had to; overshooting by more than the whole map ends up on the edge
+fn reflect(coordinate: f32) -> (f32, bool) {
+	if coordinate < 0.0 {
+		((-coordinate).min(1.0), true)
+	} else if coordinate > 1.0 {
+		((2.0 - coordinate).max(0.0), true)
+	} else {
+		(coordinate, false)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::f32::consts::FRAC_1_SQRT_2;
+
+	#[test]
+	fn clone_with_brain() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut original = Animal::random(&mut rng);
+		original.satiation = 5;
+		original.age = 2;
+
+		let clone = original.clone_with_brain(&mut rng);
+
+		assert_eq!(clone.brain.nn.weights(), original.brain.nn.weights());
+		assert_eq!(clone.eye.cells(), original.eye.cells());
+		assert_ne!(clone.position(), original.position());
+		assert_eq!(clone.fitness(), 0);
+		assert_eq!(clone.age(), 0);
+	}
+
+	// Heads right, i.e. towards the x = 1 wall, at full speed
+	fn animal_near_wall(rng: &mut dyn RngCore) -> Animal {
+		let mut animal = Animal::random(rng);
+		animal.position = na::Point2::new(0.99, 0.5);
+		animal.rotation = na::Rotation2::new(-std::f32::consts::FRAC_PI_2);
+		animal.speed = 0.005;
+		animal
+	}
+
+	#[test]
+	fn wrap_boundary() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut animal = animal_near_wall(&mut rng);
+
+		for _ in 0..3 {
+			animal.advance(&SimulationConfig::default());
+		}
+
+		assert!((animal.position.x - 0.005).abs() < 1e-5);
+		assert!((animal.position.y - 0.5).abs() < 1e-5);
+	}
+
+	#[test]
+	fn clamp_boundary() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut animal = animal_near_wall(&mut rng);
+		let config = SimulationConfig {
+			boundary: BoundaryMode::Clamp,
+			..Default::default()
+		};
+
+		for _ in 0..10 {
+			animal.advance(&config);
+			assert!(animal.position.x > 0.99);
+		}
+
+		assert_eq!(animal.position.x, 1.0);
+		assert!((animal.position.y - 0.5).abs() < 1e-5);
+		assert_eq!(animal.speed, 0.005);
+	}
+
+	// Moves an animal at `position`, heading along `direction`, and returns
+	// where it ends up and where it heads then
+	fn bounce(position: (f32, f32), direction: (f32, f32), speed: f32) -> (na::Point2<f32>, na::Vector2<f32>) {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut animal = Animal::random(&mut rng);
+		let config = SimulationConfig {
+			boundary: BoundaryMode::Bounce,
+			..Default::default()
+		};
+
+		animal.position = na::Point2::new(position.0, position.1);
+		animal.rotation =
+			na::Rotation2::rotation_between(&na::Vector2::y(), &na::Vector2::new(direction.0, direction.1));
+		animal.speed = speed;
+		animal.advance(&config);
+
+		(animal.position, animal.rotation * na::Vector2::y())
+	}
+
+	#[test]
+	fn bounce_boundary() {
+		// Travels 0.004 diagonally, i.e. this much along each axis
+		let step = 0.004 * FRAC_1_SQRT_2;
+
+		let cases = [
+			// Right, left, top and bottom wall, at 45°
+			((0.999, 0.5), (1.0, 1.0), (2.0 - 0.999 - step, 0.5 + step), (-1.0, 1.0)),
+			((0.001, 0.5), (-1.0, 1.0), (step - 0.001, 0.5 + step), (1.0, 1.0)),
+			((0.5, 0.999), (1.0, 1.0), (0.5 + step, 2.0 - 0.999 - step), (1.0, -1.0)),
+			((0.5, 0.001), (-1.0, -1.0), (0.5 - step, step - 0.001), (-1.0, 1.0)),
+			// Both walls of a corner at once
+			((0.999, 0.999), (1.0, 1.0), (2.0 - 0.999 - step, 2.0 - 0.999 - step), (-1.0, -1.0)),
+			((0.001, 0.001), (-1.0, -1.0), (step - 0.001, step - 0.001), (1.0, 1.0)),
+		];
+
+		for (start, direction, expected_position, expected_direction) in cases {
+			let (position, heading) = bounce(start, direction, 0.004);
+			let expected_heading = na::Vector2::new(expected_direction.0, expected_direction.1).normalize();
+
+			assert!((position - na::Point2::new(expected_position.0, expected_position.1)).norm() < 1e-5);
+			assert!((heading - expected_heading).norm() < 1e-5, "{:?} headed {:?}", start, heading);
+		}
+	}
+
+	#[test]
+	fn bounce_boundary_landing_on_the_edge() {
+		let (position, heading) = bounce((0.75, 0.5), (1.0, 0.0), 0.25);
+
+		assert_eq!(position.x, 1.0);
+		assert!((heading - na::Vector2::x()).norm() < 1e-5);
+
+		// Overshooting by more than the whole map still stays on it
+		let (position, _) = bounce((0.5, 0.5), (-1.0, 0.0), 3.0);
+
+		assert_eq!(position.x, 1.0);
+	}
 }
@@ -24,14 +24,25 @@ impl ga::Individual for AnimalIndividual {
 }
 
 impl AnimalIndividual {
-	pub fn from_animal(animal: &Animal) -> Self {
+	pub fn from_animal(animal: &Animal, fitness_fn: &FitnessFn, catch_penalty: f32) -> Self {
+		let mut fitness = fitness_fn.evaluate(animal);
+
+		if animal.times_caught > 0 {
+			// Roulette wheel selection needs fitnesses of at least zero
+			fitness = (fitness - catch_penalty * animal.times_caught as f32).max(0.0);
+		}
+
 		Self {
-			fitness: animal.satiation as f32,
+			fitness,
 			chromosome: animal.as_chromosome()
 		}
 	}
 
-	pub fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
-		Animal::from_chromosome(self.chromosome, rng)
+	pub fn has_same_genes(&self, other: &Self) -> bool {
+		self.chromosome.iter().eq(other.chromosome.iter())
+	}
+
+	pub fn into_animal(self, eye: Eye, rng: &mut dyn RngCore) -> Animal {
+		Animal::from_chromosome(self.chromosome, eye, rng)
 	}
 }
\ No newline at end of file
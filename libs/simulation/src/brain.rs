@@ -1,4 +1,6 @@
 use crate::*;
+use rand_distr::{Distribution, Normal};
+use std::fmt;
 
 #[derive(Debug)]
 pub struct Brain {
@@ -8,7 +10,7 @@ pub struct Brain {
 impl Brain {
 	pub fn random(rng: &mut dyn RngCore, eye: &Eye) -> Self {
 		Self {
-			nn: nn::Network::random(rng, &Self::topology(eye)),
+			nn: nn::Network::random(rng, &Self::topology(eye.cells())),
 		}
 	}
 
@@ -16,28 +18,194 @@ impl Brain {
 		chromosome: ga::Chromosome,
 		eye: &Eye,
 	) -> Self {
+		assert!(
+			Self::topology_matches(&chromosome, eye),
+			"got a chromosome of {} genes, but the eye needs a brain of {}",
+			chromosome.len(),
+			Self::chromosome_len(eye),
+		);
+
 		Self {
-			nn: nn::Network::from_weights(
-				&Self::topology(eye),
-				chromosome,
-			),
+			nn: chromosome.into_network(&Self::topology(eye.cells())),
 		}
 	}
 
 	pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
-		ga::Chromosome::new(self.nn.weights())
+		ga::Chromosome::from_network(&self.nn)
+	}
+
+	/// Number of genes encoding the brain that goes with `eye`.
+	pub fn chromosome_len(eye: &Eye) -> usize {
+		nn::LayerTopology::param_count(&Self::topology(eye.cells()))
+	}
+
+	pub fn topology_matches(chromosome: &ga::Chromosome, eye: &Eye) -> bool {
+		chromosome.len() == Self::chromosome_len(eye)
+	}
+
+	/// Number of genes encoding each neuron, in chromosome order; useful for
+	/// crossover methods that keep neurons intact.
+	pub fn segment_lengths(&self) -> Vec<usize> {
+		self.nn.segment_lengths()
+	}
+
+	/// Adds gaussian noise of standard deviation `magnitude` to every
+	/// weight (but no bias), keeping the network's topology as is; e.g. to
+	/// jolt a single animal in the middle of a generation.
+	///
+	/// Fails for a negative or non-finite `magnitude`.
+	pub fn perturb(&mut self, rng: &mut dyn RngCore, magnitude: f32) -> Result<(), BrainError> {
+		if !magnitude.is_finite() || magnitude < 0.0 {
+			return Err(BrainError::InvalidMagnitude(magnitude));
+		}
+
+		let noise = Normal::new(0.0, magnitude).unwrap();
+
+		self.nn.mutate_weights_excluding_biases(rng, |weight, rng| {
+			*weight += noise.sample(rng);
+		});
+
+		Ok(())
 	}
 
-	fn topology(eye: &Eye) -> Vec<nn::LayerTopology> {
+	/// Weights of every layer, one row per neuron, e.g. to render them as
+	/// heatmaps; see `nn::Layer::weight_matrix()`.
+	pub fn weight_matrices(&self) -> Vec<Vec<Vec<f32>>> {
+		self.nn.weight_matrices()
+	}
+
+	// Inputs are the eye's two channels, see `Eye::process_vision()`.
+	// Outputs are the speed and rotation deltas, which need a sign, so
+	// unlike the hidden layer they can't go through ReLU
+	pub(crate) fn topology(cells: usize) -> Vec<nn::LayerTopology> {
 		vec![
-			nn::LayerTopology {
-				neurons: eye.cells(),
-			},
-			nn::LayerTopology {
-				neurons: 2 * eye.cells(),
-			},
-			nn::LayerTopology { neurons: 2 },
+			nn::LayerTopology::new(VISION_CHANNELS * cells),
+			nn::LayerTopology::new(2 * cells),
+			nn::LayerTopology::new(2).with_activation(nn::Activation::Tanh),
 		]
 	}
 }
 
+/// Returned when a brain can't be perturbed by the given magnitude.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BrainError {
+	InvalidMagnitude(f32),
+}
+
+impl fmt::Display for BrainError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InvalidMagnitude(magnitude) => write!(f, "got an invalid perturbation magnitude of {}", magnitude),
+		}
+	}
+}
+
+impl std::error::Error for BrainError {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn topology_matches() {
+		let eye = Eye::default();
+		let len = Brain::chromosome_len(&eye);
+
+		assert!(Brain::topology_matches(&ga::Chromosome::new(vec![0.0; len]), &eye));
+		assert!(!Brain::topology_matches(&ga::Chromosome::new(vec![0.0; len - 1]), &eye));
+	}
+
+	#[test]
+	fn chromosome_len_matches_random_brains() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let eye = Eye::default();
+
+		assert_eq!(Brain::random(&mut rng, &eye).as_chromosome().len(), Brain::chromosome_len(&eye));
+	}
+
+	fn biases(brain: &Brain) -> Vec<f32> {
+		brain
+			.nn
+			.layers()
+			.iter()
+			.flat_map(|layer| layer.neurons())
+			.map(|neuron| neuron.bias())
+			.collect()
+	}
+
+	fn weights(brain: &Brain) -> Vec<f32> {
+		brain
+			.nn
+			.layers()
+			.iter()
+			.flat_map(|layer| layer.neurons())
+			.flat_map(|neuron| neuron.weights().iter().copied())
+			.collect()
+	}
+
+	#[test]
+	fn perturb_changes_only_weights() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut brain = Brain::random(&mut rng, &Eye::default());
+		let (original_biases, original_weights) = (biases(&brain), weights(&brain));
+
+		brain.perturb(&mut rng, 0.1).unwrap();
+
+		assert_eq!(biases(&brain), original_biases);
+		assert_ne!(weights(&brain), original_weights);
+
+		// The noise has a standard deviation of `magnitude`
+		let deltas: Vec<f32> = weights(&brain).iter().zip(&original_weights).map(|(a, b)| a - b).collect();
+		let variance = deltas.iter().map(|delta| delta * delta).sum::<f32>() / deltas.len() as f32;
+		assert!((variance.sqrt() - 0.1).abs() < 0.01, "std = {}", variance.sqrt());
+	}
+
+	#[test]
+	fn perturb_by_zero_is_a_no_op() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut brain = Brain::random(&mut rng, &Eye::default());
+		let original = brain.nn.weights();
+
+		brain.perturb(&mut rng, 0.0).unwrap();
+
+		assert_eq!(brain.nn.weights(), original);
+	}
+
+	#[test]
+	fn perturb_keeps_the_topology() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let topology = [
+			nn::LayerTopology::new(4),
+			nn::LayerTopology::new(3).with_kind(nn::LayerKind::Recurrent),
+			nn::LayerTopology::new(2).with_skip_from(0),
+		];
+		let scaler = nn::InputScaler::new(vec![0.0; 4], vec![2.0; 4]);
+		let mut brain = Brain {
+			nn: nn::Network::random(&mut rng, &topology).with_input_scaler(scaler.clone()),
+		};
+
+		brain.perturb(&mut rng, 0.1).unwrap();
+
+		assert_eq!(brain.nn.layers()[0].kind(), nn::LayerKind::Recurrent);
+		assert_eq!(brain.nn.layers()[1].skip_from(), Some(0));
+		assert_eq!(brain.nn.input_scaler(), Some(&scaler));
+	}
+
+	#[test]
+	fn perturb_rejects_invalid_magnitudes() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut brain = Brain::random(&mut rng, &Eye::default());
+		let original = brain.nn.weights();
+
+		assert_eq!(brain.perturb(&mut rng, -0.1), Err(BrainError::InvalidMagnitude(-0.1)));
+		assert_eq!(brain.perturb(&mut rng, f32::INFINITY), Err(BrainError::InvalidMagnitude(f32::INFINITY)));
+		assert!(matches!(brain.perturb(&mut rng, f32::NAN), Err(BrainError::InvalidMagnitude(magnitude)) if magnitude.is_nan()));
+		assert_eq!(brain.nn.weights(), original);
+	}
+
+	#[test]
+	#[should_panic(expected = "got a chromosome of 3 genes")]
+	fn from_chromosome_rejects_wrong_length() {
+		Brain::from_chromosome(ga::Chromosome::new(vec![0.0; 3]), &Eye::default());
+	}
+}
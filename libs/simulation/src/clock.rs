@@ -0,0 +1,52 @@
+// Measures wall-clock time; wasm32 has no `Instant`, so there it's based on
+// the browser's `performance.now()` instead
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Clock {
+	#[cfg(not(target_arch = "wasm32"))]
+	start: std::time::Instant,
+	// Milliseconds, as returned by `performance.now()`
+	#[cfg(target_arch = "wasm32")]
+	start: f64,
+}
+
+impl Clock {
+	pub(crate) fn start() -> Self {
+		Self { start: now() }
+	}
+
+	/// Seconds since `start()`.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub(crate) fn elapsed(&self) -> f64 {
+		self.start.elapsed().as_secs_f64()
+	}
+
+	/// Seconds since `start()`.
+	#[cfg(target_arch = "wasm32")]
+	pub(crate) fn elapsed(&self) -> f64 {
+		(now() - self.start) / 1000.0
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> std::time::Instant {
+	std::time::Instant::now()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now() -> f64 {
+	PERFORMANCE.now()
+}
+
+// `now()` has to be called on the `performance` object itself, so it can't
+// be imported as a free function
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+extern "C" {
+	type Performance;
+
+	#[wasm_bindgen(js_name = performance)]
+	static PERFORMANCE: Performance;
+
+	#[wasm_bindgen(method)]
+	fn now(this: &Performance) -> f64;
+}
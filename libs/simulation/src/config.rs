@@ -0,0 +1,262 @@
+use crate::*;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationConfig {
+	// Number of the best animals carried over unchanged to the next generation
+	pub elite_count: usize,
+	// Steps per second the simulation is meant to run at, see
+	// `Simulation::is_behind_schedule()`
+	pub target_tick_hz: f64,
+	// Shape of every animal's eyes, which also decides their brains' size
+	pub eye: EyeConfig,
+	// Both have to be at least 1
+	pub animal_count: usize,
+	pub food_count: usize,
+	// Range the animals' speed is kept within, per step
+	pub speed_min: f32,
+	pub speed_max: f32,
+	// How much a brain can change its animal's speed and rotation per step
+	pub speed_accel: f32,
+	pub rotation_accel: f32,
+	// Steps each generation lives for before evolution kicks in
+	pub steps_per_generation: usize,
+	// Passed to `ga::GaussianMutation::new()`
+	pub mutation_chance: f32,
+	pub mutation_coeff: f32,
+	// How close an animal has to get to a food to eat it
+	pub collision_radius: f32,
+	// Number of generations `Simulation::stats_history()` goes back; older
+	// ones get dropped
+	pub max_history: usize,
+	// Share of a full stomach an animal burns per unit of distance, and
+	// regains per food eaten
+	pub energy_drain: f32,
+	pub food_energy: f32,
+	// Share of the food that's poison, within [0, 1]
+	pub poison_ratio: f32,
+	// Number of predators, which hunt the other animals (prey) instead of
+	// food; zero turns predators off
+	pub predator_count: usize,
+	// Shape of the predators' eyes, which see prey instead of food
+	pub predator_eye: EyeConfig,
+	// How close a predator has to get to a prey to catch it
+	pub catch_radius: f32,
+	// Taken off a prey's fitness every time it gets caught
+	pub catch_penalty: f32,
+	// What happens to animals that reach the edge of the map
+	pub boundary: BoundaryMode,
+	// Longest (L2) every neuron's weights are let grow whenever animals
+	// are born, see `nn::Network::apply_max_norm()`; `None` lets them grow
+	// freely
+	pub weight_max_norm: Option<f32>,
+}
+
+/// Shape of the map's edges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoundaryMode {
+	/// Animals leaving the map come back on the opposite side, i.e. the map
+	/// is a torus
+	#[default]
+	Wrap,
+	/// Animals stop at the edge of the map, keeping their speed and
+	/// rotation
+	Clamp,
+	/// Animals bounce off the edge of the map as off a mirror, keeping
+	/// their speed
+	Bounce,
+}
+
+impl Default for SimulationConfig {
+	fn default() -> Self {
+		Self {
+			elite_count: 0,
+			target_tick_hz: 60.0,
+			eye: EyeConfig::default(),
+			animal_count: ANIMAL_COUNT,
+			food_count: FOOD_COUNT,
+			speed_min: SPEED_MIN,
+			speed_max: SPEED_MAX,
+			speed_accel: SPEED_ACCEL,
+			rotation_accel: ROTATION_ACCEL,
+			steps_per_generation: STEP_EACH_GENERATION,
+			mutation_chance: MUTATION_CHANCE,
+			mutation_coeff: MUTATION_COEFF,
+			collision_radius: COLLISION_RADIUS,
+			max_history: MAX_HISTORY,
+			energy_drain: ENERGY_DRAIN,
+			food_energy: FOOD_ENERGY,
+			poison_ratio: 0.0,
+			predator_count: 0,
+			predator_eye: EyeConfig {
+				fov_range: PREDATOR_FOV_RANGE,
+				fov_angle: PREDATOR_FOV_ANGLE,
+				..Default::default()
+			},
+			catch_radius: CATCH_RADIUS,
+			catch_penalty: CATCH_PENALTY,
+			boundary: BoundaryMode::Wrap,
+			weight_max_norm: None,
+		}
+	}
+}
+
+impl SimulationConfig {
+	/// Checks that a simulation can actually run with this config, see
+	/// `Simulation::try_new()`.
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		if self.animal_count == 0 {
+			return Err(ConfigError::NoAnimals);
+		}
+
+		if self.food_count == 0 {
+			return Err(ConfigError::NoFood);
+		}
+
+		if self.elite_count > self.animal_count {
+			return Err(ConfigError::TooManyElites {
+				elite_count: self.elite_count,
+				animal_count: self.animal_count,
+			});
+		}
+
+		if !(0.0 <= self.speed_min && self.speed_min <= self.speed_max) {
+			return Err(ConfigError::InvalidSpeedRange {
+				min: self.speed_min,
+				max: self.speed_max,
+			});
+		}
+
+		if !(self.speed_accel >= 0.0 && self.rotation_accel >= 0.0) {
+			return Err(ConfigError::NegativeAcceleration);
+		}
+
+		if self.steps_per_generation == 0 {
+			return Err(ConfigError::EmptyGeneration);
+		}
+
+		if !(0.0..=1.0).contains(&self.mutation_chance) || !(0.0..=3.0).contains(&self.mutation_coeff) {
+			return Err(ConfigError::InvalidMutation {
+				chance: self.mutation_chance,
+				coeff: self.mutation_coeff,
+			});
+		}
+
+		if self.max_history == 0 {
+			return Err(ConfigError::NoHistory);
+		}
+
+		if self.collision_radius.is_nan() || self.collision_radius <= 0.0 {
+			return Err(ConfigError::InvalidCollisionRadius(self.collision_radius));
+		}
+
+		if self.catch_radius.is_nan() || self.catch_radius <= 0.0 {
+			return Err(ConfigError::InvalidCatchRadius(self.catch_radius));
+		}
+
+		if self.catch_penalty.is_nan() || self.catch_penalty < 0.0 {
+			return Err(ConfigError::NegativeCatchPenalty(self.catch_penalty));
+		}
+
+		if !(self.energy_drain >= 0.0 && self.food_energy >= 0.0) {
+			return Err(ConfigError::NegativeEnergy);
+		}
+
+		if !(0.0..=1.0).contains(&self.poison_ratio) {
+			return Err(ConfigError::InvalidPoisonRatio(self.poison_ratio));
+		}
+
+		if let Some(max_norm) = self.weight_max_norm {
+			if max_norm.is_nan() || max_norm < 0.0 {
+				return Err(ConfigError::InvalidWeightMaxNorm(max_norm));
+			}
+		}
+
+		if self.target_tick_hz.is_nan() || self.target_tick_hz <= 0.0 {
+			return Err(ConfigError::InvalidTickRate(self.target_tick_hz));
+		}
+
+		Eye::new(self.eye.fov_range, self.eye.fov_angle, self.eye.cells)?;
+		Eye::new(self.predator_eye.fov_range, self.predator_eye.fov_angle, self.predator_eye.cells)?;
+
+		Ok(())
+	}
+}
+
+/// Returned when a simulation can't run with the given config.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfigError {
+	NoAnimals,
+	NoFood,
+	TooManyElites {
+		elite_count: usize,
+		animal_count: usize,
+	},
+	InvalidSpeedRange {
+		min: f32,
+		max: f32,
+	},
+	NegativeAcceleration,
+	EmptyGeneration,
+	NoHistory,
+	/// `GaussianMutation` needs a chance within [0, 1] and a coefficient
+	/// within [0, 3].
+	InvalidMutation {
+		chance: f32,
+		coeff: f32,
+	},
+	InvalidCollisionRadius(f32),
+	InvalidCatchRadius(f32),
+	InvalidTickRate(f64),
+	NegativeEnergy,
+	InvalidPoisonRatio(f32),
+	NegativeCatchPenalty(f32),
+	InvalidWeightMaxNorm(f32),
+	Eye(EyeError),
+}
+
+impl From<EyeError> for ConfigError {
+	fn from(err: EyeError) -> Self {
+		Self::Eye(err)
+	}
+}
+
+impl fmt::Display for ConfigError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NoAnimals => write!(f, "got no animals"),
+			Self::NoFood => write!(f, "got no food"),
+			Self::TooManyElites {
+				elite_count,
+				animal_count,
+			} => write!(f, "got {} elites, but only {} animals", elite_count, animal_count),
+			Self::InvalidSpeedRange { min, max } => {
+				write!(f, "got an invalid speed range of [{}, {}]", min, max)
+			}
+			Self::NegativeAcceleration => write!(f, "got a negative acceleration"),
+			Self::EmptyGeneration => write!(f, "got generations of no steps"),
+			Self::NoHistory => write!(f, "got a history of no generations"),
+			Self::InvalidMutation { chance, coeff } => write!(
+				f,
+				"got an invalid mutation chance of {} or coefficient of {}",
+				chance, coeff
+			),
+			Self::InvalidCollisionRadius(radius) => {
+				write!(f, "got an invalid collision radius of {}", radius)
+			}
+			Self::InvalidCatchRadius(radius) => write!(f, "got an invalid catch radius of {}", radius),
+			Self::InvalidTickRate(hz) => write!(f, "got an invalid tick rate of {} Hz", hz),
+			Self::NegativeEnergy => write!(f, "got a negative energy drain or food energy"),
+			Self::InvalidPoisonRatio(ratio) => write!(f, "got an invalid poison ratio of {}", ratio),
+			Self::NegativeCatchPenalty(penalty) => write!(f, "got a negative catch penalty of {}", penalty),
+			Self::InvalidWeightMaxNorm(max_norm) => {
+				write!(f, "got an invalid maximum weight norm of {}", max_norm)
+			}
+			Self::Eye(err) => err.fmt(f),
+		}
+	}
+}
+
+impl std::error::Error for ConfigError {}
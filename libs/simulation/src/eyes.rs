@@ -1,5 +1,6 @@
 use crate::*;
 use std::f32::consts::*;
+use std::fmt;
 
 // 25% of the map
 const FOV_RANGE: f32 = 0.25;
@@ -10,7 +11,45 @@ const FOV_ANGLE: f32 = PI + FRAC_PI_4;
 // number of photoreceptors
 const CELLS: usize = 9;
 
-#[derive(Debug)]
+// Each cell sees nutritious food and poison separately
+pub(crate) const VISION_CHANNELS: usize = 2;
+
+/// A single photoreceptor's reading.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VisionCell {
+	/// Direction the cell is centered on, in world space; like rotations,
+	/// measured from the y axis
+	pub angle: f32,
+	/// How much nutritious food the cell sees
+	pub intensity: f32,
+	/// How much poison the cell sees
+	pub poison_intensity: f32,
+}
+
+/// Shape of the animals' eyes, see `SimulationConfig`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EyeConfig {
+	/// How far the eye sees, as a fraction of the map
+	pub fov_range: f32,
+	/// How wide the eye sees, in radians
+	pub fov_angle: f32,
+	/// Number of photoreceptors; the brain gets two inputs per cell, see
+	/// `Eye::process_vision()`
+	pub cells: usize,
+}
+
+impl Default for EyeConfig {
+	fn default() -> Self {
+		Self {
+			fov_range: FOV_RANGE,
+			fov_angle: FOV_ANGLE,
+			cells: CELLS,
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
 pub struct Eye {
 	fov_range: f32,
 	fov_angle: f32,
@@ -18,24 +57,120 @@ pub struct Eye {
 }
 
 impl Eye {
-	fn new (fov_range: f32, fov_angle: f32, cells: usize) -> Self {
-		assert!(fov_angle > 0.0);
-		assert!(fov_angle > 0.0);
-		assert!(cells > 0);
-		Self {fov_range, fov_angle, cells}
+	/// Needs a positive `fov_range`, a `fov_angle` within (0, 2π) and at
+	/// least one cell.
+	pub fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Result<Self, EyeError> {
+		// NaNs fail every comparison, so they're rejected too
+		if fov_range.is_nan() || fov_range <= 0.0 {
+			return Err(EyeError::InvalidFovRange(fov_range));
+		}
+
+		if !(fov_angle > 0.0 && fov_angle < TAU) {
+			return Err(EyeError::InvalidFovAngle(fov_angle));
+		}
+
+		if cells == 0 {
+			return Err(EyeError::NoCells);
+		}
+
+		Ok(Self {
+			fov_range,
+			fov_angle,
+			cells,
+		})
+	}
+
+	// Same as `new()`, but panics on invalid parameters
+	pub(crate) fn new_unchecked(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
+		Self::new(fov_range, fov_angle, cells).unwrap_or_else(|err| panic!("{}", err))
+	}
+
+	/// Panics if `config` is invalid, see `new()`.
+	pub fn from_config(config: &EyeConfig) -> Self {
+		Self::new_unchecked(config.fov_range, config.fov_angle, config.cells)
+	}
+
+	/// Parameters this eye was built with.
+	pub fn config(&self) -> EyeConfig {
+		EyeConfig {
+			fov_range: self.fov_range,
+			fov_angle: self.fov_angle,
+			cells: self.cells,
+		}
 	}
 
 	pub fn cells(&self) -> usize {
 		self.cells
 	}
 
+	/// Length of `process_vision()`'s output, i.e. number of the brain's
+	/// inputs.
+	pub fn input_count(&self) -> usize {
+		VISION_CHANNELS * self.cells
+	}
+
+	/// Width of a single cell's field of view.
+	pub fn cell_angle(&self) -> f32 {
+		self.fov_angle / self.cells as f32
+	}
+
+	/// Returns what each cell sees of nutritious food, followed by what
+	/// each cell sees of poison.
+	///
+	/// Distances are measured straight across the map, so the eye never
+	/// sees past its edges, whatever the `BoundaryMode`.
 	pub fn process_vision(
 		&self,
 		position: na::Point2<f32>,
 		rotation: na::Rotation2<f32>,
 		foods: &[Food],
 	) -> Vec<f32> {
-		let mut cells = vec![0.0; self.cells];
+		Self::intensities(&self.process_vision_detailed(position, rotation, foods))
+	}
+
+	/// Same as `process_vision()`, but only looks at the foods `grid` (built
+	/// from the positions of `foods`) finds within range; the result is
+	/// exactly the same.
+	pub fn process_vision_with_grid(
+		&self,
+		position: na::Point2<f32>,
+		rotation: na::Rotation2<f32>,
+		foods: &[Food],
+		grid: &SpatialGrid,
+	) -> Vec<f32> {
+		let in_range = grid.query_circle(position, self.fov_range);
+		let cells = self.see(position, rotation, in_range.into_iter().map(|index| &foods[index]));
+
+		Self::intensities(&cells)
+	}
+
+	/// Same as `process_vision()`, but also tells where each cell looks.
+	pub fn process_vision_detailed(
+		&self,
+		position: na::Point2<f32>,
+		rotation: na::Rotation2<f32>,
+		foods: &[Food],
+	) -> Vec<VisionCell> {
+		self.see(position, rotation, foods.iter())
+	}
+
+	fn see<'a>(
+		&self,
+		position: na::Point2<f32>,
+		rotation: na::Rotation2<f32>,
+		foods: impl Iterator<Item = &'a Food>,
+	) -> Vec<VisionCell> {
+		let mut cells: Vec<_> = (0..self.cells)
+			.map(|cell| {
+				let angle = -self.fov_angle / 2.0 + (cell as f32 + 0.5) * self.cell_angle();
+
+				VisionCell {
+					angle: na::wrap(rotation.angle() + angle, -PI, PI),
+					intensity: 0.0,
+					poison_intensity: 0.0,
+				}
+			})
+			.collect();
 
 		for food in foods {
 			let vec = food.position - position;
@@ -51,7 +186,7 @@ impl Eye {
 
 			let angle = angle - rotation.angle();
 			let angle = na::wrap(angle, -PI, PI);
-			if angle < -self.fov_angle / 2.0 || angle < self.fov_angle / 2.0 {
+			if angle < -self.fov_angle / 2.0 || angle > self.fov_angle / 2.0 {
 				continue;
 			}
 
@@ -61,17 +196,156 @@ impl Eye {
 			let cell = (cell as usize).min(cells.len() - 1);
 
 			let energy = (self.fov_range - dist) / self.fov_range;
-			cells[cell] += energy;
+
+			match food.kind {
+				FoodKind::Nutritious => cells[cell].intensity += energy,
+				FoodKind::Poison => cells[cell].poison_intensity += energy,
+			}
 		}
 
 		cells
 	}
+
+	fn intensities(cells: &[VisionCell]) -> Vec<f32> {
+		cells
+			.iter()
+			.map(|cell| cell.intensity)
+			.chain(cells.iter().map(|cell| cell.poison_intensity))
+			.collect()
+	}
 }
 
 impl Default for Eye {
 	fn default() -> Self {
-		Self::new(FOV_RANGE, FOV_ANGLE, CELLS)
+		Self::from_config(&EyeConfig::default())
+	}
+}
+
+/// Returned when an eye can't be built with the given parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EyeError {
+	InvalidFovRange(f32),
+	InvalidFovAngle(f32),
+	NoCells,
+}
+
+impl fmt::Display for EyeError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InvalidFovRange(range) => write!(f, "got an invalid field of view range of {}", range),
+			Self::InvalidFovAngle(angle) => write!(f, "got an invalid field of view angle of {}", angle),
+			Self::NoCells => write!(f, "got an eye with no cells"),
+		}
 	}
 }
 
-// TODO: Tests
\ No newline at end of file
+impl std::error::Error for EyeError {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn food(x: f32, y: f32) -> Food {
+		Food::new(na::Point2::new(x, y))
+	}
+
+	#[test]
+	fn new() {
+		assert!(Eye::new(FOV_RANGE, FOV_ANGLE, CELLS).is_ok());
+
+		assert_eq!(Eye::new(0.0, FOV_ANGLE, CELLS).err(), Some(EyeError::InvalidFovRange(0.0)));
+		assert_eq!(Eye::new(-0.1, FOV_ANGLE, CELLS).err(), Some(EyeError::InvalidFovRange(-0.1)));
+		assert_eq!(Eye::new(FOV_RANGE, 0.0, CELLS).err(), Some(EyeError::InvalidFovAngle(0.0)));
+		assert_eq!(Eye::new(FOV_RANGE, -1.0, CELLS).err(), Some(EyeError::InvalidFovAngle(-1.0)));
+		assert_eq!(Eye::new(FOV_RANGE, TAU, CELLS).err(), Some(EyeError::InvalidFovAngle(TAU)));
+		assert_eq!(Eye::new(FOV_RANGE, FOV_ANGLE, 0).err(), Some(EyeError::NoCells));
+
+		// NaNs never equal each other, so the errors can't be compared as is
+		assert!(matches!(Eye::new(f32::NAN, FOV_ANGLE, CELLS), Err(EyeError::InvalidFovRange(range)) if range.is_nan()));
+		assert!(matches!(Eye::new(FOV_RANGE, f32::NAN, CELLS), Err(EyeError::InvalidFovAngle(angle)) if angle.is_nan()));
+	}
+
+	#[test]
+	#[should_panic(expected = "got an eye with no cells")]
+	fn new_unchecked() {
+		Eye::new_unchecked(FOV_RANGE, FOV_ANGLE, 0);
+	}
+
+	#[test]
+	fn food_straight_ahead() {
+		let eye = Eye::default();
+		let rotation = na::Rotation2::new(FRAC_PI_4);
+		let position = na::Point2::new(0.5, 0.5);
+		let ahead = position + rotation * na::Vector2::new(0.0, 0.1);
+
+		let cells = eye.process_vision_detailed(position, rotation, &[food(ahead.x, ahead.y)]);
+		let brightest = cells
+			.iter()
+			.max_by(|a, b| a.intensity.total_cmp(&b.intensity))
+			.unwrap();
+
+		assert!(brightest.intensity > 0.0);
+		assert!((brightest.angle - rotation.angle()).abs() < eye.cell_angle() / 2.0);
+	}
+
+	#[test]
+	fn food_out_of_range() {
+		let eye = Eye::default();
+		let cells = eye.process_vision(
+			na::Point2::new(0.5, 0.5),
+			na::Rotation2::new(0.0),
+			&[food(0.5, 0.9)],
+		);
+
+		assert_eq!(cells, vec![0.0; VISION_CHANNELS * CELLS]);
+	}
+
+	#[test]
+	fn food_across_the_edge() {
+		let eye = Eye::default();
+		let cells = eye.process_vision(
+			na::Point2::new(0.99, 0.5),
+			na::Rotation2::new(-FRAC_PI_2),
+			&[food(0.01, 0.5)],
+		);
+
+		assert_eq!(cells, vec![0.0; VISION_CHANNELS * CELLS]);
+	}
+
+	#[test]
+	fn poison_straight_ahead() {
+		let eye = Eye::default();
+		let position = na::Point2::new(0.5, 0.5);
+		let poison = Food::with_kind(na::Point2::new(0.5, 0.6), FoodKind::Poison);
+
+		let vision = eye.process_vision(position, na::Rotation2::new(0.0), &[poison]);
+		let (nutritious, poison) = vision.split_at(CELLS);
+
+		assert_eq!(vision.len(), eye.input_count());
+		assert_eq!(nutritious, [0.0; CELLS]);
+		assert!(poison[CELLS / 2] > 0.0);
+
+		for (cell, intensity) in poison.iter().enumerate() {
+			if cell != CELLS / 2 {
+				assert_eq!(*intensity, 0.0);
+			}
+		}
+	}
+
+	#[test]
+	fn process_vision_matches_detailed() {
+		let eye = Eye::default();
+		let position = na::Point2::new(0.5, 0.5);
+		let rotation = na::Rotation2::new(-1.0);
+		let foods = [food(0.4, 0.6), food(0.6, 0.55), food(0.3, 0.3)];
+
+		let detailed = eye.process_vision_detailed(position, rotation, &foods);
+		let detailed: Vec<f32> = detailed
+			.iter()
+			.map(|cell| cell.intensity)
+			.chain(detailed.iter().map(|cell| cell.poison_intensity))
+			.collect();
+
+		assert_eq!(eye.process_vision(position, rotation, &foods), detailed);
+	}
+}
\ No newline at end of file
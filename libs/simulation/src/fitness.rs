@@ -0,0 +1,62 @@
+use crate::*;
+
+/// How animals get scored when the generation ends, i.e. what evolution
+/// selects for; see `Simulation::with_fitness_fn()`.
+///
+/// Roulette wheel selection needs fitnesses of at least zero, so custom ones
+/// below that count as zero.
+pub enum FitnessFn {
+	/// Number of foods eaten
+	Satiation,
+	/// Number of foods eaten, minus `weight` for every unit of distance
+	/// traveled (i.e. the map's width), floored at zero
+	SatiationMinusDistance { weight: f32 },
+	/// Number of foods eaten, plus `weight` for every step survived
+	SatiationPlusSurvival { weight: f32 },
+	Custom(Box<dyn Fn(&Animal) -> f32 + Send>),
+}
+
+impl FitnessFn {
+	pub fn custom(f: impl Fn(&Animal) -> f32 + Send + 'static) -> Self {
+		Self::Custom(Box::new(f))
+	}
+
+	pub fn evaluate(&self, animal: &Animal) -> f32 {
+		match self {
+			Self::Satiation => animal.satiation as f32,
+			Self::SatiationMinusDistance { weight } => {
+				(animal.satiation as f32 - weight * animal.distance_traveled).max(0.0)
+			}
+			Self::SatiationPlusSurvival { weight } => {
+				animal.satiation as f32 + weight * animal.steps_alive as f32
+			}
+			Self::Custom(f) => f(animal).max(0.0),
+		}
+	}
+}
+
+impl Default for FitnessFn {
+	fn default() -> Self {
+		Self::Satiation
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn evaluate() {
+		let mut animal = Animal::random(&mut ChaCha8Rng::seed_from_u64(0));
+		animal.satiation = 5;
+		animal.distance_traveled = 2.0;
+		animal.steps_alive = 10;
+
+		assert_eq!(FitnessFn::Satiation.evaluate(&animal), 5.0);
+		assert_eq!(FitnessFn::SatiationMinusDistance { weight: 0.5 }.evaluate(&animal), 4.0);
+		assert_eq!(FitnessFn::SatiationMinusDistance { weight: 3.0 }.evaluate(&animal), 0.0);
+		assert_eq!(FitnessFn::SatiationPlusSurvival { weight: 0.1 }.evaluate(&animal), 6.0);
+		assert_eq!(FitnessFn::custom(|animal| animal.age() as f32 + 1.0).evaluate(&animal), 1.0);
+		assert_eq!(FitnessFn::custom(|_| -2.0).evaluate(&animal), 0.0);
+	}
+}
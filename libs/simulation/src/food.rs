@@ -1,17 +1,123 @@
 use crate::*;
 
-#[derive(Debug)]
+/// Whether eating a food helps or hurts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FoodKind {
+	#[default]
+	Nutritious,
+	/// Takes away a point of satiation and `food_energy` (see
+	/// `SimulationConfig`) when eaten
+	Poison,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Food {
-	pub(crate) position: na::Point2<f32>
+	pub(crate) position: na::Point2<f32>,
+	pub(crate) kind: FoodKind,
+	// Where the food respawns, see `FoodGrid`; anywhere when `None`
+	pub(crate) zone: Option<FoodZone>,
 }
+
 impl Food {
-	pub fn random(rng: &mut dyn RngCore) -> Self {
+	pub fn new(position: na::Point2<f32>) -> Self {
+		Self::with_kind(position, FoodKind::Nutritious)
+	}
+
+	pub fn with_kind(position: na::Point2<f32>, kind: FoodKind) -> Self {
 		Self {
-			position: rng.gen()
+			position,
+			kind,
+			zone: None,
 		}
 	}
 
+	// Food placed somewhere within `zone`, where it also respawns
+	pub(crate) fn random_in(rng: &mut dyn RngCore, zone: &FoodZone) -> Self {
+		Self {
+			zone: Some(zone.clone()),
+			..Self::new(zone.random_position(rng))
+		}
+	}
+
+	pub fn random(rng: &mut dyn RngCore) -> Self {
+		Self::new(rng.gen())
+	}
+
 	pub fn position(&self) -> na::Point2<f32> {
 		self.position
 	}
-}
\ No newline at end of file
+
+	pub fn position_mut(&mut self) -> &mut na::Point2<f32> {
+		&mut self.position
+	}
+
+	pub fn kind(&self) -> FoodKind {
+		self.kind
+	}
+
+	/// Zone the food respawns within, if it was placed by a `FoodGrid`.
+	pub fn zone(&self) -> Option<&FoodZone> {
+		self.zone.as_ref()
+	}
+
+	// Moves eaten food somewhere else (within its zone, if it has one); it
+	// stays of the same kind
+	pub(crate) fn respawn(&mut self, rng: &mut dyn RngCore) {
+		self.position = match &self.zone {
+			Some(zone) => zone.random_position(rng),
+			None => rng.gen(),
+		};
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn new() {
+		let position = na::Point2::new(0.25, 0.75);
+
+		assert_eq!(Food::new(position).position(), position);
+		assert_eq!(Food::new(position).kind(), FoodKind::Nutritious);
+	}
+
+	#[test]
+	fn position_mut() {
+		let mut food = Food::new(na::Point2::new(0.25, 0.75));
+		food.position_mut().x = 0.5;
+
+		assert_eq!(food.position(), na::Point2::new(0.5, 0.75));
+	}
+
+	#[test]
+	fn matches_respawned_food() {
+		let mut food = Food::new(na::Point2::new(0.25, 0.75));
+		food.respawn(&mut ChaCha8Rng::seed_from_u64(0));
+
+		let position = ChaCha8Rng::seed_from_u64(0).gen();
+
+		assert_eq!(Food::new(position), food);
+	}
+
+	#[test]
+	fn respawns_within_its_zone() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let zone = FoodZone::new(na::Point2::new(0.2, 0.7), 0.05, 1);
+		let mut food = Food::random_in(&mut rng, &zone);
+
+		for _ in 0..100 {
+			food.respawn(&mut rng);
+			assert!(zone.contains(food.position()), "{:?} is outside of the zone", food.position());
+		}
+	}
+
+	#[test]
+	fn respawned_poison_stays_poison() {
+		let mut food = Food::with_kind(na::Point2::new(0.25, 0.75), FoodKind::Poison);
+		food.respawn(&mut ChaCha8Rng::seed_from_u64(0));
+
+		assert_eq!(food.kind(), FoodKind::Poison);
+	}
+}
@@ -0,0 +1,161 @@
+use crate::*;
+
+/// Describes where food should be placed, so that it forms clusters instead
+/// of being spread uniformly across the world.
+#[derive(Clone, Debug, Default)]
+pub struct FoodGrid {
+	pub zones: Vec<FoodZone>,
+}
+
+/// Circle containing `density` pieces of food.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FoodZone {
+	pub center: na::Point2<f32>,
+	pub radius: f32,
+	pub density: u32,
+}
+
+impl FoodZone {
+	pub fn new(center: na::Point2<f32>, radius: f32, density: u32) -> Self {
+		assert!(radius >= 0.0);
+
+		Self { center, radius, density }
+	}
+
+	pub fn contains(&self, position: na::Point2<f32>) -> bool {
+		na::distance(&self.center, &position) <= self.radius
+	}
+
+	pub(crate) fn random_position(&self, rng: &mut dyn RngCore) -> na::Point2<f32> {
+		// sqrt() keeps the food evenly spread instead of piling up in the center
+		let distance = self.radius * rng.gen::<f32>().sqrt();
+		let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+
+		self.center + na::Vector2::new(angle.cos(), angle.sin()) * distance
+	}
+}
+
+impl FoodGrid {
+	pub fn new(zones: Vec<FoodZone>) -> Self {
+		Self { zones }
+	}
+
+	/// Number of foods the grid places, i.e. the sum of the zones' densities.
+	pub fn food_count(&self) -> usize {
+		self.zones.iter().map(|zone| zone.density as usize).sum()
+	}
+
+	pub(crate) fn random_foods(&self, rng: &mut dyn RngCore) -> Vec<Food> {
+		self.zones
+			.iter()
+			.flat_map(|zone| (0..zone.density).map(move |_| zone))
+			.map(|zone| Food::random_in(rng, zone))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn grid() -> FoodGrid {
+		FoodGrid::new(vec![
+			FoodZone::new(na::Point2::new(0.2, 0.2), 0.1, 10),
+			FoodZone::new(na::Point2::new(0.7, 0.5), 0.25, 25),
+			FoodZone::new(na::Point2::new(0.5, 0.9), 0.0, 3),
+		])
+	}
+
+	#[test]
+	fn food_falls_within_zones() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let grid = grid();
+		let world = World::random_with_food_grid(&mut rng, &SimulationConfig::default(), &grid);
+
+		for food in world.food() {
+			assert!(
+				grid.zones.iter().any(|zone| zone.contains(food.position())),
+				"{:?} is outside of every zone",
+				food.position(),
+			);
+		}
+	}
+
+	#[test]
+	fn density_food_per_zone() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let grid = grid();
+		let world = World::random_with_food_grid(&mut rng, &SimulationConfig::default(), &grid);
+
+		assert_eq!(world.food().len(), 38);
+
+		// Zones don't overlap, so each piece of food belongs to exactly one
+		for zone in &grid.zones {
+			let count = world.food().iter().filter(|food| zone.contains(food.position())).count();
+			assert_eq!(count, zone.density as usize);
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "got no food")]
+	fn rejects_grids_without_food() {
+		let grid = FoodGrid::new(vec![FoodZone::new(na::Point2::new(0.5, 0.5), 0.1, 0)]);
+
+		World::random_with_food_grid(&mut ChaCha8Rng::seed_from_u64(0), &SimulationConfig::default(), &grid);
+	}
+
+	#[test]
+	fn uses_the_config() {
+		let config = SimulationConfig {
+			animal_count: 7,
+			predator_count: 2,
+			..Default::default()
+		};
+		let world = World::random_with_food_grid(&mut ChaCha8Rng::seed_from_u64(0), &config, &grid());
+
+		assert_eq!(world.animals().len(), 7);
+		assert_eq!(world.predators().len(), 2);
+	}
+
+	#[test]
+	fn food_stays_within_zones() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let grid = FoodGrid::new(vec![
+			FoodZone::new(na::Point2::new(0.3, 0.3), 0.2, 20),
+			FoodZone::new(na::Point2::new(0.7, 0.7), 0.2, 20),
+		]);
+		let config = SimulationConfig {
+			steps_per_generation: 200,
+			..Default::default()
+		};
+		let mut simulation = Simulation::with_food_grid(config, &grid, &mut rng);
+
+		// Food respawns both when it's eaten and when a generation ends
+		for _ in 0..1000 {
+			simulation.step(&mut rng);
+		}
+
+		assert!(simulation.total_food_eaten() > 0);
+		assert!(simulation.generation() > 0);
+
+		for food in simulation.world().food() {
+			assert!(
+				grid.zones.iter().any(|zone| zone.contains(food.position())),
+				"{:?} is outside of every zone",
+				food.position(),
+			);
+		}
+	}
+
+	#[test]
+	fn simulation_rejects_grids_without_food() {
+		let grid = FoodGrid::new(vec![FoodZone::new(na::Point2::new(0.5, 0.5), 0.1, 0)]);
+		let result = Simulation::try_with_food_grid(
+			SimulationConfig::default(),
+			&grid,
+			&mut ChaCha8Rng::seed_from_u64(0),
+		);
+
+		assert!(matches!(result, Err(ConfigError::NoFood)));
+	}
+}
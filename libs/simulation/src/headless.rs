@@ -0,0 +1,85 @@
+use crate::*;
+
+/// Summary of a single generation, as reported by `run_headless()`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationStats {
+	pub generation: usize,
+	pub min_fitness: f32,
+	pub max_fitness: f32,
+	pub mean_fitness: f32,
+	pub median_fitness: f32,
+	/// Most food any animal ate, whatever `FitnessFn` made of it
+	pub best_satiation: usize,
+}
+
+impl SimulationStats {
+	pub(crate) fn new(generation: usize, stats: &ga::PopulationStats, best_satiation: usize) -> Self {
+		Self {
+			generation,
+			min_fitness: stats.min_fitness(),
+			max_fitness: stats.max_fitness(),
+			mean_fitness: stats.mean_fitness(),
+			median_fitness: stats.median_fitness(),
+			best_satiation,
+		}
+	}
+}
+
+/// Runs a simulation seeded with `seed` for `generations` generations, with
+/// no rendering involved, and returns the statistics of each of them.
+pub fn run_headless(seed: u64, generations: usize, config: SimulationConfig) -> Vec<SimulationStats> {
+	let mut rng = ChaCha8Rng::seed_from_u64(seed);
+	let mut simulation = Simulation::new(config, &mut rng);
+	let mut stats = Vec::with_capacity(generations);
+
+	while stats.len() < generations {
+		stats.extend(simulation.step(&mut rng));
+	}
+
+	stats
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn run_headless() {
+		let stats = super::run_headless(42, 2, SimulationConfig::default());
+
+		assert_eq!(stats.len(), 2);
+		assert_eq!(stats[0].generation, 1);
+		assert_eq!(stats[1].generation, 2);
+
+		for stats in &stats {
+			assert!(stats.min_fitness <= stats.mean_fitness);
+			assert!(stats.mean_fitness <= stats.max_fitness);
+		}
+	}
+
+	#[test]
+	fn run_headless_with_no_generations() {
+		assert!(super::run_headless(42, 0, SimulationConfig::default()).is_empty());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serialize() {
+		let stats = SimulationStats {
+			generation: 3,
+			min_fitness: 0.0,
+			max_fitness: 4.0,
+			mean_fitness: 1.5,
+			median_fitness: 1.0,
+			best_satiation: 4,
+		};
+		let json = serde_json::to_string(&stats).unwrap();
+
+		assert_eq!(
+			json,
+			r#"{"generation":3,"min_fitness":0.0,"max_fitness":4.0,"mean_fitness":1.5,"median_fitness":1.0,"best_satiation":4}"#
+		);
+		assert_eq!(serde_json::from_str::<SimulationStats>(&json).unwrap(), stats);
+	}
+}
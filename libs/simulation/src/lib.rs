@@ -1,16 +1,31 @@
 mod animal;
 mod animal_individual;
+mod clock;
+mod config;
 mod food;
+mod food_grid;
+mod headless;
+mod observation;
+mod seeded;
+mod snapshot;
+mod spatial_grid;
 mod world;
 mod eyes;
 mod brain;
+mod fitness;
 
-pub use self::{animal::*, brain::*, eyes::*, food::*, world::*};
+pub use self::{
+	animal::*, brain::*, config::*, eyes::*, fitness::*, food::*, food_grid::*, headless::*, observation::*, seeded::*,
+	snapshot::*, spatial_grid::*,
+	world::*,
+};
 use self::animal_individual::*;
+use self::clock::*;
 use lib_neural_network as nn;
 use lib_genetic_algorithm as ga;
 use nalgebra as na;
-use rand::{Rng, RngCore};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 use std::f32::consts::FRAC_PI_2;
 const SPEED_MIN: f32 = 0.001;
@@ -20,107 +35,1613 @@ const ROTATION_ACCEL: f32 = FRAC_PI_2;
 
 const STEP_EACH_GENERATION: usize = 1000;
 
+const MUTATION_CHANCE: f32 = 0.005;
+const MUTATION_COEFF: f32 = 0.5;
+
+const COLLISION_RADIUS: f32 = 0.01;
+
+const MAX_HISTORY: usize = 1000;
+
+// With a full stomach, an animal can go two widths of the map, i.e. 400
+// steps at full speed
+const ENERGY_DRAIN: f32 = 0.5;
+const FOOD_ENERGY: f32 = 0.25;
+
+// Predators see further than prey, but only what's in front of them
+const PREDATOR_FOV_RANGE: f32 = 0.4;
+const PREDATOR_FOV_ANGLE: f32 = FRAC_PI_2;
+
+// A bit more than `COLLISION_RADIUS`, since prey move as well
+const CATCH_RADIUS: f32 = 0.02;
+const CATCH_PENALTY: f32 = 1.0;
+
+// Applies only to mutation methods that have a temperature
+const MUTATION_COOLING_RATE: f32 = 0.001;
+
+// Share of `target_tick_hz` below which the simulation counts as lagging
+const SCHEDULE_TOLERANCE: f64 = 0.9;
+
 pub struct Simulation {
 	world: World,
 	ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
+	// Predators evolve on their own, by the number of prey caught
+	predator_ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
 	pub age: usize,
+	// Statistics of the last `config.max_history` generations, oldest first
+	history: Vec<SimulationStats>,
+	config: SimulationConfig,
+	fitness_fn: FitnessFn,
+	// Number of foods eaten since creation, and within this generation
+	total_food_eaten: usize,
+	foods_eaten_this_generation: usize,
+	// Foods eaten during the current step, moved elsewhere once every
+	// animal has had its turn
+	pending_food_respawn: Vec<usize>,
+	clock: Clock,
+	// Steps since creation, across generations
+	ticks: usize,
 }
 
 impl Simulation {
+	/// Builds and then runs on nothing but `rng`, so that the same rng
+	/// state (see `from_seed()` and `SeededSimulation`) always leads to the
+	/// same simulation.
 	pub fn random(rng: &mut dyn RngCore) -> Self {
-		let world = World::random(rng);
+		Self::new(SimulationConfig::default(), rng)
+	}
+
+	/// Panics if `config` is invalid, see `try_new()`.
+	pub fn new(config: SimulationConfig, rng: &mut dyn RngCore) -> Self {
+		Self::try_new(config, rng).unwrap_or_else(|err| panic!("{}", err))
+	}
 
+	pub fn try_new(config: SimulationConfig, rng: &mut dyn RngCore) -> Result<Self, ConfigError> {
+		config.validate()?;
+
+		let world = World::random_with_config(rng, &config);
+
+		Ok(Self::with_world(config, world))
+	}
+
+	/// Panics if `config` is invalid or `grid` has no food, see
+	/// `try_with_food_grid()`.
+	pub fn with_food_grid(config: SimulationConfig, grid: &FoodGrid, rng: &mut dyn RngCore) -> Self {
+		Self::try_with_food_grid(config, grid, rng).unwrap_or_else(|err| panic!("{}", err))
+	}
+
+	/// Same as `try_new()`, but with the food clustered within `grid`'s
+	/// zones instead of `config.food_count` pieces spread all over the
+	/// world; see `World::random_with_food_grid()`.
+	pub fn try_with_food_grid(
+		config: SimulationConfig,
+		grid: &FoodGrid,
+		rng: &mut dyn RngCore,
+	) -> Result<Self, ConfigError> {
+		config.validate()?;
+
+		if grid.food_count() == 0 {
+			return Err(ConfigError::NoFood);
+		}
+
+		let world = World::random_with_food_grid(rng, &config, grid);
+
+		Ok(Self::with_world(config, world))
+	}
+
+	fn with_world(config: SimulationConfig, world: World) -> Self {
 		let ga = ga::GeneticAlgorithm::new(
 			ga::RouletteWheelSelection,
 			ga::UniformCrossover,
-			ga::GaussianMutation::new(0.005, 0.5),
+			ga::GaussianMutation::new(config.mutation_chance, config.mutation_coeff),
+		)
+		.with_elitism(config.elite_count);
+		let predator_ga = ga::GeneticAlgorithm::new(
+			ga::RouletteWheelSelection,
+			ga::UniformCrossover,
+			ga::GaussianMutation::new(config.mutation_chance, config.mutation_coeff),
 		);
-		Self {
+		let mut simulation = Self {
 			world,
 			ga,
+			predator_ga,
 			age: 0,
+			history: Vec::new(),
+			config,
+			fitness_fn: FitnessFn::default(),
+			total_food_eaten: 0,
+			foods_eaten_this_generation: 0,
+			pending_food_respawn: Vec::new(),
+			clock: Clock::start(),
+			ticks: 0,
+		};
+
+		simulation.cap_weights();
+		simulation
+	}
+
+	// Brains only change when animals are born, so that's when their
+	// weights get capped, see `SimulationConfig::weight_max_norm`
+	fn cap_weights(&mut self) {
+		if let Some(max_norm) = self.config.weight_max_norm {
+			for animal in self.world.animals.iter_mut().chain(&mut self.world.predators) {
+				animal.brain.nn.apply_max_norm(max_norm);
+			}
 		}
 	}
+
+	/// Replaces how animals are scored at the end of each generation, which
+	/// is by `FitnessFn::Satiation` by default.
+	pub fn with_fitness_fn(mut self, fitness_fn: FitnessFn) -> Self {
+		self.fitness_fn = fitness_fn;
+		self
+	}
+
+	pub fn set_fitness_fn(&mut self, fitness_fn: FitnessFn) {
+		self.fitness_fn = fitness_fn;
+	}
+
+	/// Builds the same simulation for the same `seed`, every time.
+	pub fn from_seed(seed: u64) -> Self {
+		Self::random(&mut ChaCha8Rng::seed_from_u64(seed))
+	}
 	
 	pub fn world(&self) -> &World {
 		&self.world
 	}
 
+	/// Gives direct access to the world, e.g. to set up a specific scenario.
+	pub fn world_mut(&mut self) -> &mut World {
+		&mut self.world
+	}
+
 	pub fn age(&self) -> usize {
 		self.age
 	}
 
+	/// Number of foods eaten since the simulation was created.
+	pub fn total_food_eaten(&self) -> usize {
+		self.total_food_eaten
+	}
+
+	/// Number of foods eaten since the current generation began.
+	pub fn foods_eaten_this_generation(&self) -> usize {
+		self.foods_eaten_this_generation
+	}
+
+	/// Number of animals, which stays the same across generations.
+	pub fn population_size(&self) -> usize {
+		self.world.animals.len()
+	}
+
+	/// Number of foods; eaten ones respawn, so this doesn't change either.
+	pub fn food_count(&self) -> usize {
+		self.world.foods.len()
+	}
+
+	/// Returns `[x, y, rotation]` of every animal, one after another, e.g. to
+	/// upload into a GPU buffer.
+	pub fn animal_position_buffer(&self) -> Vec<f32> {
+		let mut buffer = Vec::with_capacity(3 * self.world.animals.len());
+
+		for animal in &self.world.animals {
+			buffer.extend([animal.position.x, animal.position.y, animal.rotation.angle()]);
+		}
+
+		buffer
+	}
+
+	/// Returns `[x, y]` of every food, one after another.
+	pub fn food_position_buffer(&self) -> Vec<f32> {
+		let mut buffer = Vec::with_capacity(2 * self.world.foods.len());
+
+		for food in &self.world.foods {
+			buffer.extend([food.position.x, food.position.y]);
+		}
+
+		buffer
+	}
+
 	pub fn generation(&self) -> usize {
 		self.ga.generation()
 	}
 
+	/// Returns the chromosome of the animal that has eaten the most so far.
+	pub fn best_chromosome(&self) -> ga::Chromosome {
+		self.world
+			.animals
+			.iter()
+			.max_by_key(|animal| animal.satiation)
+			.expect("got an empty world")
+			.as_chromosome()
+	}
+
+	/// Replaces the animal that has eaten the least so far with one built
+	/// from `best`, e.g. the best chromosome of an earlier run.
+	pub fn inject_best_from_previous_run(&mut self, best: ga::Chromosome, rng: &mut dyn RngCore) {
+		let worst = self
+			.world
+			.animals
+			.iter_mut()
+			.min_by_key(|animal| animal.satiation)
+			.expect("got an empty world");
+
+		assert_eq!(
+			best.len(),
+			worst.as_chromosome().len(),
+			"got a chromosome of the wrong length",
+		);
+
+		*worst = Animal::from_chromosome(best, Eye::from_config(&self.config.eye), rng);
+		self.cap_weights();
+	}
+
+	/// Returns whether the best satiation (see
+	/// `SimulationStats::best_satiation`) of the last `window` generations
+	/// has a standard deviation below `threshold`, i.e. whether evolution
+	/// seems to have stopped making progress.
+	///
+	/// Panics if `window` is zero or longer than the history kept, see
+	/// `SimulationConfig::max_history`.
+	pub fn is_converged(&self, window: usize, threshold: f32) -> bool {
+		assert!(window > 0);
+		assert!(
+			window <= self.config.max_history,
+			"got a window of {} generations, but only {} are kept",
+			window,
+			self.config.max_history,
+		);
+
+		if self.history.len() < window {
+			return false;
+		}
+
+		let window = &self.history[self.history.len() - window..];
+		let count = window.len() as f32;
+		let mean = window.iter().map(|stats| stats.best_satiation as f32).sum::<f32>() / count;
+		let variance = window
+			.iter()
+			.map(|stats| (stats.best_satiation as f32 - mean).powi(2))
+			.sum::<f32>()
+			/ count;
+
+		variance.sqrt() < threshold
+	}
+
+	/// Statistics of the most recent generations, oldest first; how many
+	/// are kept is up to `SimulationConfig::max_history`.
+	pub fn stats_history(&self) -> &[SimulationStats] {
+		&self.history
+	}
+
+	/// Statistics of the last generation that has ended, if any.
+	pub fn latest_stats(&self) -> Option<&SimulationStats> {
+		self.history.last()
+	}
+
+	/// Whether the next step is the last one of the current generation;
+	/// `step()` reports when it's actually over.
 	pub fn is_last_run(&self) -> bool {
-		self.age == STEP_EACH_GENERATION - 1
+		self.age == self.config.steps_per_generation - 1
+	}
+
+	/// Seconds of wall-clock time since the simulation was created.
+	pub fn real_time_elapsed(&self) -> f64 {
+		self.clock.elapsed()
+	}
+
+	/// Mean wall-clock seconds a step has taken so far, or `None` before the
+	/// first step.
+	pub fn tick_duration_estimate(&self) -> Option<f64> {
+		(self.ticks > 0).then(|| self.real_time_elapsed() / self.ticks as f64)
+	}
+
+	/// Whether the simulation steps noticeably slower than `target_tick_hz`
+	/// (see `SimulationConfig`), e.g. so that the frontend can skip frames.
+	///
+	/// Nothing counts as late until at least one tick's worth of time has
+	/// passed.
+	pub fn is_behind_schedule(&self) -> bool {
+		let elapsed = self.real_time_elapsed();
+
+		if elapsed < 1.0 / self.config.target_tick_hz {
+			return false;
+		}
+
+		(self.ticks as f64 / elapsed) < self.config.target_tick_hz * SCHEDULE_TOLERANCE
+	}
+
+	/// Returns the statistics of the generation that has just ended, if
+	/// this step ended one.
+	pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<SimulationStats> {
+		self.process_step(rng, None)?;
+		self.latest_stats().cloned()
+	}
+
+	/// Steps until the current generation ends, however far into it the
+	/// simulation already is, and returns that generation's statistics;
+	/// the new population is left in place, at age 0.
+	pub fn train(&mut self, rng: &mut dyn RngCore) -> SimulationStats {
+		loop {
+			if let Some(stats) = self.step(rng) {
+				return stats;
+			}
+		}
+	}
+
+	/// Keeps evolving until `detector` reports that the population has
+	/// converged and returns the number of generations it took, or `None` if
+	/// it hasn't within `max_generations`.
+	///
+	/// The detector looks at `stats_history()`, so generations from before
+	/// this call count as well, and it can't look further back than
+	/// `SimulationConfig::max_history` generations.
+	pub fn train_until_converged(
+		&mut self,
+		rng: &mut dyn RngCore,
+		detector: &ga::ConvergenceDetector,
+		max_generations: usize,
+	) -> Option<usize> {
+		for generations in 1..=max_generations {
+			self.train(rng);
+
+			let mean_fitnesses: Vec<_> = self.history.iter().map(|stats| stats.mean_fitness).collect();
+
+			if detector.has_converged_by_means(&mean_fitnesses) {
+				return Some(generations);
+			}
+		}
+
+		None
 	}
 
-	pub fn step(&mut self, rng: &mut dyn RngCore) {
+	// Returns the statistics of the generation that has just ended, if any;
+	// `actions` take the place of the brains' outputs, see `act()`
+	fn process_step(
+		&mut self,
+		rng: &mut dyn RngCore,
+		actions: Option<&[(f32, f32)]>,
+	) -> Option<ga::PopulationStats> {
 		self.process_collision(rng);
-		self.process_brains();
+		self.process_catches(rng);
+
+		match actions {
+			Some(actions) => {
+				for (animal, &(speed, rotation)) in self.world.animals.iter_mut().zip(actions) {
+					animal.accelerate(&self.config, speed, rotation);
+				}
+			}
+			None => self.process_brains(),
+		}
+
+		self.process_predator_brains();
+
 		self.process_movement();
 
 		self.age += 1;
-		if self.age >= STEP_EACH_GENERATION {
+		self.ticks += 1;
+		if self.age >= self.config.steps_per_generation {
 			self.age = 0;
-			self.evolve(rng);
+			Some(self.evolve(rng))
+		} else {
+			None
 		}
 	}
 
 	fn process_movement(&mut self) {
-		for animal in &mut self.world.animals {
-			animal.position += animal.rotation * na::Vector2::new(0.0, animal.speed);
-
-			animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
-			animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+		for animal in self.world.animals.iter_mut().chain(&mut self.world.predators) {
+			animal.advance(&self.config);
 		}
 	}
 
-	fn process_collision(&mut self, rng: &mut dyn RngCore) {
-		for animal in &mut self.world.animals {
-			for food in &mut self.world.foods {
+	// Returns `(animal, food)` index pairs of every food eaten; each food
+	// can be eaten only once per step
+	fn process_collision(&mut self, rng: &mut dyn RngCore) -> Vec<(usize, usize)> {
+		let grid = self.spatial_grid();
+		let mut collisions = Vec::new();
+
+		for (animal_index, animal) in self.world.animals.iter_mut().enumerate() {
+			if !animal.is_alive() {
+				continue;
+			}
+
+			for food_index in grid.query_circle(animal.position, self.config.collision_radius) {
+				if self.pending_food_respawn.contains(&food_index) {
+					continue;
+				}
+
+				let food = &self.world.foods[food_index];
 				let distance = na::distance(&animal.position, &food.position);
 
-				if distance < 0.01 {
-					animal.satiation += 1;
-					food.position = rng.gen();
+				if distance < self.config.collision_radius {
+					match food.kind {
+						FoodKind::Nutritious => {
+							animal.satiation += 1;
+							animal.energy = (animal.energy + self.config.food_energy).min(1.0);
+						}
+						FoodKind::Poison => {
+							animal.satiation = animal.satiation.saturating_sub(1);
+							animal.energy = (animal.energy - self.config.food_energy).max(0.0);
+
+							if !animal.is_alive() {
+								animal.speed = 0.0;
+							}
+						}
+					}
+
+					collisions.push((animal_index, food_index));
+					self.pending_food_respawn.push(food_index);
 				}
 			}
 		}
+
+		for food_index in self.pending_food_respawn.drain(..) {
+			self.world.foods[food_index].respawn(rng);
+		}
+
+		self.total_food_eaten += collisions.len();
+		self.foods_eaten_this_generation += collisions.len();
+
+		collisions
+	}
+
+	// Buckets the foods by position; cells are about as large as the
+	// farthest anyone looks for food, so that a query covers few of them
+	fn spatial_grid(&self) -> SpatialGrid {
+		let cell_size = self.config.eye.fov_range.max(self.config.collision_radius);
+
+		SpatialGrid::new(cell_size, self.world.foods.iter().map(Food::position))
+	}
+
+	// Returns `(predator, prey)` index pairs of every prey caught; each prey
+	// gets caught at most once per step and respawns somewhere else
+	fn process_catches(&mut self, rng: &mut dyn RngCore) -> Vec<(usize, usize)> {
+		let mut catches: Vec<(usize, usize)> = Vec::new();
+
+		for (predator_index, predator) in self.world.predators.iter_mut().enumerate() {
+			if !predator.is_alive() {
+				continue;
+			}
+
+			for (prey_index, prey) in self.world.animals.iter_mut().enumerate() {
+				if !prey.is_alive() || catches.iter().any(|&(_, caught)| caught == prey_index) {
+					continue;
+				}
+
+				let distance = na::distance(&predator.position, &prey.position);
+
+				if distance < self.config.catch_radius {
+					predator.satiation += 1;
+					predator.energy = (predator.energy + self.config.food_energy).min(1.0);
+					prey.times_caught += 1;
+					catches.push((predator_index, prey_index));
+				}
+			}
+		}
+
+		for &(_, prey_index) in &catches {
+			self.world.animals[prey_index].position = rng.gen();
+		}
+
+		catches
 	}
 
 	fn process_brains(&mut self) {
+		let grid = self.spatial_grid();
+
 		for animal in &mut self.world.animals {
-			let vision = animal.eye.process_vision(
+			if !animal.is_alive() {
+				continue;
+			}
+
+			let vision = animal.eye.process_vision_with_grid(
 				animal.position,
 				animal.rotation,
-				&self.world.foods
+				&self.world.foods,
+				&grid,
 			);
 
 			let response = animal.brain.nn.propagate(vision);
 
-			let speed = response[0].clamp(-SPEED_ACCEL, SPEED_ACCEL);
-			let rotation = response[1].clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
+			animal.accelerate(&self.config, response[0], response[1]);
+		}
+
+	}
 
-			animal.speed = (animal.speed + speed).clamp(SPEED_MIN, SPEED_MAX);
-			animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
+	// Predators see living prey the way prey see nutritious food
+	fn process_predator_brains(&mut self) {
+		if self.world.predators.is_empty() {
+			return;
 		}
 
+		let prey: Vec<_> = self
+			.world
+			.animals
+			.iter()
+			.filter(|animal| animal.is_alive())
+			.map(|animal| Food::new(animal.position))
+			.collect();
+
+		for predator in &mut self.world.predators {
+			if !predator.is_alive() {
+				continue;
+			}
+
+			let vision = predator.eye.process_vision(predator.position, predator.rotation, &prey);
+
+			let response = predator.brain.nn.propagate(vision);
+
+			predator.accelerate(&self.config, response[0], response[1]);
+		}
 	}
 
-	fn evolve(&mut self, rng: &mut dyn RngCore) {
+	fn evolve(&mut self, rng: &mut dyn RngCore) -> ga::PopulationStats {
 		self.age = 0;
-		let current_population: Vec<_> = self.world.animals.iter().map(AnimalIndividual::from_animal).collect();
+		self.foods_eaten_this_generation = 0;
+		let current_population: Vec<_> = self
+			.world
+			.animals
+			.iter()
+			.map(|animal| AnimalIndividual::from_animal(animal, &self.fitness_fn, self.config.catch_penalty))
+			.collect();
+		let stats = ga::PopulationStats::new(&current_population);
+		let best_satiation = self.world.animals.iter().map(|animal| animal.satiation).max().unwrap_or(0);
+		self.history.push(SimulationStats::new(self.generation(), &stats, best_satiation));
+		if self.history.len() > self.config.max_history {
+			self.history.drain(..self.history.len() - self.config.max_history);
+		}
 		let evovled_population = self.ga.evolve(rng, &current_population);
-		self.world.animals = evovled_population.into_iter().map(|individual| individual.into_animal(rng)).collect();
+
+		self.world.animals = evovled_population
+			.into_iter()
+			.enumerate()
+			.map(|(i, individual)| {
+				// Elites, which come first, get older instead of being born
+				// anew; children that happen to match a parent don't
+				let age = if i < self.config.elite_count {
+					current_population
+						.iter()
+						.zip(&self.world.animals)
+						.find(|(previous, _)| previous.has_same_genes(&individual))
+						.map_or(0, |(_, animal)| animal.age + 1)
+				} else {
+					0
+				};
+
+				let mut animal = individual.into_animal(Eye::from_config(&self.config.eye), rng);
+				animal.age = age;
+				animal
+			})
+			.collect();
 
 		for food in &mut self.world.foods {
-			food.position = rng.gen();
+			food.respawn(rng);
 		}
+
+		self.evolve_predators(rng);
+		self.cap_weights();
+
+		if let Some(mutation) = self.ga.mutation_method_mut().as_temperature_mut() {
+			mutation.cool(MUTATION_COOLING_RATE);
+		}
+
+		stats
+	}
+
+	fn evolve_predators(&mut self, rng: &mut dyn RngCore) {
+		if self.world.predators.is_empty() {
+			return;
+		}
+
+		let population: Vec<_> = self
+			.world
+			.predators
+			.iter()
+			.map(|predator| AnimalIndividual::from_animal(predator, &FitnessFn::Satiation, 0.0))
+			.collect();
+
+		self.world.predators = self
+			.predator_ga
+			.evolve(rng, &population)
+			.into_iter()
+			.map(|individual| individual.into_animal(Eye::from_config(&self.config.predator_eye), rng))
+			.collect();
 	}
 
 }
 
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::f32::consts::PI;
+
+	#[test]
+	fn from_seed_is_deterministic() {
+		let a = Simulation::from_seed(42);
+		let b = Simulation::from_seed(42);
+
+		assert_eq!(a.world.animals.len(), b.world.animals.len());
+		for (a, b) in a.world.animals.iter().zip(&b.world.animals) {
+			assert_eq!(a.position.x.to_bits(), b.position.x.to_bits());
+			assert_eq!(a.position.y.to_bits(), b.position.y.to_bits());
+			assert_eq!(a.rotation.angle().to_bits(), b.rotation.angle().to_bits());
+			assert_eq!(a.brain.nn, b.brain.nn);
+		}
+
+		assert_eq!(a.world.foods.len(), b.world.foods.len());
+		for (a, b) in a.world.foods.iter().zip(&b.world.foods) {
+			assert_eq!(a.position.x.to_bits(), b.position.x.to_bits());
+			assert_eq!(a.position.y.to_bits(), b.position.y.to_bits());
+		}
+	}
+
+	#[test]
+	fn from_seed_depends_on_seed() {
+		let a = Simulation::from_seed(42);
+		let b = Simulation::from_seed(43);
+
+		assert_ne!(a.world.animals[0].position, b.world.animals[0].position);
+	}
+
+	fn same_genes(a: &ga::Chromosome, b: &ga::Chromosome) -> bool {
+		a.iter().eq(b.iter())
+	}
+
+	#[test]
+	fn best_chromosome() {
+		let mut simulation = Simulation::from_seed(42);
+		simulation.world.animals[7].satiation = 3;
+
+		assert!(same_genes(
+			&simulation.best_chromosome(),
+			&simulation.world.animals[7].as_chromosome(),
+		));
+	}
+
+	#[test]
+	fn is_not_behind_schedule_right_after_creation() {
+		let simulation = Simulation::from_seed(42);
+
+		assert!(!simulation.is_behind_schedule());
+		assert!(simulation.real_time_elapsed() >= 0.0);
+		assert_eq!(simulation.tick_duration_estimate(), None);
+	}
+
+	#[test]
+	fn is_behind_schedule_when_not_stepped() {
+		let simulation = Simulation::new(
+			SimulationConfig {
+				target_tick_hz: 1e6,
+				..Default::default()
+			},
+			&mut ChaCha8Rng::seed_from_u64(0),
+		);
+
+		std::thread::sleep(std::time::Duration::from_millis(1));
+
+		assert!(simulation.is_behind_schedule());
+	}
+
+	#[test]
+	fn respects_config() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			animal_count: 7,
+			food_count: 42,
+			speed_min: 0.002,
+			speed_max: 0.003,
+			steps_per_generation: 10,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		for _ in 0..25 {
+			simulation.step(&mut rng);
+
+			assert_eq!(simulation.world().animals().len(), 7);
+			assert_eq!(simulation.world().food().len(), 42);
+
+			for animal in simulation.world().animals() {
+				assert!((0.002..=0.003).contains(&animal.speed()));
+			}
+		}
+
+		assert_eq!(simulation.generation(), 3);
+		assert_eq!(simulation.age(), 5);
+	}
+
+	#[test]
+	fn population_size_and_food_count() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			animal_count: 7,
+			food_count: 42,
+			steps_per_generation: 5,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		for _ in 0..12 {
+			assert_eq!(simulation.population_size(), 7);
+			assert_eq!(simulation.food_count(), 42);
+
+			simulation.step(&mut rng);
+		}
+	}
+
+	#[test]
+	fn weight_max_norm() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			predator_count: 2,
+			weight_max_norm: Some(0.5),
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		let assert_capped = |simulation: &Simulation| {
+			for animal in simulation.world.animals.iter().chain(&simulation.world.predators) {
+				for neuron in animal.brain.nn.layers().iter().flat_map(|layer| layer.neurons()) {
+					let norm = neuron.weights().iter().map(|weight| weight * weight).sum::<f32>().sqrt();
+
+					assert!(norm <= 0.5 + 1e-6);
+				}
+			}
+		};
+
+		assert_capped(&simulation);
+
+		simulation.evolve(&mut rng);
+		assert_capped(&simulation);
+	}
+
+	#[test]
+	fn try_new_rejects_invalid_configs() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+		let mut try_new = |config| Simulation::try_new(config, &mut rng).err();
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				animal_count: 0,
+				..Default::default()
+			}),
+			Some(ConfigError::NoAnimals),
+		);
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				speed_min: 0.5,
+				speed_max: 0.1,
+				..Default::default()
+			}),
+			Some(ConfigError::InvalidSpeedRange { min: 0.5, max: 0.1 }),
+		);
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				steps_per_generation: 0,
+				..Default::default()
+			}),
+			Some(ConfigError::EmptyGeneration),
+		);
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				max_history: 0,
+				..Default::default()
+			}),
+			Some(ConfigError::NoHistory),
+		);
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				eye: EyeConfig {
+					cells: 0,
+					..Default::default()
+				},
+				..Default::default()
+			}),
+			Some(ConfigError::Eye(EyeError::NoCells)),
+		);
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				poison_ratio: 1.5,
+				..Default::default()
+			}),
+			Some(ConfigError::InvalidPoisonRatio(1.5)),
+		);
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				weight_max_norm: Some(-1.0),
+				..Default::default()
+			}),
+			Some(ConfigError::InvalidWeightMaxNorm(-1.0)),
+		);
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				catch_radius: 0.0,
+				..Default::default()
+			}),
+			Some(ConfigError::InvalidCatchRadius(0.0)),
+		);
+
+		assert_eq!(try_new(SimulationConfig::default()), None);
+	}
+
+	#[test]
+	fn try_new_rejects_nan_configs() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+		let mut try_new = |config| Simulation::try_new(config, &mut rng).err();
+
+		assert!(matches!(
+			try_new(SimulationConfig {
+				collision_radius: f32::NAN,
+				..Default::default()
+			}),
+			Some(ConfigError::InvalidCollisionRadius(radius)) if radius.is_nan()
+		));
+
+		assert!(matches!(
+			try_new(SimulationConfig {
+				catch_radius: f32::NAN,
+				..Default::default()
+			}),
+			Some(ConfigError::InvalidCatchRadius(radius)) if radius.is_nan()
+		));
+
+		assert!(matches!(
+			try_new(SimulationConfig {
+				catch_penalty: f32::NAN,
+				..Default::default()
+			}),
+			Some(ConfigError::NegativeCatchPenalty(penalty)) if penalty.is_nan()
+		));
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				energy_drain: f32::NAN,
+				..Default::default()
+			}),
+			Some(ConfigError::NegativeEnergy),
+		);
+
+		assert_eq!(
+			try_new(SimulationConfig {
+				food_energy: f32::NAN,
+				..Default::default()
+			}),
+			Some(ConfigError::NegativeEnergy),
+		);
+
+		assert!(matches!(
+			try_new(SimulationConfig {
+				target_tick_hz: f64::NAN,
+				..Default::default()
+			}),
+			Some(ConfigError::InvalidTickRate(hz)) if hz.is_nan()
+		));
+	}
+
+	#[test]
+	#[should_panic(expected = "got no food")]
+	fn new_panics_on_invalid_configs() {
+		let config = SimulationConfig {
+			food_count: 0,
+			..Default::default()
+		};
+
+		Simulation::new(config, &mut ChaCha8Rng::seed_from_u64(0));
+	}
+
+	#[test]
+	fn inject_best_from_previous_run() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let best = Simulation::from_seed(1).best_chromosome();
+
+		let config = SimulationConfig {
+			elite_count: 1,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+		for animal in &mut simulation.world.animals {
+			animal.satiation = 1;
+		}
+		simulation.world.animals[3].satiation = 0;
+
+		simulation.inject_best_from_previous_run(best.clone(), &mut rng);
+		assert!(same_genes(&simulation.world.animals[3].as_chromosome(), &best));
+
+		simulation.world.animals[3].satiation = 10;
+		simulation.evolve(&mut rng);
+
+		assert!(simulation
+			.world
+			.animals
+			.iter()
+			.any(|animal| same_genes(&animal.as_chromosome(), &best)));
+	}
+
+	#[test]
+	#[should_panic(expected = "got a chromosome of the wrong length")]
+	fn inject_best_from_previous_run_validates_length() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+
+		simulation.inject_best_from_previous_run(ga::Chromosome::new(vec![0.0; 3]), &mut rng);
+	}
+
+	#[test]
+	fn elites_get_older() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			elite_count: 1,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		assert!(simulation.world.animals.iter().all(|animal| animal.age() == 0));
+
+		for generation in 1..=3 {
+			for animal in &mut simulation.world.animals {
+				animal.satiation = 1;
+			}
+			simulation.world.animals[0].satiation = 100;
+
+			simulation.evolve(&mut rng);
+
+			// Elites come first
+			assert_eq!(simulation.world.animals[0].age(), generation);
+		}
+	}
+
+	#[test]
+	fn children_matching_a_parent_are_born_anew() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			elite_count: 1,
+			mutation_chance: 0.0,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		// Without mutation, every child of these clones gets their genes
+		let chromosome = simulation.world.animals[0].as_chromosome();
+
+		for animal in &mut simulation.world.animals {
+			animal.brain = Brain::from_chromosome(chromosome.clone(), &animal.eye);
+		}
+
+		simulation.evolve(&mut rng);
+
+		let animals = &simulation.world.animals;
+
+		assert!(animals.iter().all(|animal| animal.as_chromosome().iter().eq(chromosome.iter())));
+		assert_eq!(animals[0].age(), 1);
+		assert!(animals[1..].iter().all(|animal| animal.age() == 0));
+	}
+
+	#[test]
+	fn position_buffers() {
+		let simulation = Simulation::from_seed(0);
+		let animals = simulation.animal_position_buffer();
+		let foods = simulation.food_position_buffer();
+
+		assert_eq!(animals.len(), 3 * simulation.world().animals().len());
+		assert_eq!(foods.len(), 2 * simulation.world().food().len());
+
+		for (chunk, animal) in animals.chunks(3).zip(simulation.world().animals()) {
+			assert_eq!(chunk, [animal.position().x, animal.position().y, animal.rotation().angle()]);
+		}
+
+		for (chunk, food) in foods.chunks(2).zip(simulation.world().food()) {
+			assert_eq!(chunk, [food.position().x, food.position().y]);
+		}
+	}
+
+	#[test]
+	fn animal_placed_on_food_eats_it() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+		let position = na::Point2::new(0.5, 0.5);
+
+		simulation.world_mut().set_animal_position(0, position);
+		simulation.world_mut().set_food_position(0, position);
+
+		simulation.step(&mut rng);
+
+		assert_eq!(simulation.world().animals()[0].satiation, 1);
+		assert_ne!(simulation.world().food()[0].position(), position);
+	}
+
+	#[test]
+	fn process_collision_reports_each_food_once() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			animal_count: 2,
+			food_count: 1,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+		let position = na::Point2::new(0.5, 0.5);
+
+		simulation.world_mut().set_animal_position(0, position);
+		simulation.world_mut().set_animal_position(1, na::Point2::new(0.1, 0.1));
+		simulation.world_mut().set_food_position(0, position);
+
+		assert_eq!(simulation.process_collision(&mut rng), vec![(0, 0)]);
+		assert_ne!(simulation.world().food()[0].position(), position);
+
+		// Only the first animal to reach a food gets to eat it
+		simulation.world_mut().set_animal_position(1, position);
+		simulation.world_mut().set_food_position(0, position);
+
+		assert_eq!(simulation.process_collision(&mut rng), vec![(0, 0)]);
+		assert_eq!(simulation.world().animals()[0].satiation, 2);
+		assert_eq!(simulation.world().animals()[1].satiation, 0);
+		assert!(simulation.pending_food_respawn.is_empty());
+	}
+
+	#[test]
+	fn brains_can_slow_down_and_turn_both_ways() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			animal_count: 1,
+			speed_accel: 0.001,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		// With every weight at zero, the outputs are just tanh of the output
+		// layer's biases, which come last: one plus a weight per hidden
+		// neuron each
+		let cells = simulation.world.animals[0].eye.cells();
+		let topology = Brain::topology(cells);
+		let mut weights = vec![0.0; nn::LayerTopology::param_count(&topology)];
+		let len = weights.len();
+		weights[len - 2 * (1 + 2 * cells)] = -1.0;
+		weights[len - (1 + 2 * cells)] = -1.0;
+
+		let animal = &mut simulation.world.animals[0];
+		animal.brain = Brain {
+			nn: nn::Network::from_weights(&topology, weights),
+		};
+		animal.speed = 0.004;
+		let rotation = animal.rotation.angle();
+
+		simulation.process_brains();
+
+		let animal = &simulation.world.animals[0];
+		assert_eq!(animal.speed, 0.003);
+		assert!(animal.rotation.angle() < rotation || animal.rotation.angle() - rotation > PI);
+	}
+
+	#[test]
+	fn fitness_fn_decides_selection() {
+		let config = SimulationConfig {
+			elite_count: 1,
+			..Default::default()
+		};
+
+		let evolve = |fitness_fn| {
+			let mut rng = ChaCha8Rng::seed_from_u64(0);
+			let mut simulation = Simulation::new(config.clone(), &mut rng).with_fitness_fn(fitness_fn);
+
+			simulation.world.animals[0].satiation = 5;
+			simulation.world.animals[0].distance_traveled = 4.0;
+			simulation.world.animals[1].satiation = 3;
+			let genes = [0, 1].map(|i| simulation.world.animals[i].as_chromosome());
+
+			simulation.evolve(&mut rng);
+
+			// Elites come first
+			let elite = simulation.world.animals[0].as_chromosome();
+			genes.iter().position(|genes| same_genes(genes, &elite))
+		};
+
+		assert_eq!(evolve(FitnessFn::Satiation), Some(0));
+		assert_eq!(evolve(FitnessFn::SatiationMinusDistance { weight: 1.0 }), Some(1));
+		assert_eq!(evolve(FitnessFn::custom(|animal| (animal.satiation == 3) as u8 as f32)), Some(1));
+	}
+
+	#[test]
+	fn evolve_when_nobody_scores() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+		for fitness_fn in [
+			FitnessFn::Satiation,
+			FitnessFn::SatiationMinusDistance { weight: 1.0 },
+			FitnessFn::custom(|_| -1.0),
+		] {
+			let mut simulation = Simulation::random(&mut rng).with_fitness_fn(fitness_fn);
+
+			for animal in &mut simulation.world.animals {
+				animal.distance_traveled = 2.0;
+			}
+
+			let stats = simulation.evolve(&mut rng);
+
+			assert_eq!(stats.max_fitness(), 0.0);
+			assert_eq!(simulation.generation(), 2);
+		}
+	}
+
+	#[test]
+	fn animals_track_distance_traveled() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+
+		simulation.act(&mut rng, &vec![(0.0, 0.0); simulation.population_size()]);
+		simulation.act(&mut rng, &vec![(0.0, 0.0); simulation.population_size()]);
+
+		for animal in simulation.world().animals() {
+			assert_eq!(animal.distance_traveled(), 2.0 * animal.speed());
+		}
+	}
+
+	#[test]
+	fn animals_starve_without_food() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			animal_count: 1,
+			food_count: 1,
+			speed_min: 0.005,
+			speed_max: 0.005,
+			energy_drain: 2.0,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		// Every step at full speed costs 1% of a full stomach
+		simulation.world.foods[0].position = na::Point2::new(-10.0, -10.0);
+
+		for _ in 0..99 {
+			simulation.step(&mut rng);
+		}
+
+		let animal = &simulation.world.animals[0];
+		assert!(animal.is_alive());
+		assert!(animal.energy() < 0.02);
+
+		simulation.step(&mut rng);
+		simulation.step(&mut rng);
+
+		let animal = &simulation.world.animals[0];
+		let position = animal.position();
+		assert!(!animal.is_alive());
+		assert_eq!(animal.speed(), 0.0);
+		assert!((100..=101).contains(&animal.survival_time()));
+
+		// Dead animals stay put
+		simulation.step(&mut rng);
+		assert_eq!(simulation.world.animals[0].position(), position);
+	}
+
+	#[test]
+	fn eating_refills_energy() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+		let position = na::Point2::new(0.5, 0.5);
+
+		simulation.world.animals[0].energy = 0.5;
+		simulation.world_mut().set_animal_position(0, position);
+		simulation.world_mut().set_food_position(0, position);
+
+		simulation.process_collision(&mut rng);
+
+		assert_eq!(simulation.world.animals[0].energy(), 0.75);
+	}
+
+	#[test]
+	fn eating_poison_hurts() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+		let position = na::Point2::new(0.5, 0.5);
+
+		simulation.world.animals[0].satiation = 2;
+		simulation.world.animals[0].energy = 0.5;
+		simulation.world.foods[0].kind = FoodKind::Poison;
+		simulation.world_mut().set_animal_position(0, position);
+		simulation.world_mut().set_food_position(0, position);
+
+		simulation.process_collision(&mut rng);
+
+		let animal = &simulation.world.animals[0];
+		assert_eq!(animal.satiation, 1);
+		assert_eq!(animal.energy(), 0.25);
+		assert_eq!(simulation.world.foods[0].kind(), FoodKind::Poison);
+	}
+
+	#[test]
+	fn predators_catch_prey() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			predator_count: 2,
+			catch_penalty: 2.0,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+		let position = na::Point2::new(0.5, 0.5);
+
+		assert_eq!(simulation.world().predators().len(), 2);
+		assert_eq!(simulation.world().predators()[0].eye.config(), simulation.config.predator_eye);
+
+		simulation.world.animals[0].satiation = 3;
+		simulation.world_mut().set_animal_position(0, position);
+		simulation.world_mut().set_predator_position(0, position);
+		simulation.world_mut().set_predator_position(1, position);
+
+		// Only one predator gets to catch the prey, which then respawns
+		assert_eq!(simulation.process_catches(&mut rng), vec![(0, 0)]);
+		assert_ne!(simulation.world().animals()[0].position(), position);
+
+		let predators = simulation.world().predators();
+		assert_eq!(predators[0].fitness(), 1);
+		assert_eq!(predators[1].fitness(), 0);
+
+		let prey = &simulation.world().animals()[0];
+		assert_eq!(prey.times_caught(), 1);
+
+		let individual = AnimalIndividual::from_animal(prey, &FitnessFn::Satiation, 2.0);
+		assert_eq!(ga::Individual::fitness(&individual), 1.0);
+	}
+
+	#[test]
+	fn predators_evolve() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			predator_count: 3,
+			steps_per_generation: 10,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		for _ in 0..30 {
+			simulation.step(&mut rng);
+		}
+
+		assert_eq!(simulation.generation(), 4);
+		assert_eq!(simulation.world().predators().len(), 3);
+		assert!(simulation.world().predators().iter().all(|predator| predator.fitness() == 0));
+	}
+
+	#[test]
+	fn evolve_when_every_prey_got_caught() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			predator_count: 2,
+			catch_penalty: 1.0,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		for animal in &mut simulation.world.animals {
+			animal.satiation = 1;
+			animal.times_caught = 1;
+		}
+
+		let stats = simulation.evolve(&mut rng);
+
+		assert_eq!(stats.max_fitness(), 0.0);
+		assert_eq!(simulation.world().predators().len(), 2);
+	}
+
+	#[test]
+	fn spatial_grid_matches_brute_force() {
+		for seed in 0..10 {
+			let mut rng = ChaCha8Rng::seed_from_u64(seed);
+			let config = SimulationConfig {
+				animal_count: 200,
+				food_count: 500,
+				poison_ratio: 0.2,
+				collision_radius: 0.03,
+				..Default::default()
+			};
+			let mut simulation = Simulation::new(config, &mut rng);
+			let grid = simulation.spatial_grid();
+
+			for animal in &simulation.world.animals {
+				let foods = &simulation.world.foods;
+
+				assert_eq!(
+					animal.eye.process_vision_with_grid(animal.position, animal.rotation, foods, &grid),
+					animal.eye.process_vision(animal.position, animal.rotation, foods),
+				);
+			}
+
+			let mut eaten = Vec::new();
+			let mut expected = Vec::new();
+
+			for (animal_index, animal) in simulation.world.animals.iter().enumerate() {
+				for (food_index, food) in simulation.world.foods.iter().enumerate() {
+					if !eaten.contains(&food_index) && na::distance(&animal.position, &food.position) < 0.03 {
+						eaten.push(food_index);
+						expected.push((animal_index, food_index));
+					}
+				}
+			}
+
+			assert!(!expected.is_empty());
+			assert_eq!(simulation.process_collision(&mut rng), expected);
+		}
+	}
+
+	#[test]
+	fn poison_ratio() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			food_count: 40,
+			poison_ratio: 0.25,
+			..Default::default()
+		};
+		let simulation = Simulation::new(config, &mut rng);
+
+		let poison = simulation
+			.world()
+			.food()
+			.iter()
+			.filter(|food| food.kind() == FoodKind::Poison)
+			.count();
+
+		assert_eq!(poison, 10);
+	}
+
+	#[test]
+	fn counts_food_eaten() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			animal_count: 2,
+			food_count: 2,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		let mut feed = |simulation: &mut Simulation, times| {
+			for _ in 0..times {
+				for i in 0..2 {
+					let position = na::Point2::new(0.25 + 0.5 * i as f32, 0.5);
+					simulation.world_mut().set_animal_position(i, position);
+					simulation.world_mut().set_food_position(i, position);
+				}
+
+				assert_eq!(simulation.process_collision(&mut rng).len(), 2);
+			}
+		};
+
+		feed(&mut simulation, 3);
+		assert_eq!(simulation.total_food_eaten(), 6);
+		assert_eq!(simulation.foods_eaten_this_generation(), 6);
+
+		simulation.evolve(&mut ChaCha8Rng::seed_from_u64(1));
+		assert_eq!(simulation.total_food_eaten(), 6);
+		assert_eq!(simulation.foods_eaten_this_generation(), 0);
+
+		feed(&mut simulation, 2);
+		assert_eq!(simulation.total_food_eaten(), 10);
+		assert_eq!(simulation.foods_eaten_this_generation(), 4);
+	}
+
+	#[test]
+	fn evolve_cools_mutation_down() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+		simulation.ga.set_mutation_method(ga::BoltzmannMutation::new(0.01, 0.5));
+
+		for _ in 0..2 {
+			simulation.world.animals[0].satiation = 1;
+			simulation.evolve(&mut rng);
+		}
+
+		let mutation = simulation.ga.mutation_method_mut().as_temperature_mut().unwrap();
+		assert_eq!(mutation.temperature(), 0.999 * 0.999);
+	}
+
+	fn history(best_satiations: impl IntoIterator<Item = usize>) -> Vec<SimulationStats> {
+		best_satiations
+			.into_iter()
+			.enumerate()
+			.map(|(i, best)| SimulationStats {
+				generation: i + 1,
+				min_fitness: 0.0,
+				max_fitness: best as f32,
+				mean_fitness: best as f32 / 2.0,
+				median_fitness: best as f32 / 2.0,
+				best_satiation: best,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn eye_config_shapes_animals() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+		let mut simulation = |cells| {
+			let config = SimulationConfig {
+				eye: EyeConfig {
+					cells,
+					..Default::default()
+				},
+				..Default::default()
+			};
+
+			Simulation::new(config, &mut rng)
+		};
+
+		let small = simulation(5);
+		let mut large = simulation(9);
+
+		for animal in &large.world.animals {
+			assert_eq!(animal.eye.cells(), 9);
+			assert_eq!(animal.as_chromosome().len(), Brain::chromosome_len(&animal.eye));
+		}
+
+		assert_eq!(small.world.animals[0].eye.cells(), 5);
+
+		let small_params = small.world.animals[0].brain.nn.param_count();
+		assert!(large.world.animals[0].brain.nn.param_count() > small_params);
+
+		// Animals born during evolution get the same eyes
+		large.world.animals[0].satiation = 1;
+		large.evolve(&mut ChaCha8Rng::seed_from_u64(1));
+		assert!(large.world.animals.iter().all(|animal| animal.eye.cells() == 9));
+	}
+
+	#[test]
+	fn is_converged() {
+		let mut simulation = Simulation::from_seed(0);
+		assert!(!simulation.is_converged(5, 0.1));
+
+		simulation.history = history([3, 9, 7, 7, 7, 7, 7]);
+		assert!(simulation.is_converged(5, 0.1));
+		assert!(!simulation.is_converged(6, 0.1));
+
+		simulation.history = history(0..10);
+		assert!(!simulation.is_converged(5, 0.1));
+	}
+
+	#[test]
+	fn is_converged_follows_satiation_rather_than_fitness() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+
+		// Fitness keeps climbing with the distance traveled, while the best
+		// satiation stays put
+		simulation.set_fitness_fn(FitnessFn::custom(|animal| animal.distance_traveled()));
+
+		for i in 0..5 {
+			simulation.world.animals[0].satiation = 3;
+			simulation.world.animals[0].distance_traveled = i as f32;
+			simulation.evolve(&mut rng);
+		}
+
+		let fitnesses: Vec<_> = simulation.history.iter().map(|stats| stats.max_fitness).collect();
+		assert!(fitnesses.windows(2).any(|pair| pair[0] != pair[1]), "{:?}", fitnesses);
+		assert!(simulation.history.iter().all(|stats| stats.best_satiation == 3));
+		assert!(simulation.is_converged(5, 0.1));
+	}
+
+	#[test]
+	#[should_panic(expected = "got a window of 6 generations, but only 5 are kept")]
+	fn is_converged_needs_the_window_kept() {
+		let config = SimulationConfig {
+			max_history: 5,
+			..Default::default()
+		};
+
+		Simulation::new(config, &mut ChaCha8Rng::seed_from_u64(0)).is_converged(6, 0.1);
+	}
+
+	#[test]
+	fn evolve_records_history() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+		simulation.world.animals[0].satiation = 4;
+		simulation.world.animals[1].satiation = 2;
+
+		simulation.evolve(&mut rng);
+
+		assert_eq!(simulation.history.len(), 1);
+		assert_eq!(simulation.history[0].generation, 1);
+		assert_eq!(simulation.history[0].max_fitness, 4.0);
+	}
+
+	#[test]
+	fn stats_history() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+		assert_eq!(simulation.latest_stats(), None);
+
+		simulation.train(&mut rng);
+		simulation.train(&mut rng);
+
+		let history = simulation.stats_history();
+		assert_eq!(history.len(), 2);
+		assert_eq!(simulation.latest_stats(), history.last());
+
+		for stats in history {
+			assert!(stats.max_fitness >= stats.mean_fitness);
+			assert!(stats.mean_fitness >= stats.min_fitness);
+			assert!((stats.min_fitness..=stats.max_fitness).contains(&stats.median_fitness));
+		}
+	}
+
+	#[test]
+	fn stats_history_is_capped() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let config = SimulationConfig {
+			max_history: 2,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut rng);
+
+		for _ in 0..3 {
+			simulation.world.animals[0].satiation = 1;
+			simulation.evolve(&mut rng);
+		}
+
+		let generations: Vec<_> = simulation.stats_history().iter().map(|stats| stats.generation).collect();
+		assert_eq!(generations, vec![2, 3]);
+	}
+
+	#[test]
+	fn step_reports_generation_rollover() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+
+		for _ in 1..STEP_EACH_GENERATION {
+			assert_eq!(simulation.step(&mut rng), None);
+		}
+
+		assert!(simulation.is_last_run());
+
+		let stats = simulation.step(&mut rng).unwrap();
+		assert_eq!(stats.generation, 1);
+		assert_eq!(simulation.generation(), 2);
+		assert_eq!(simulation.age(), 0);
+		assert_eq!(simulation.latest_stats(), Some(&stats));
+	}
+
+	#[test]
+	fn train() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+
+		// Halfway through a generation, only the rest of it gets run
+		for _ in 0..500 {
+			simulation.step(&mut rng);
+		}
+
+		let first = simulation.train(&mut rng);
+		let second = simulation.train(&mut rng);
+
+		assert_eq!(simulation.generation(), 3);
+		assert_eq!(simulation.age(), 0);
+		assert_eq!((first.generation, second.generation), (1, 2));
+		assert_eq!(simulation.history, vec![first, second]);
+	}
+
+	#[test]
+	fn train_until_converged() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+		let detector = ga::ConvergenceDetector::new(1, 0.0);
+
+		assert_eq!(simulation.train_until_converged(&mut rng, &detector, 10), Some(1));
+		assert_eq!(simulation.age(), 0);
+		assert_eq!(simulation.generation(), 2);
+	}
+
+	#[test]
+	fn train_until_converged_gives_up() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+		let detector = ga::ConvergenceDetector::new(3, 0.0);
+
+		assert_eq!(simulation.train_until_converged(&mut rng, &detector, 2), None);
+		assert_eq!(simulation.generation(), 3);
+		assert_eq!(simulation.stats_history().len(), 2);
+	}
+}
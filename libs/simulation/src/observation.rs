@@ -0,0 +1,122 @@
+use crate::*;
+
+/// What the animals currently see and know, e.g. for an external agent to
+/// pick their actions; see `Simulation::observe()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationObservation {
+	/// `[x, y, rotation]` of every animal
+	pub animal_positions: Vec<[f32; 3]>,
+	/// `[x, y]` of every food
+	pub food_positions: Vec<[f32; 2]>,
+	/// What every animal's eye sees, i.e. its brain's inputs
+	pub visions: Vec<Vec<f32>>,
+	pub satiations: Vec<usize>,
+}
+
+impl Simulation {
+	pub fn observe(&self) -> SimulationObservation {
+		let animals = &self.world.animals;
+
+		SimulationObservation {
+			animal_positions: animals
+				.iter()
+				.map(|animal| [animal.position.x, animal.position.y, animal.rotation.angle()])
+				.collect(),
+			food_positions: self
+				.world
+				.foods
+				.iter()
+				.map(|food| [food.position.x, food.position.y])
+				.collect(),
+			visions: animals
+				.iter()
+				.map(|animal| {
+					animal
+						.eye
+						.process_vision(animal.position, animal.rotation, &self.world.foods)
+				})
+				.collect(),
+			satiations: animals.iter().map(|animal| animal.satiation).collect(),
+		}
+	}
+
+	/// Same as `step()`, but with every animal's change of speed and
+	/// rotation given by `actions` instead of its brain; they're limited
+	/// the same way brains' outputs are.
+	pub fn act(&mut self, rng: &mut dyn RngCore, actions: &[(f32, f32)]) -> Option<SimulationStats> {
+		assert_eq!(
+			actions.len(),
+			self.world.animals.len(),
+			"got a different number of actions than animals",
+		);
+
+		self.process_step(rng, Some(actions))?;
+		self.latest_stats().cloned()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn observe() {
+		let simulation = Simulation::from_seed(0);
+		let observation = simulation.observe();
+		let animal = &simulation.world().animals()[3];
+
+		assert_eq!(observation.animal_positions.len(), simulation.population_size());
+		assert_eq!(
+			observation.animal_positions[3],
+			[animal.position().x, animal.position().y, animal.rotation().angle()],
+		);
+		assert_eq!(observation.food_positions.concat(), simulation.food_position_buffer());
+		assert_eq!(
+			observation.visions[3],
+			animal.eye.process_vision(animal.position, animal.rotation, simulation.world().food()),
+		);
+		assert_eq!(observation.satiations, vec![0; simulation.population_size()]);
+	}
+
+	#[test]
+	fn act_without_change() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+		let before = simulation.observe();
+		let speeds: Vec<_> = simulation.world().animals().iter().map(Animal::speed).collect();
+
+		simulation.act(&mut rng, &vec![(0.0, 0.0); simulation.population_size()]);
+
+		let after = simulation.observe();
+
+		for (animal, speed) in simulation.world().animals().iter().zip(speeds) {
+			assert_eq!(animal.speed(), speed);
+		}
+
+		for (before, after) in before.animal_positions.iter().zip(&after.animal_positions) {
+			assert!((before[2] - after[2]).abs() < 1e-6);
+			assert_ne!(before[..2], after[..2]);
+		}
+	}
+
+	#[test]
+	fn act_limits_actions() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+
+		simulation.act(&mut rng, &vec![(1.0, 0.0); simulation.population_size()]);
+		assert!(simulation.world().animals().iter().all(|animal| animal.speed() == SPEED_MAX));
+
+		simulation.act(&mut rng, &vec![(-1.0, 0.0); simulation.population_size()]);
+		assert!(simulation.world().animals().iter().all(|animal| animal.speed() == SPEED_MIN));
+	}
+
+	#[test]
+	#[should_panic(expected = "got a different number of actions than animals")]
+	fn act_needs_an_action_per_animal() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+
+		simulation.act(&mut rng, &[(0.0, 0.0)]);
+	}
+}
@@ -0,0 +1,139 @@
+use crate::*;
+
+/// A simulation bundled with its own seeded rng, so that stepping it
+/// doesn't need one passed in; the same seed always plays out the same way.
+pub struct SeededSimulation {
+	rng: ChaCha8Rng,
+	simulation: Simulation,
+}
+
+impl SeededSimulation {
+	pub fn new(seed: u64) -> Self {
+		Self::with_config(seed, SimulationConfig::default())
+	}
+
+	/// Panics if `config` is invalid, see `Simulation::try_new()`.
+	pub fn with_config(seed: u64, config: SimulationConfig) -> Self {
+		let mut rng = ChaCha8Rng::seed_from_u64(seed);
+		let simulation = Simulation::new(config, &mut rng);
+
+		Self { rng, simulation }
+	}
+
+	pub fn simulation(&self) -> &Simulation {
+		&self.simulation
+	}
+
+	pub fn simulation_mut(&mut self) -> &mut Simulation {
+		&mut self.simulation
+	}
+
+	/// See `Simulation::step()`.
+	pub fn step(&mut self) -> Option<SimulationStats> {
+		self.simulation.step(&mut self.rng)
+	}
+
+	/// See `Simulation::train()`.
+	pub fn train(&mut self) -> SimulationStats {
+		self.simulation.train(&mut self.rng)
+	}
+}
+
+// Odd 64-bit constant (2^64 / golden ratio) that spreads consecutive
+// generations far apart before they get mixed into the seed
+const GENERATION_MIX: u64 = 0x9e37_79b9_7f4a_7c15;
+
+impl Simulation {
+	/// Derives the rng of a single generation from `master_seed`, so that a
+	/// generation can be replayed (e.g. from a snapshot taken when it
+	/// began) without replaying every one before it; see
+	/// `step_deterministic()`.
+	pub fn rng_for_generation(master_seed: u64, generation: usize) -> ChaCha8Rng {
+		ChaCha8Rng::seed_from_u64(master_seed ^ (generation as u64).wrapping_mul(GENERATION_MIX))
+	}
+
+	/// Same as `step()`, but only with an rng from `rng_for_generation()`,
+	/// which is to be used for the whole generation and nothing else.
+	pub fn step_deterministic(&mut self, generation_rng: &mut ChaCha8Rng) -> Option<SimulationStats> {
+		self.step(generation_rng)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn positions(simulation: &SeededSimulation) -> Vec<(u32, u32, u32)> {
+		simulation
+			.simulation()
+			.world()
+			.animals()
+			.iter()
+			.map(|animal| {
+				(
+					animal.position().x.to_bits(),
+					animal.position().y.to_bits(),
+					animal.rotation().angle().to_bits(),
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn same_seed_plays_out_the_same() {
+		let mut a = SeededSimulation::new(42);
+		let mut b = SeededSimulation::new(42);
+
+		for _ in 0..500 {
+			assert_eq!(a.step(), b.step());
+		}
+
+		assert_eq!(positions(&a), positions(&b));
+		assert_eq!(a.simulation().food_position_buffer(), b.simulation().food_position_buffer());
+
+		let mut c = SeededSimulation::new(43);
+
+		for _ in 0..500 {
+			c.step();
+		}
+
+		assert_ne!(positions(&a), positions(&c));
+	}
+
+	#[test]
+	fn rng_for_generation() {
+		let rng = |seed, generation| Simulation::rng_for_generation(seed, generation).gen::<u64>();
+
+		assert_eq!(rng(42, 3), rng(42, 3));
+		assert_ne!(rng(42, 3), rng(42, 4));
+		assert_ne!(rng(42, 3), rng(43, 3));
+	}
+
+	#[test]
+	fn generations_replay_from_their_rng() {
+		const SEED: u64 = 42;
+
+		let config = SimulationConfig {
+			animal_count: 10,
+			steps_per_generation: 20,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, &mut Simulation::rng_for_generation(SEED, 0));
+
+		// Play out the first generation, then replay the second one in two
+		// independent instances
+		let mut rng = Simulation::rng_for_generation(SEED, simulation.generation());
+
+		while simulation.step_deterministic(&mut rng).is_none() {}
+
+		let snapshot = simulation.snapshot();
+		let mut a = Simulation::restore(snapshot.clone(), &mut ChaCha8Rng::seed_from_u64(0));
+		let mut b = Simulation::restore(snapshot, &mut ChaCha8Rng::seed_from_u64(1));
+
+		a.step_deterministic(&mut Simulation::rng_for_generation(SEED, a.generation()));
+		b.step_deterministic(&mut Simulation::rng_for_generation(SEED, b.generation()));
+
+		assert_eq!(a.generation(), 2);
+		assert_eq!(a.snapshot(), b.snapshot());
+	}
+}
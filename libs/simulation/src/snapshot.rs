@@ -0,0 +1,285 @@
+use crate::*;
+
+/// Everything needed to replay a generation: the animals' genes and where
+/// the food was.
+#[derive(Clone, Debug)]
+pub struct GenerationSnapshot {
+	pub animal_chromosomes: Vec<ga::Chromosome>,
+	pub food_positions: Vec<na::Point2<f32>>,
+	/// Kind of each food in `food_positions`; missing ones are nutritious
+	pub food_kinds: Vec<FoodKind>,
+	pub generation: usize,
+}
+
+/// Everything needed to carry on with a simulation later on, e.g. after a
+/// restart; see `Simulation::snapshot()`. Only the rng isn't included.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationSnapshot {
+	pub config: SimulationConfig,
+	pub animals: Vec<AnimalSnapshot>,
+	pub predators: Vec<AnimalSnapshot>,
+	pub food_positions: Vec<na::Point2<f32>>,
+	/// Kind of each food in `food_positions`; missing ones are nutritious
+	pub food_kinds: Vec<FoodKind>,
+	pub generation: usize,
+	pub age: usize,
+	pub history: Vec<SimulationStats>,
+	pub total_food_eaten: usize,
+	pub foods_eaten_this_generation: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnimalSnapshot {
+	pub position: na::Point2<f32>,
+	pub rotation: na::Rotation2<f32>,
+	pub speed: f32,
+	pub eye: EyeConfig,
+	/// The brain's weights, as in `Brain::as_chromosome()`
+	pub genes: Vec<f32>,
+	pub satiation: usize,
+	pub age: usize,
+	pub distance_traveled: f32,
+	pub energy: f32,
+	pub steps_alive: usize,
+	pub times_caught: usize,
+}
+
+impl Simulation {
+	pub fn snapshot(&self) -> SimulationSnapshot {
+		SimulationSnapshot {
+			config: self.config.clone(),
+			animals: self.world.animals.iter().map(AnimalSnapshot::new).collect(),
+			predators: self.world.predators.iter().map(AnimalSnapshot::new).collect(),
+			food_positions: self.world.foods.iter().map(Food::position).collect(),
+			food_kinds: self.world.foods.iter().map(Food::kind).collect(),
+			generation: self.generation(),
+			age: self.age,
+			history: self.history.clone(),
+			total_food_eaten: self.total_food_eaten,
+			foods_eaten_this_generation: self.foods_eaten_this_generation,
+		}
+	}
+
+	/// Rebuilds the simulation exactly as it was when `snapshot` was taken,
+	/// except for the rng and the fitness function, which are up to the
+	/// caller.
+	///
+	/// Panics if the snapshot is inconsistent, e.g. has genes that don't fit
+	/// an animal's eye.
+	pub fn restore(snapshot: SimulationSnapshot, rng: &mut dyn RngCore) -> Self {
+		assert!(!snapshot.animals.is_empty(), "got no animals");
+
+		let animals = snapshot
+			.animals
+			.into_iter()
+			.map(|saved| saved.into_animal(rng))
+			.collect();
+
+		let predators = snapshot
+			.predators
+			.into_iter()
+			.map(|saved| saved.into_animal(rng))
+			.collect();
+
+		let foods = foods_from(&snapshot.food_positions, &snapshot.food_kinds);
+
+		let world = World {
+			animals,
+			foods,
+			predators,
+		};
+
+		let mut simulation = Self::with_world(snapshot.config, world);
+		simulation.ga = simulation.ga.with_generation(snapshot.generation);
+		simulation.age = snapshot.age;
+		simulation.history = snapshot.history;
+		simulation.total_food_eaten = snapshot.total_food_eaten;
+		simulation.foods_eaten_this_generation = snapshot.foods_eaten_this_generation;
+		simulation
+	}
+
+	pub fn snapshot_generation(&self) -> GenerationSnapshot {
+		GenerationSnapshot {
+			animal_chromosomes: self.world.animals.iter().map(Animal::as_chromosome).collect(),
+			food_positions: self.world.foods.iter().map(Food::position).collect(),
+			food_kinds: self.world.foods.iter().map(Food::kind).collect(),
+			generation: self.generation(),
+		}
+	}
+
+	/// Rebuilds the simulation from `snapshot`, placing the animals at new
+	/// random positions; the food is put back where it was. Predators
+	/// aren't part of such snapshots, so they start anew.
+	pub fn from_snapshot(
+		rng: &mut dyn RngCore,
+		snapshot: &GenerationSnapshot,
+		config: SimulationConfig,
+	) -> Self {
+		assert!(!snapshot.animal_chromosomes.is_empty(), "got no animals");
+
+		let animals = snapshot
+			.animal_chromosomes
+			.iter()
+			.map(|chromosome| {
+				Animal::from_chromosome(chromosome.clone(), Eye::from_config(&config.eye), rng)
+			})
+			.collect();
+
+		let foods = foods_from(&snapshot.food_positions, &snapshot.food_kinds);
+
+		let world = World {
+			animals,
+			foods,
+			predators: random_predators(rng, &config),
+		};
+
+		let mut simulation = Self::with_world(config, world);
+		simulation.ga = simulation.ga.with_generation(snapshot.generation);
+		simulation
+	}
+}
+
+impl AnimalSnapshot {
+	fn new(animal: &Animal) -> Self {
+		Self {
+			position: animal.position,
+			rotation: animal.rotation,
+			speed: animal.speed,
+			eye: animal.eye.config(),
+			genes: animal.as_chromosome().iter().copied().collect(),
+			satiation: animal.satiation,
+			age: animal.age,
+			distance_traveled: animal.distance_traveled,
+			energy: animal.energy,
+			steps_alive: animal.steps_alive,
+			times_caught: animal.times_caught,
+		}
+	}
+
+	fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
+		let eye = Eye::from_config(&self.eye);
+		let mut animal = Animal::from_chromosome(ga::Chromosome::new(self.genes), eye, rng);
+
+		animal.position = self.position;
+		animal.rotation = self.rotation;
+		animal.speed = self.speed;
+		animal.satiation = self.satiation;
+		animal.age = self.age;
+		animal.distance_traveled = self.distance_traveled;
+		animal.energy = self.energy;
+		animal.steps_alive = self.steps_alive;
+		animal.times_caught = self.times_caught;
+		animal
+	}
+}
+
+fn foods_from(positions: &[na::Point2<f32>], kinds: &[FoodKind]) -> Vec<Food> {
+	positions
+		.iter()
+		.enumerate()
+		.map(|(i, &position)| Food::with_kind(position, kinds.get(i).copied().unwrap_or_default()))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_same_simulation(a: &mut Simulation, b: &mut Simulation) {
+		assert_eq!(a.generation(), b.generation());
+		assert_eq!(a.age(), b.age());
+		assert_eq!(a.stats_history(), b.stats_history());
+		assert_eq!(a.total_food_eaten(), b.total_food_eaten());
+		assert_eq!(a.food_position_buffer(), b.food_position_buffer());
+		assert_eq!(a.animal_position_buffer(), b.animal_position_buffer());
+
+		let inputs = vec![0.5; a.world.animals[0].eye.input_count()];
+
+		for (a, b) in a.world.animals.iter_mut().zip(&mut b.world.animals) {
+			assert_eq!(a.satiation, b.satiation);
+			assert_eq!(a.age, b.age);
+			assert_eq!(a.speed, b.speed);
+			assert_eq!(a.brain.nn.propagate(inputs.clone()), b.brain.nn.propagate(inputs.clone()));
+		}
+	}
+
+	fn simulation_mid_generation(rng: &mut dyn RngCore) -> Simulation {
+		let config = SimulationConfig {
+			animal_count: 10,
+			predator_count: 2,
+			steps_per_generation: 50,
+			..Default::default()
+		};
+		let mut simulation = Simulation::new(config, rng);
+
+		for _ in 0..80 {
+			simulation.step(rng);
+		}
+
+		simulation
+	}
+
+	#[test]
+	fn snapshot_and_restore() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = simulation_mid_generation(&mut rng);
+		assert_eq!(simulation.age(), 30);
+
+		let snapshot = simulation.snapshot();
+		let mut restored = Simulation::restore(snapshot.clone(), &mut rng);
+
+		assert_same_simulation(&mut simulation, &mut restored);
+		assert_eq!(restored.snapshot(), snapshot);
+
+		// Both carry on the same way, given the same rng
+		let mut rngs = (ChaCha8Rng::seed_from_u64(1), ChaCha8Rng::seed_from_u64(1));
+
+		for _ in 0..30 {
+			simulation.world.animals[0].satiation += 1;
+			restored.world.animals[0].satiation += 1;
+
+			assert_eq!(simulation.step(&mut rngs.0), restored.step(&mut rngs.1));
+		}
+
+		assert_same_simulation(&mut simulation, &mut restored);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serialize_snapshot() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = simulation_mid_generation(&mut rng);
+
+		let json = serde_json::to_string(&simulation.snapshot()).unwrap();
+		let snapshot: SimulationSnapshot = serde_json::from_str(&json).unwrap();
+		let mut restored = Simulation::restore(snapshot, &mut rng);
+
+		assert_same_simulation(&mut simulation, &mut restored);
+	}
+
+	#[test]
+	fn from_snapshot() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+		let mut simulation = Simulation::random(&mut rng);
+
+		for _ in 0..4 {
+			simulation.evolve(&mut rng);
+		}
+
+		let snapshot = simulation.snapshot_generation();
+		assert_eq!(snapshot.generation, 5);
+
+		let restored = Simulation::from_snapshot(&mut rng, &snapshot, SimulationConfig::default());
+		assert_eq!(restored.generation(), 5);
+
+		for (a, b) in simulation.world.animals.iter().zip(&restored.world.animals) {
+			assert_eq!(a.brain.nn, b.brain.nn);
+		}
+
+		let positions: Vec<_> = restored.world.foods.iter().map(Food::position).collect();
+		assert_eq!(positions, snapshot.food_positions);
+		assert_eq!(restored.world.animals.len(), simulation.world.animals.len());
+	}
+}
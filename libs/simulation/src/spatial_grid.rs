@@ -0,0 +1,125 @@
+use crate::*;
+
+/// Buckets points of the map into square cells, so that finding the ones
+/// near a spot doesn't need to look at all of them.
+///
+/// Points outside the map (e.g. moved there by hand) go into the nearest
+/// cell on its edge and are still found.
+#[derive(Clone, Debug)]
+pub struct SpatialGrid {
+	cell_size: f32,
+	// Number of cells along each side of the map
+	size: usize,
+	// Indices of the points in each cell, row by row
+	cells: Vec<Vec<usize>>,
+	points: Vec<na::Point2<f32>>,
+}
+
+impl SpatialGrid {
+	/// Panics if `cell_size` isn't positive.
+	pub fn new(cell_size: f32, points: impl IntoIterator<Item = na::Point2<f32>>) -> Self {
+		assert!(cell_size > 0.0, "got an invalid cell size of {}", cell_size);
+
+		let size = ((1.0 / cell_size).ceil() as usize).max(1);
+
+		let mut grid = Self {
+			cell_size,
+			size,
+			cells: vec![Vec::new(); size * size],
+			points: points.into_iter().collect(),
+		};
+
+		for (index, point) in grid.points.iter().enumerate() {
+			let cell = grid.cell(point.y) * size + grid.cell(point.x);
+			grid.cells[cell].push(index);
+		}
+
+		grid
+	}
+
+	/// Returns the indices of the points closer than `radius` to `center`,
+	/// in ascending order, i.e. the same ones in the same order as checking
+	/// every point would.
+	pub fn query_circle(&self, center: na::Point2<f32>, radius: f32) -> Vec<usize> {
+		let (min_x, max_x) = (self.cell(center.x - radius), self.cell(center.x + radius));
+		let (min_y, max_y) = (self.cell(center.y - radius), self.cell(center.y + radius));
+
+		// One bit per point, which puts them back in order far cheaper than
+		// sorting would
+		let mut found = vec![0u64; (self.points.len() + 63) / 64];
+
+		for y in min_y..=max_y {
+			for x in min_x..=max_x {
+				for &index in &self.cells[y * self.size + x] {
+					if (self.points[index] - center).norm() < radius {
+						found[index / 64] |= 1 << (index % 64);
+					}
+				}
+			}
+		}
+
+		found
+			.into_iter()
+			.enumerate()
+			.flat_map(|(word_index, mut word)| {
+				std::iter::from_fn(move || {
+					(word != 0).then(|| {
+						let bit = word.trailing_zeros() as usize;
+						word &= word - 1;
+						word_index * 64 + bit
+					})
+				})
+			})
+			.collect()
+	}
+
+	// Float-to-int casts saturate, so whatever lies below the map ends up
+	// in the first cell
+	fn cell(&self, coordinate: f32) -> usize {
+		((coordinate / self.cell_size) as usize).min(self.size - 1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn brute_force(points: &[na::Point2<f32>], center: na::Point2<f32>, radius: f32) -> Vec<usize> {
+		(0..points.len())
+			.filter(|&index| (points[index] - center).norm() < radius)
+			.collect()
+	}
+
+	#[test]
+	fn matches_brute_force() {
+		let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+		for cell_size in [0.01, 0.1, 0.25, 0.3, 1.0, 2.0] {
+			let points: Vec<na::Point2<f32>> = (0..500).map(|_| rng.gen()).collect();
+			let grid = SpatialGrid::new(cell_size, points.iter().copied());
+
+			for _ in 0..100 {
+				let center = rng.gen();
+				let radius = rng.gen_range(0.0..0.5);
+
+				assert_eq!(grid.query_circle(center, radius), brute_force(&points, center, radius));
+			}
+		}
+	}
+
+	#[test]
+	fn points_outside_the_map() {
+		let points = [na::Point2::new(-0.05, 0.5), na::Point2::new(1.5, 1.5), na::Point2::new(0.5, 0.5)];
+		let grid = SpatialGrid::new(0.25, points);
+
+		assert_eq!(grid.query_circle(na::Point2::new(0.01, 0.5), 0.1), vec![0]);
+		assert_eq!(grid.query_circle(na::Point2::new(1.4, 1.4), 0.2), vec![1]);
+		assert_eq!(grid.query_circle(na::Point2::new(-10.0, -10.0), 0.1), Vec::<usize>::new());
+	}
+
+	#[test]
+	#[should_panic(expected = "got an invalid cell size of 0")]
+	fn rejects_empty_cells() {
+		SpatialGrid::new(0.0, []);
+	}
+}
@@ -1,19 +1,86 @@
 use crate::*;
+use rand::seq::SliceRandom;
+
+pub(crate) const ANIMAL_COUNT: usize = 40;
+pub(crate) const FOOD_COUNT: usize = 60;
 
 #[derive(Debug)]
 pub struct World { 
 	pub(crate) animals: Vec<Animal>,
-	pub(crate) foods: Vec<Food>
+	pub(crate) foods: Vec<Food>,
+	pub(crate) predators: Vec<Animal>,
 }
 
 impl World {
 	pub fn random(rng: &mut dyn RngCore) -> Self {
-		let animals = (0..40).map(|_| Animal::random(rng)).collect();
-		let foods = (0..60).map(|_| Food::random(rng)).collect();
+		Self::random_with_count(rng, ANIMAL_COUNT, FOOD_COUNT)
+	}
+
+	/// Panics if either count is zero.
+	pub fn random_with_count(rng: &mut dyn RngCore, animal_count: usize, food_count: usize) -> Self {
+		Self::random_with(rng, animal_count, 0.0, &EyeConfig::default(), |rng| {
+			random_foods(rng, food_count)
+		})
+	}
+
+	/// Same as `random_with_count()`, with the counts taken from `config`
+	/// and animals whose eyes (and so brains) are shaped by it.
+	pub fn random_with_config(rng: &mut dyn RngCore, config: &SimulationConfig) -> Self {
+		let mut world = Self::random_with(rng, config.animal_count, config.poison_ratio, &config.eye, |rng| {
+			random_foods(rng, config.food_count)
+		});
+
+		world.predators = random_predators(rng, config);
+		world
+	}
+
+	/// Same as `random_with_config()`, but with the food placed within the
+	/// grid's zones (where it respawns once eaten) instead of
+	/// `config.food_count` pieces spread all over the world.
+	///
+	/// Panics if the grid has no food.
+	pub fn random_with_food_grid(rng: &mut dyn RngCore, config: &SimulationConfig, grid: &FoodGrid) -> Self {
+		let mut world = Self::random_with(rng, config.animal_count, config.poison_ratio, &config.eye, |rng| {
+			// Mixed up, so that poison doesn't all end up in the last zone
+			let mut foods = grid.random_foods(rng);
+			foods.shuffle(rng);
+			foods
+		});
+
+		world.predators = random_predators(rng, config);
+		world
+	}
+
+	fn random_with(
+		rng: &mut dyn RngCore,
+		animal_count: usize,
+		poison_ratio: f32,
+		eye: &EyeConfig,
+		foods: impl FnOnce(&mut dyn RngCore) -> Vec<Food>,
+	) -> Self {
+		assert!(animal_count > 0, "got no animals");
+
+		let animals = (0..animal_count)
+			.map(|_| Animal::random_with_eye(rng, Eye::from_config(eye)))
+			.collect();
+		let mut foods = foods(rng);
+
+		assert!(!foods.is_empty(), "got no food");
+
+		// Positions are random already, so it doesn't matter which ones
+		let poison_count = (foods.len() as f32 * poison_ratio).round() as usize;
 
-		Self { animals, foods }
+		for food in foods.iter_mut().rev().take(poison_count) {
+			food.kind = FoodKind::Poison;
+		}
+
+		Self {
+			animals,
+			foods,
+			predators: Vec::new(),
+		}
 	}
-	
+
 	pub fn animals(&self) -> &[Animal] {
 		&self.animals
 	}
@@ -21,4 +88,51 @@ impl World {
 	pub fn food(&self) -> &[Food] {
 		&self.foods
 	}
-}
\ No newline at end of file
+
+	/// Animals that hunt the others instead of eating food; empty unless
+	/// `SimulationConfig::predator_count` says otherwise.
+	pub fn predators(&self) -> &[Animal] {
+		&self.predators
+	}
+
+	pub fn set_animal_position(&mut self, index: usize, position: na::Point2<f32>) {
+		self.animals[index].position = position;
+	}
+
+	pub fn set_food_position(&mut self, index: usize, position: na::Point2<f32>) {
+		self.foods[index].position = position;
+	}
+
+	pub fn set_predator_position(&mut self, index: usize, position: na::Point2<f32>) {
+		self.predators[index].position = position;
+	}
+}
+
+fn random_foods(rng: &mut dyn RngCore, count: usize) -> Vec<Food> {
+	(0..count).map(|_| Food::random(rng)).collect()
+}
+
+pub(crate) fn random_predators(rng: &mut dyn RngCore, config: &SimulationConfig) -> Vec<Animal> {
+	(0..config.predator_count)
+		.map(|_| Animal::random_with_eye(rng, Eye::from_config(&config.predator_eye)))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn random_with_count() {
+		let world = World::random_with_count(&mut ChaCha8Rng::seed_from_u64(0), 7, 15);
+
+		assert_eq!(world.animals().len(), 7);
+		assert_eq!(world.food().len(), 15);
+	}
+
+	#[test]
+	#[should_panic(expected = "got no animals")]
+	fn random_with_count_rejects_zero_animals() {
+		World::random_with_count(&mut ChaCha8Rng::seed_from_u64(0), 0, 15);
+	}
+}
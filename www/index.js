@@ -3,6 +3,14 @@ import * as sim from "lib-simulation-wasm"
 const simulation = new sim.Simulation();
 const world = simulation.world();
 
+function logStats(stats) {
+  console.log(`Generation ${stats.generation} - Average fitness: ${stats.mean_fitness}, Max fitness: ${stats.max_fitness}`);
+}
+
+document.getElementById("train").onclick = function() {
+  logStats(simulation.train());
+};
+
 const viewport = document.getElementById("viewport");
 const viewportWidth = viewport.width;
 const viewportHeight = viewport.height;
@@ -16,7 +24,7 @@ viewport.style.height = viewportHeight + "px";
 const ctxt = viewport.getContext("2d");
 ctxt.scale(viewportScale, viewportScale);
 
-function drawTriangle(ctxt, x, y, size, rotation) {
+function drawTriangle(ctxt, x, y, size, rotation, color) {
   ctxt.beginPath();
   ctxt.moveTo(x - Math.sin(rotation) * 1.5, y + Math.cos(rotation) * 1.5);
   ctxt.lineTo(
@@ -28,31 +36,24 @@ function drawTriangle(ctxt, x, y, size, rotation) {
     y + Math.cos(rotation + 4.0 / 3.0 * Math.PI) * size,
   );
   ctxt.lineTo(x - Math.sin(rotation) * 1.5, y + Math.cos(rotation) * 1.5);
-  ctxt.fillStyle = 'rgb(255, 255, 255)';
+  ctxt.fillStyle = color;
   ctxt.fill();
 }
 
-function drawCircle(ctxt, x, y, radius) {
+function drawCircle(ctxt, x, y, radius, color) {
   ctxt.beginPath();
   ctxt.arc(x, y, radius, 0, 2.0 * Math.PI);
-  ctxt.fillStyle = 'rgb(0, 255, 128)';
+  ctxt.fillStyle = color;
   ctxt.fill();
 }
 
 function redraw() {
   ctxt.clearRect(0, 0, viewportWidth, viewportHeight);
   const world = simulation.world();
-  if (simulation.is_last_run()) {
-    let avg_fitness = 0;
-    let max_fitness = 0;
-    for (const animal of world.animals) {
-      avg_fitness += animal.fitness;
-      max_fitness = Math.max(max_fitness, animal.fitness);
-    }
-    avg_fitness /= world.animals.length;
-    console.log(`Generation ${simulation.generation()} - Average fitness: ${avg_fitness}, Max fitness: ${max_fitness}`);
+  const stats = simulation.step();
+  if (stats) {
+    logStats(stats);
   }
-  simulation.step();
 
   for (const food of world.foods) {
     drawCircle(
@@ -60,6 +61,7 @@ function redraw() {
       food.x * viewportWidth,
       food.y * viewportHeight,
       0.003 * viewportWidth,
+      food.poison ? 'rgb(255, 64, 64)' : 'rgb(0, 255, 128)',
     );
   }
 
@@ -70,6 +72,18 @@ function redraw() {
       animal.y * viewportHeight,
       0.01 * viewportWidth,
       animal.rotation,
+      animal.alive ? 'rgb(255, 255, 255)' : 'rgb(96, 96, 96)',
+    );
+  }
+
+  for (const predator of world.predators) {
+    drawTriangle(
+      ctxt,
+      predator.x * viewportWidth,
+      predator.y * viewportHeight,
+      0.015 * viewportWidth,
+      predator.rotation,
+      predator.alive ? 'rgb(255, 160, 0)' : 'rgb(96, 96, 96)',
     );
   }
 